@@ -0,0 +1,86 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use rand::RngCore;
+
+// Hector's scalar decoder can be configured with features,
+// to compare them run this with `--save-baseline` and then again with `--baseline`, for instance:
+// `cargo bench --bench decode-compare --features force-fallback -- --save-baseline arithmetic`
+// `cargo bench --bench decode-compare --features force-fallback,lut -- --baseline arithmetic`
+fn bench(c: &mut Criterion) {
+    const SIZES: [usize; 6] = [2, 8, 32, 128, 512, 2048];
+    {
+        let mut group = c.benchmark_group("decode-to-vec");
+
+        for size in SIZES {
+            let mut input = vec![0; size];
+
+            rand::thread_rng().fill_bytes(&mut input);
+
+            let input = hex::encode(input);
+
+            group
+                .throughput(Throughput::Bytes(input.len() as u64))
+                .bench_with_input(BenchmarkId::new("hex", input.len()), &input, |b, input| {
+                    b.iter(|| hex::decode(input).unwrap())
+                })
+                .bench_with_input(
+                    BenchmarkId::new("hector", input.len()),
+                    &input,
+                    |b, input| b.iter(|| hector::decode(input).unwrap()),
+                );
+        }
+    }
+
+    let mut group = c.benchmark_group("decode-to-slice");
+
+    for size in SIZES {
+        let mut input = vec![0; size];
+
+        rand::thread_rng().fill_bytes(&mut input);
+
+        let input = hex::encode(input);
+
+        group
+            .throughput(Throughput::Bytes(input.len() as u64))
+            .bench_with_input(BenchmarkId::new("hex", input.len()), &input, |b, input| {
+                b.iter_batched(
+                    || (input, vec![0; size]),
+                    |(input, mut output)| {
+                        hex::decode_to_slice(input, &mut output).unwrap();
+                        output
+                    },
+                    BatchSize::SmallInput,
+                )
+            })
+            .bench_with_input(
+                BenchmarkId::new("faster-hex", input.len()),
+                &input,
+                |b, input| {
+                    b.iter_batched(
+                        || (input, vec![0; size]),
+                        |(input, mut output)| {
+                            faster_hex::hex_decode(input.as_bytes(), &mut output).unwrap();
+                            output
+                        },
+                        BatchSize::SmallInput,
+                    )
+                },
+            )
+            .bench_with_input(
+                BenchmarkId::new("hector", input.len()),
+                &input,
+                |b, input| {
+                    b.iter_batched(
+                        || (input, vec![0; size]),
+                        |(input, mut output)| {
+                            hector::decode_to_slice(input, &mut output).unwrap();
+                            output
+                        },
+                        BatchSize::SmallInput,
+                    )
+                },
+            );
+    }
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
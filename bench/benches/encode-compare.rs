@@ -15,17 +15,48 @@ fn bench(c: &mut Criterion) {
             group
                 .throughput(Throughput::Bytes(input.len() as u64))
                 .bench_with_input(BenchmarkId::new("hex", input.len()), &input, |b, input| {
-                    b.iter(|| hex::encode(&input))
+                    b.iter(|| hex::encode(input))
                 })
                 .bench_with_input(
                     BenchmarkId::new("faster-hex", input.len()),
                     &input,
-                    |b, input| b.iter(|| faster_hex::hex_string(&input)),
+                    |b, input| b.iter(|| faster_hex::hex_string(input)),
                 )
                 .bench_with_input(
                     BenchmarkId::new("hector", input.len()),
                     &input,
-                    |b, input| b.iter(|| hector::encode(&input)),
+                    |b, input| b.iter(|| hector::encode(input)),
+                );
+        }
+    }
+
+    {
+        // Big enough to spill out of cache, this is where `hector` switches to blocked encoding.
+        const LARGE_SIZES: [usize; 4] =
+            [256 * 1024, 1024 * 1024, 4 * 1024 * 1024, 16 * 1024 * 1024];
+
+        let mut group = c.benchmark_group("to-string-large");
+        group.sample_size(20);
+
+        for size in LARGE_SIZES {
+            let mut input = vec![0; size];
+
+            rand::thread_rng().fill_bytes(&mut input);
+
+            group
+                .throughput(Throughput::Bytes(input.len() as u64))
+                .bench_with_input(BenchmarkId::new("hex", input.len()), &input, |b, input| {
+                    b.iter(|| hex::encode(input))
+                })
+                .bench_with_input(
+                    BenchmarkId::new("faster-hex", input.len()),
+                    &input,
+                    |b, input| b.iter(|| faster_hex::hex_string(input)),
+                )
+                .bench_with_input(
+                    BenchmarkId::new("hector", input.len()),
+                    &input,
+                    |b, input| b.iter(|| hector::encode(input)),
                 );
         }
     }
@@ -43,7 +74,7 @@ fn bench(c: &mut Criterion) {
                 b.iter_batched(
                     || (input, vec![0; size * 2]),
                     |(input, mut output)| {
-                        hex::encode_to_slice(&input, &mut output).unwrap();
+                        hex::encode_to_slice(input, &mut output).unwrap();
                         unsafe { String::from_utf8_unchecked(output) }
                     },
                     BatchSize::SmallInput,
@@ -56,7 +87,7 @@ fn bench(c: &mut Criterion) {
                     b.iter_batched(
                         || (input, vec![0; size * 2]),
                         |(input, mut output)| {
-                            faster_hex::hex_encode(&input, &mut output).unwrap();
+                            faster_hex::hex_encode(input, &mut output).unwrap();
                             unsafe { String::from_utf8_unchecked(output) }
                         },
                         BatchSize::SmallInput,
@@ -70,7 +101,7 @@ fn bench(c: &mut Criterion) {
                     b.iter_batched(
                         || (input, vec![0; size * 2]),
                         |(input, mut output)| {
-                            hector::encode_to_slice(&input, &mut output).unwrap();
+                            hector::encode_to_slice(input, &mut output).unwrap();
                             unsafe { String::from_utf8_unchecked(output) }
                         },
                         BatchSize::SmallInput,
@@ -0,0 +1,263 @@
+//! Encoding into a [`BufMut`] and decoding from a [`Buf`] (from the `bytes` crate),
+//! for network code that works with frame buffers.
+//!
+//! A [`Buf`] doesn't have to be contiguous (like a [`Chain`](bytes::buf::Chain) of [`Bytes`](bytes::Bytes)),
+//! so decoding reads it a chunk at a time, and bytes can be split between chunks.
+//!
+//! # Examples
+//! ```
+//! use bytes::{Buf, Bytes, BytesMut};
+//!
+//! let mut frame = BytesMut::new();
+//! hector::buf::encode_to_bufmut([0xde, 0xca, 0xff], &mut frame)?;
+//! assert_eq!(&frame[..], b"decaff");
+//!
+//! let mut input = Bytes::from_static(b"dec").chain(Bytes::from_static(b"aff"));
+//! let mut output = [0; 3];
+//! hector::buf::decode_from_buf(&mut input, &mut output)?;
+//! assert_eq!(output, [0xde, 0xca, 0xff]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use bytes::{Buf, BufMut};
+
+use crate::encode::{encode_chunked, FMT_BUFFER_LEN};
+use crate::scratch::Scratch;
+use crate::stream::Decoder;
+use crate::{DecodeError, EncodeError};
+
+/// Encode `input` into `output`, checking that there's room for all of it first.
+fn encode_to_bufmut_impl<const UPPER: bool, B: BufMut + ?Sized>(
+    input: &[u8],
+    output: &mut B,
+) -> Result<(), EncodeError> {
+    if output.remaining_mut() < input.len() * 2 {
+        return Err(EncodeError);
+    }
+
+    crate::output::for_each_piece(
+        |write| encode_chunked::<_, FMT_BUFFER_LEN, UPPER>(input, write),
+        |chunk| output.put_slice(chunk.as_bytes()),
+    );
+
+    Ok(())
+}
+
+/// Encode `input` as lowercase hex onto the end of `output`.
+///
+/// # Errors
+/// - [`EncodeError`] if `output` doesn't have room for `input.len() * 2` more bytes,
+///   in which case nothing is written.
+///
+/// # Examples
+/// ```
+/// let mut frame = bytes::BytesMut::from(&b"id="[..]);
+///
+/// hector::buf::encode_to_bufmut([0xde, 0xca, 0xff], &mut frame)?;
+/// assert_eq!(&frame[..], b"id=decaff");
+///
+/// let mut storage = [0; 4];
+/// assert!(hector::buf::encode_to_bufmut([0xde, 0xca, 0xff], &mut &mut storage[..]).is_err());
+/// # Ok::<(), hector::EncodeError>(())
+/// ```
+pub fn encode_to_bufmut<T: AsRef<[u8]>, B: BufMut + ?Sized>(
+    input: T,
+    output: &mut B,
+) -> Result<(), EncodeError> {
+    encode_to_bufmut_impl::<false, B>(input.as_ref(), output)
+}
+
+/// Encode `input` as uppercase hex onto the end of `output`.
+///
+/// # Errors
+/// - [`EncodeError`] if `output` doesn't have room for `input.len() * 2` more bytes,
+///   in which case nothing is written.
+///
+/// # Examples
+/// ```
+/// let mut frame = bytes::BytesMut::new();
+///
+/// hector::buf::encode_to_bufmut_upper([0xde, 0xca, 0xff], &mut frame)?;
+/// assert_eq!(&frame[..], b"DECAFF");
+/// # Ok::<(), hector::EncodeError>(())
+/// ```
+pub fn encode_to_bufmut_upper<T: AsRef<[u8]>, B: BufMut + ?Sized>(
+    input: T,
+    output: &mut B,
+) -> Result<(), EncodeError> {
+    encode_to_bufmut_impl::<true, B>(input.as_ref(), output)
+}
+
+/// Decode the first `len` characters of `input` (which has at least that many), passing each run of bytes to `push`.
+///
+/// `input` is advanced past everything that's decoded, up to any error.
+fn decode_buf_with<B: Buf + ?Sized>(
+    input: &mut B,
+    len: usize,
+    mut push: impl FnMut(&[u8]),
+) -> Result<(), DecodeError> {
+    let mut decoder = Decoder::new();
+    // The same size of buffer on the stack as encoding uses.
+    let mut buffer = Scratch([0; FMT_BUFFER_LEN / 2]);
+
+    while decoder.consumed() < len {
+        let chunk = input.chunk();
+        let chunk = &chunk[..chunk.len().min(len - decoder.consumed())];
+
+        let (consumed, written) = decoder.decode_chunk(chunk, &mut buffer)?;
+        input.advance(consumed);
+
+        push(&buffer[..written]);
+    }
+
+    decoder.finish()
+}
+
+/// Decode exactly `output.len() * 2` characters from the start of `input` into `output`, advancing `input` past them.
+///
+/// For convenience, this returns a reference to `output`.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`DecodeError::MismatchedLength`] if `input` has fewer than `output.len() * 2` bytes remaining,
+///   with `source_len` as the number remaining, in which case `input` isn't advanced.
+/// - [`DecodeError::InvalidHex`] if any character isn't a valid hex character,
+///   the offset is from where `input` was when this was called, and it's advanced up to the byte with the error.
+///
+/// # Examples
+/// ```
+/// use bytes::Buf;
+///
+/// let mut input = &b"c0ffee, and the rest"[..];
+/// let mut output = [0; 3];
+///
+/// assert_eq!(hector::buf::decode_from_buf(&mut input, &mut output), Ok(&[0xc0, 0xff, 0xee][..]));
+/// assert_eq!(input, b", and the rest");
+/// ```
+///
+/// ```
+/// use bytes::{Buf, Bytes};
+/// use hector::DecodeError;
+///
+/// let mut input = Bytes::from_static(b"c0f").chain(Bytes::from_static(b"feg"));
+///
+/// assert_eq!(
+///     hector::buf::decode_from_buf(&mut input, &mut [0; 3]),
+///     Err(DecodeError::InvalidHex { offset: 5, value: b'g' })
+/// );
+/// assert_eq!(input.remaining(), 2);
+/// ```
+pub fn decode_from_buf<'a, B: Buf + ?Sized>(
+    input: &mut B,
+    output: &'a mut [u8],
+) -> Result<&'a [u8], DecodeError> {
+    if input.remaining() < output.len() * 2 {
+        return Err(DecodeError::MismatchedLength {
+            source_len: input.remaining(),
+            dest_len: output.len(),
+        });
+    }
+
+    let mut written = 0;
+    decode_buf_with(input, output.len() * 2, |bytes| {
+        output[written..][..bytes.len()].copy_from_slice(bytes);
+        written += bytes.len();
+    })?;
+
+    Ok(output)
+}
+
+/// Decode everything remaining in `input` onto the end of `output`, advancing `input` to the end.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`DecodeError::OddLength`] if `input` has an odd number of bytes remaining.
+/// - [`DecodeError::MismatchedLength`] if `output` doesn't have room for all of `input`,
+///   with `dest_len` as the room it does have.
+/// - [`DecodeError::InvalidHex`] if any character isn't a valid hex character,
+///   the offset is from where `input` was when this was called, and it's advanced up to the byte with the error.
+///
+/// Nothing is read or written if the lengths are wrong.
+///
+/// # Examples
+/// ```
+/// use bytes::{Buf, Bytes, BytesMut};
+///
+/// let mut input = Bytes::from_static(b"dec").chain(Bytes::from_static(b"aff"));
+/// let mut frame = BytesMut::new();
+///
+/// hector::buf::decode_to_bufmut(&mut input, &mut frame)?;
+/// assert_eq!(&frame[..], [0xde, 0xca, 0xff]);
+/// assert!(!input.has_remaining());
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+pub fn decode_to_bufmut<B: Buf + ?Sized, O: BufMut + ?Sized>(
+    input: &mut B,
+    output: &mut O,
+) -> Result<(), DecodeError> {
+    let len = input.remaining();
+
+    if !len.is_multiple_of(2) {
+        return Err(DecodeError::OddLength);
+    }
+
+    if output.remaining_mut() < len / 2 {
+        return Err(DecodeError::MismatchedLength {
+            source_len: len,
+            dest_len: output.remaining_mut(),
+        });
+    }
+
+    decode_buf_with(input, len, |bytes| output.put_slice(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{Buf, BytesMut};
+
+    use super::{decode_from_buf, decode_to_bufmut, encode_to_bufmut, FMT_BUFFER_LEN};
+
+    #[test]
+    fn round_trips_across_chunks() {
+        let input = crate::test_util::bytes(FMT_BUFFER_LEN * 3 + 1);
+
+        let mut hex = BytesMut::new();
+        encode_to_bufmut(&input, &mut hex).unwrap();
+        assert_eq!(hex, crate::encode(&input).as_bytes());
+
+        // Split at every odd offset, so bytes are split between chunks.
+        for split in (1..hex.len()).step_by(97) {
+            let (first, second) = hex.split_at(split);
+
+            let mut output = std::vec![0; input.len()];
+            let mut chain = first.chain(second);
+            assert_eq!(decode_from_buf(&mut chain, &mut output), Ok(&input[..]));
+
+            let mut output = BytesMut::new();
+            let mut chain = first.chain(second);
+            assert_eq!(decode_to_bufmut(&mut chain, &mut output), Ok(()));
+            assert_eq!(output, input);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let mut input = &b"decaf"[..];
+        assert_eq!(
+            decode_to_bufmut(&mut input, &mut BytesMut::new()),
+            Err(crate::DecodeError::OddLength)
+        );
+        assert_eq!(input, b"decaf");
+
+        assert_eq!(
+            decode_from_buf(&mut input, &mut [0; 3]),
+            Err(crate::DecodeError::MismatchedLength {
+                source_len: 5,
+                dest_len: 3
+            })
+        );
+        assert_eq!(input, b"decaf");
+    }
+}
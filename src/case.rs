@@ -0,0 +1,310 @@
+//! Utilities for hex strings with inconsistent casing.
+
+use core::cmp::Ordering;
+
+use crate::DecodeError;
+
+/// The casing convention of a hex string, as reported by [`detect_case`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Case {
+    /// Every letter is lowercase (`c0ffee`).
+    Lower,
+
+    /// Every letter is uppercase (`C0FFEE`).
+    Upper,
+
+    /// There are both lowercase and uppercase letters (`C0ffee`).
+    Mixed,
+
+    /// There aren't any letters at all (`1234`), so the string is valid in any casing convention.
+    None,
+}
+
+/// Fold the case of a hex character.
+///
+/// Hex digits already have the "lowercase" bit set, so setting it makes both cases of the letters the same,
+/// but other characters can end up the same too (`@` and `` ` ``, for instance).
+#[inline(always)]
+fn fold(char: u8) -> u8 {
+    char | 0x20
+}
+
+/// Returns `true` if `a` and `b` are the same hex string, ignoring case.
+///
+/// This is faster than [`eq_ignore_ascii_case`](slice::eq_ignore_ascii_case), but only works for hex,
+/// if either input isn't valid hex, the result is unspecified (but safe).
+///
+/// # Examples
+/// ```
+/// assert!(hector::hex_eq_ignore_case("DeCaFF", "decaff"));
+///
+/// assert!(!hector::hex_eq_ignore_case("decaff", "decafe"));
+/// assert!(!hector::hex_eq_ignore_case("decaff", "deca"));
+/// ```
+#[must_use]
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn hex_eq_ignore_case<A: AsRef<[u8]>, B: AsRef<[u8]>>(a: A, b: B) -> bool {
+    let (a, b) = (a.as_ref(), b.as_ref());
+
+    // `&` rather than `&&` so that there's no early exit (which lets this vectorize).
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .fold(true, |equal, (&a, &b)| equal & (fold(a) == fold(b)))
+}
+
+/// Compare `a` and `b` as if they were both lowercase hex strings.
+///
+/// This is consistent with [`hex_eq_ignore_case`], and has the same caveat for input that isn't valid hex.
+///
+/// # Examples
+/// ```
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(hector::hex_cmp_ignore_case("DECAFF", "decaff"), Ordering::Equal);
+/// assert_eq!(hector::hex_cmp_ignore_case("C0FFEE", "decaff"), Ordering::Less);
+/// assert_eq!(hector::hex_cmp_ignore_case("9f", "A0"), Ordering::Less);
+///
+/// let mut ids = ["DECAFF", "c0ffee", "Decade"];
+/// ids.sort_by(|a, b| hector::hex_cmp_ignore_case(a, b));
+/// assert_eq!(ids, ["c0ffee", "Decade", "DECAFF"]);
+/// ```
+#[must_use]
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn hex_cmp_ignore_case<A: AsRef<[u8]>, B: AsRef<[u8]>>(a: A, b: B) -> Ordering {
+    let (a, b) = (a.as_ref(), b.as_ref());
+
+    a.iter()
+        .map(|&it| fold(it))
+        .cmp(b.iter().map(|&it| fold(it)))
+}
+
+/// Work out which casing convention `input` uses, after checking that it's valid hex.
+///
+/// # Errors
+/// The same as [`validate`](crate::validate):
+/// - [`DecodeError::OddLength`] if `input.len()` is not even.
+/// - [`DecodeError::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// use hector::Case;
+///
+/// assert_eq!(hector::detect_case("c0ffee"), Ok(Case::Lower));
+/// assert_eq!(hector::detect_case("C0FFEE"), Ok(Case::Upper));
+/// assert_eq!(hector::detect_case("C0ffee"), Ok(Case::Mixed));
+/// assert_eq!(hector::detect_case("1234"), Ok(Case::None));
+/// assert_eq!(hector::detect_case(""), Ok(Case::None));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// assert_eq!(hector::detect_case("c0ffeg"), Err(DecodeError::InvalidHex { offset: 5, value: b'g' }));
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn detect_case<T: AsRef<[u8]>>(input: T) -> Result<Case, DecodeError> {
+    let input = input.as_ref();
+
+    crate::validate(input)?;
+
+    // Once we know the input is hex, letters are the only characters with `0x40` set,
+    // and of those, the lowercase ones are the only ones with `0x20` set.
+    let (lower, upper) = input.iter().fold((false, false), |(lower, upper), &char| {
+        let letter = char & 0x40 != 0;
+        let lowercase = char & 0x20 != 0;
+        (lower | (letter & lowercase), upper | (letter & !lowercase))
+    });
+
+    Ok(match (lower, upper) {
+        (true, true) => Case::Mixed,
+        (true, false) => Case::Lower,
+        (false, true) => Case::Upper,
+        (false, false) => Case::None,
+    })
+}
+
+/// Convert already validated hex to lowercase, without branching on each character.
+pub(crate) fn to_lowercase_unchecked(input: &mut [u8]) {
+    // Letters are the only hex characters with `0x40` set, shifting that down gives the case bit.
+    for char in input {
+        *char |= (*char & 0x40) >> 1;
+    }
+}
+
+/// Convert already validated hex to uppercase, without branching on each character.
+pub(crate) fn to_uppercase_unchecked(input: &mut [u8]) {
+    for char in input {
+        *char &= !((*char & 0x40) >> 1);
+    }
+}
+
+/// Check that `input` is valid hex, and then convert it to lowercase in place.
+///
+/// This is useful for canonicalizing hex before hashing it or using it as a key.
+///
+/// # Errors
+/// The same as [`validate`](crate::validate), `input` is left untouched if there's an error:
+/// - [`DecodeError::OddLength`] if `input.len()` is not even.
+/// - [`DecodeError::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// let mut key = *b"DeCaFF";
+/// hector::make_lowercase(&mut key)?;
+///
+/// assert_eq!(&key, b"decaff");
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// let mut key = *b"DeCaFG";
+///
+/// assert_eq!(hector::make_lowercase(&mut key), Err(DecodeError::InvalidHex { offset: 5, value: b'G' }));
+/// assert_eq!(&key, b"DeCaFG");
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn make_lowercase(input: &mut [u8]) -> Result<(), DecodeError> {
+    crate::validate(&*input)?;
+
+    to_lowercase_unchecked(input);
+
+    Ok(())
+}
+
+/// Check that `input` is valid hex, and then convert it to uppercase in place.
+///
+/// # Errors
+/// The same as [`validate`](crate::validate), `input` is left untouched if there's an error:
+/// - [`DecodeError::OddLength`] if `input.len()` is not even.
+/// - [`DecodeError::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// let mut key = *b"DeCaFF";
+/// hector::make_uppercase(&mut key)?;
+///
+/// assert_eq!(&key, b"DECAFF");
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn make_uppercase(input: &mut [u8]) -> Result<(), DecodeError> {
+    crate::validate(&*input)?;
+
+    to_uppercase_unchecked(input);
+
+    Ok(())
+}
+
+/// Like [`make_lowercase`], but for string slices.
+///
+/// # Errors
+/// The same as [`make_lowercase`].
+///
+/// # Examples
+/// ```
+/// let mut key = String::from("DeCaFF");
+/// hector::make_lowercase_str(&mut key)?;
+///
+/// assert_eq!(key, "decaff");
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn make_lowercase_str(input: &mut str) -> Result<(), DecodeError> {
+    #[cfg(not(feature = "safe"))]
+    // Safety: the bytes are only changed after they've been validated as hex, which is ascii,
+    // and the case of ascii letters can be changed without breaking UTF-8.
+    return make_lowercase(unsafe { input.as_bytes_mut() });
+
+    #[cfg(feature = "safe")]
+    {
+        crate::validate(&*input)?;
+        input.make_ascii_lowercase();
+        Ok(())
+    }
+}
+
+/// Like [`make_uppercase`], but for string slices.
+///
+/// # Errors
+/// The same as [`make_uppercase`].
+///
+/// # Examples
+/// ```
+/// let mut key = String::from("DeCaFF");
+/// hector::make_uppercase_str(&mut key)?;
+///
+/// assert_eq!(key, "DECAFF");
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn make_uppercase_str(input: &mut str) -> Result<(), DecodeError> {
+    #[cfg(not(feature = "safe"))]
+    // Safety: see `make_lowercase_str`.
+    return make_uppercase(unsafe { input.as_bytes_mut() });
+
+    #[cfg(feature = "safe")]
+    {
+        crate::validate(&*input)?;
+        input.make_ascii_uppercase();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        detect_case, hex_cmp_ignore_case, hex_eq_ignore_case, make_lowercase, make_uppercase, Case,
+    };
+
+    const HEX: &[u8] = b"0123456789abcdefABCDEF";
+
+    #[test]
+    fn matches_lowercase() {
+        for &a in HEX {
+            for &b in HEX {
+                let (a, b) = ([a], [b]);
+
+                assert_eq!(
+                    hex_eq_ignore_case(a, b),
+                    a.eq_ignore_ascii_case(&b),
+                    "{a:?} {b:?}"
+                );
+                assert_eq!(
+                    hex_cmp_ignore_case(a, b),
+                    a.to_ascii_lowercase().cmp(&b.to_ascii_lowercase()),
+                    "{a:?} {b:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn detect_case_single() {
+        for &char in HEX {
+            let expected = match char {
+                b'0'..=b'9' => Case::None,
+                b'a'..=b'f' => Case::Lower,
+                _ => Case::Upper,
+            };
+
+            assert_eq!(detect_case([char, b'0']), Ok(expected), "{char:?}");
+        }
+    }
+
+    #[test]
+    fn make_case_matches_ascii() {
+        for &char in HEX {
+            let mut lower = [char, b'0'];
+            let mut upper = lower;
+
+            assert_eq!(make_lowercase(&mut lower), Ok(()));
+            assert_eq!(make_uppercase(&mut upper), Ok(()));
+
+            assert_eq!(lower, [char.to_ascii_lowercase(), b'0'], "{char:?}");
+            assert_eq!(upper, [char.to_ascii_uppercase(), b'0'], "{char:?}");
+        }
+    }
+}
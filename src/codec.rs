@@ -0,0 +1,392 @@
+//! A [`tokio_util::codec`] implementation, for hex based text protocols.
+
+use std::io;
+
+use bytes::BytesMut;
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::io::decode_error_to_io;
+use crate::DecodeError;
+
+/// A codec for hex encoded streams, for use with [`Framed`](tokio_util::codec::Framed) and friends.
+///
+/// By default, the stream is one continuous run of hex, and decoding yields whatever whole bytes are available,
+/// with [`lines`](Self::lines), each frame is a line of hex instead.
+///
+/// Decoding does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// Decoding errors are returned as an [`io::Error`] wrapping a [`DecodeError`] (like [`HexDecodeReader`](crate::io::HexDecodeReader)),
+/// with offsets counted from the start of the stream (including any line endings),
+/// or wrapping a [`LineTooLong`] for lines longer than the [`max_line_len`](Self::max_line_len).
+///
+/// # Examples
+/// ```
+/// use bytes::BytesMut;
+/// use tokio_util::codec::{Decoder, Encoder};
+///
+/// let mut codec = hector::codec::HexCodec::new();
+///
+/// let mut buffer = BytesMut::new();
+/// codec.encode(&[0xde, 0xca, 0xff], &mut buffer).unwrap();
+/// assert_eq!(&buffer[..], b"decaff");
+///
+/// // A byte that's split between reads stays buffered.
+/// let mut buffer = BytesMut::from(&b"decaf"[..]);
+/// assert_eq!(codec.decode(&mut buffer).unwrap().as_deref(), Some(&[0xde, 0xca][..]));
+/// assert_eq!(&buffer[..], b"f");
+/// ```
+#[derive(Debug, Clone)]
+pub struct HexCodec {
+    lines: bool,
+    upper: bool,
+    max_line_len: usize,
+    /// The number of characters decoded so far, for error offsets.
+    consumed: usize,
+    /// How much of the buffered input has already been searched for a line ending.
+    scanned: usize,
+}
+
+/// The error for a line longer than the [`max_line_len`](HexCodec::max_line_len) of a [`HexCodec`].
+///
+/// This is returned from decoding as an [`io::Error`] (of kind [`InvalidData`](io::ErrorKind::InvalidData)) wrapping it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LineTooLong {
+    /// The maximum length of a line, that was exceeded.
+    pub max_len: usize,
+}
+
+impl core::fmt::Display for LineTooLong {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "line is longer than the maximum of `{}` characters",
+            self.max_len
+        )
+    }
+}
+
+impl std::error::Error for LineTooLong {}
+
+impl HexCodec {
+    /// Create a new codec, for a continuous stream of hex.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            lines: false,
+            upper: false,
+            max_line_len: usize::MAX,
+            consumed: 0,
+            scanned: 0,
+        }
+    }
+
+    /// Create a new codec, where each frame is a line of hex.
+    ///
+    /// When decoding, lines can end in either `\n` or `\r\n`, when encoding, lines end in `\n`.
+    ///
+    /// By default there's no limit on how long a line can be, so a peer that never ends its line
+    /// can make the buffer grow without bound, for input that isn't trusted, set a [`max_line_len`](Self::max_line_len).
+    ///
+    /// # Examples
+    /// ```
+    /// use bytes::BytesMut;
+    /// use tokio_util::codec::Decoder;
+    ///
+    /// let mut codec = hector::codec::HexCodec::lines();
+    /// let mut buffer = BytesMut::from(&b"deca\r\nff\nc0f"[..]);
+    ///
+    /// // Every line is a separate frame, and the last one waits for its line ending.
+    /// assert_eq!(codec.decode(&mut buffer).unwrap().as_deref(), Some(&[0xde, 0xca][..]));
+    /// assert_eq!(codec.decode(&mut buffer).unwrap().as_deref(), Some(&[0xff][..]));
+    /// assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+    /// ```
+    #[must_use]
+    pub fn lines() -> Self {
+        Self {
+            lines: true,
+            ..Self::new()
+        }
+    }
+
+    /// Encode to uppercase hex, rather than lowercase.
+    ///
+    /// # Examples
+    /// ```
+    /// use bytes::BytesMut;
+    /// use tokio_util::codec::Encoder;
+    ///
+    /// let mut codec = hector::codec::HexCodec::lines().upper();
+    /// let mut buffer = BytesMut::new();
+    ///
+    /// codec.encode(&[0xde, 0xca, 0xff], &mut buffer).unwrap();
+    ///
+    /// assert_eq!(&buffer[..], b"DECAFF\n");
+    /// ```
+    #[must_use]
+    pub fn upper(self) -> Self {
+        Self {
+            upper: true,
+            ..self
+        }
+    }
+
+    /// Limit lines to `len` characters (not counting their line endings) when decoding.
+    ///
+    /// A longer line is an error, which is returned as soon as it's buffered (rather than once it ends),
+    /// after which the stream can't be decoded any further.
+    ///
+    /// This only affects [`lines`](Self::lines) codecs.
+    ///
+    /// # Examples
+    /// ```
+    /// use bytes::BytesMut;
+    /// use hector::codec::{HexCodec, LineTooLong};
+    /// use tokio_util::codec::Decoder;
+    ///
+    /// let mut codec = HexCodec::lines().max_line_len(4);
+    ///
+    /// let mut buffer = BytesMut::from(&b"deca\r\nc0ffee"[..]);
+    /// assert_eq!(codec.decode(&mut buffer).unwrap().as_deref(), Some(&[0xde, 0xca][..]));
+    ///
+    /// let err = codec.decode(&mut buffer).unwrap_err();
+    /// assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    /// assert_eq!(err.get_ref().unwrap().downcast_ref(), Some(&LineTooLong { max_len: 4 }));
+    /// ```
+    #[must_use]
+    pub fn max_line_len(self, len: usize) -> Self {
+        Self {
+            max_line_len: len,
+            ..self
+        }
+    }
+
+    /// The error for a line that's too long.
+    fn line_too_long(&self) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            LineTooLong {
+                max_len: self.max_line_len,
+            },
+        )
+    }
+
+    /// Decode one whole frame of hex.
+    fn decode_frame(&mut self, hex: &[u8]) -> io::Result<BytesMut> {
+        if !hex.len().is_multiple_of(2) {
+            // A whole frame can't be waiting on more input, unlike the end of a stream.
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                DecodeError::OddLength,
+            ));
+        }
+
+        let mut output = BytesMut::zeroed(hex.len() / 2);
+
+        crate::decode_to_slice(hex, &mut output)
+            .map_err(|err| decode_error_to_io(err.offset_by(self.consumed)))?;
+
+        self.consumed += hex.len();
+
+        Ok(output)
+    }
+
+    /// Decode a line, stripping its line ending.
+    fn decode_line(&mut self, line: &[u8]) -> io::Result<BytesMut> {
+        let hex = line.strip_suffix(b"\n").unwrap_or(line);
+        let hex = hex.strip_suffix(b"\r").unwrap_or(hex);
+
+        if hex.len() > self.max_line_len {
+            return Err(self.line_too_long());
+        }
+
+        let output = self.decode_frame(hex)?;
+        self.consumed += line.len() - hex.len();
+
+        Ok(output)
+    }
+}
+
+impl Decoder for HexCodec {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+        if self.lines {
+            // Only look through what's been buffered since last time.
+            let Some(end) = src[self.scanned..].iter().position(|&it| it == b'\n') else {
+                self.scanned = src.len();
+
+                // The line could still end in `\r\n`, so an `\r` past the maximum doesn't count (yet).
+                if src.len() > self.max_line_len.saturating_add(1) {
+                    return Err(self.line_too_long());
+                }
+
+                return Ok(None);
+            };
+
+            let line = src.split_to(self.scanned + end + 1);
+            self.scanned = 0;
+
+            return self.decode_line(&line).map(Some);
+        }
+
+        // Anything after the last whole byte waits for more input.
+        match src.len() / 2 {
+            0 => Ok(None),
+            len => self.decode_frame(&src.split_to(len * 2)).map(Some),
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+        if let Some(frame) = self.decode(src)? {
+            return Ok(Some(frame));
+        }
+
+        match (src.is_empty(), self.lines) {
+            (true, _) => Ok(None),
+            // The last line doesn't need a line ending.
+            (false, true) => {
+                self.scanned = 0;
+                self.decode_line(&src.split()).map(Some)
+            }
+            (false, false) => Err(decode_error_to_io(DecodeError::OddLength)),
+        }
+    }
+}
+
+impl Default for HexCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: AsRef<[u8]>> Encoder<T> for HexCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> io::Result<()> {
+        let item = item.as_ref();
+
+        let start = dst.len();
+        dst.resize(start + item.len() * 2, 0);
+
+        // The output is exactly the right length, so this can't fail.
+        let output = &mut dst[start..];
+        let result = match self.upper {
+            false => crate::encode_to_slice(item, output),
+            true => crate::encode_to_slice_upper(item, output),
+        };
+        debug_assert!(result.is_ok());
+
+        if self.lines {
+            dst.extend_from_slice(b"\n");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::vec::Vec;
+
+    use bytes::BytesMut;
+    use tokio_util::codec::{Decoder, Encoder};
+
+    use super::{HexCodec, LineTooLong};
+    use crate::DecodeError;
+
+    fn decode_err(err: &io::Error) -> Option<DecodeError> {
+        err.get_ref()?.downcast_ref().copied()
+    }
+
+    /// Feed `input` to `codec` a byte at a time, collecting every frame.
+    fn decode_all(codec: &mut HexCodec, input: &[u8]) -> io::Result<Vec<Vec<u8>>> {
+        let mut buffer = BytesMut::new();
+        let mut frames = Vec::new();
+
+        for &byte in input {
+            buffer.extend_from_slice(&[byte]);
+
+            while let Some(frame) = codec.decode(&mut buffer)? {
+                frames.push(frame.to_vec());
+            }
+        }
+
+        while let Some(frame) = codec.decode_eof(&mut buffer)? {
+            frames.push(frame.to_vec());
+        }
+
+        Ok(frames)
+    }
+
+    #[test]
+    fn stream_round_trip() {
+        let bytes = crate::test_util::bytes(100);
+
+        let mut codec = HexCodec::new().upper();
+        let mut buffer = BytesMut::new();
+        codec.encode(&bytes, &mut buffer).unwrap();
+        assert_eq!(&buffer[..], crate::encode_upper(&bytes).as_bytes());
+
+        let frames = decode_all(&mut HexCodec::new(), &buffer).unwrap();
+        assert_eq!(frames.concat(), bytes);
+    }
+
+    #[test]
+    fn lines_round_trip() {
+        let mut codec = HexCodec::lines();
+        let mut buffer = BytesMut::new();
+
+        codec.encode([0xde, 0xca], &mut buffer).unwrap();
+        codec.encode([], &mut buffer).unwrap();
+        codec.encode([0xff], &mut buffer).unwrap();
+        assert_eq!(&buffer[..], b"deca\n\nff\n");
+
+        let frames = decode_all(&mut HexCodec::lines(), b"deca\r\n\nff").unwrap();
+        assert_eq!(frames, [&[0xde, 0xca][..], &[], &[0xff]]);
+    }
+
+    #[test]
+    fn error_offsets() {
+        let err = decode_all(&mut HexCodec::new(), b"decafg").unwrap_err();
+        assert_eq!(
+            decode_err(&err),
+            Some(DecodeError::InvalidHex {
+                offset: 5,
+                value: b'g'
+            })
+        );
+
+        let err = decode_all(&mut HexCodec::lines(), b"de\r\ncafg\n").unwrap_err();
+        assert_eq!(
+            decode_err(&err),
+            Some(DecodeError::InvalidHex {
+                offset: 7,
+                value: b'g'
+            })
+        );
+
+        let err = decode_all(&mut HexCodec::new(), b"decaf").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        // A whole line can't be waiting for more input, so it's invalid rather than cut short.
+        let err = decode_all(&mut HexCodec::lines(), b"de\ncaf\n").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(decode_err(&err), Some(DecodeError::OddLength));
+    }
+
+    #[test]
+    fn max_line_len() {
+        let mut codec = HexCodec::lines().max_line_len(4);
+        let frames = decode_all(&mut codec, b"deca\r\nff\n\nc0ff").unwrap();
+        assert_eq!(frames, [&[0xde, 0xca][..], &[0xff], &[], &[0xc0, 0xff]]);
+
+        for input in [&b"decaff\n"[..], b"de\ndecaf", b"decaf\r\n"] {
+            let err = decode_all(&mut HexCodec::lines().max_line_len(4), input).unwrap_err();
+            let err = err.get_ref().and_then(|err| err.downcast_ref());
+            assert_eq!(err, Some(&LineTooLong { max_len: 4 }));
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! Constant-time encoding and comparison, for when the bytes are secret (keys, authentication tokens, ...).
+//!
+//! Everything here takes time that depends only on the _lengths_ of the inputs, never on their contents,
+//! so lengths shouldn't be secret.
+//!
+//! The rest of the crate is written to be fast, and makes no such promise
+//! (lookup tables and early exits are both fair game there).
+
+use crate::EncodeError;
+
+/// Build a mask that's `0xff` if `a < b`, otherwise `0x00`, without comparing.
+#[inline(always)]
+fn lt_mask(a: u8, b: u8) -> u8 {
+    // The subtraction borrows from the high byte exactly when `a < b`.
+    let mask = ((u16::from(a).wrapping_sub(u16::from(b))) >> 8) as u8;
+
+    // Hide the mask from the optimizer, so that it can't turn the selects that use it back into branches.
+    core::hint::black_box(mask)
+}
+
+/// Build a mask that's `0xff` if `a == b`, otherwise `0x00`, without comparing.
+#[inline(always)]
+fn eq_mask(a: u8, b: u8) -> u8 {
+    // `a ^ b` is zero exactly when they're equal, and zero is the only value less than 1.
+    lt_mask(a ^ b, 1)
+}
+
+/// Convert a 4-bit number to a single hex character.
+#[inline(always)]
+fn nibble_to_hex<const UPPER: bool>(nibble: u8) -> u8 {
+    let ascii_a = if UPPER { b'A' } else { b'a' };
+
+    // Same idea as the regular encoder, but the adjustment is selected with a mask, where it uses a bool.
+    let digit = nibble + b'0';
+    digit + (!lt_mask(nibble, 10) & (ascii_a - 1 - b'9'))
+}
+
+#[inline(always)]
+fn byte_to_hex<const UPPER: bool>(byte: u8) -> [u8; 2] {
+    [
+        nibble_to_hex::<UPPER>(byte >> 4),
+        nibble_to_hex::<UPPER>(byte & 0xf),
+    ]
+}
+
+fn encode_to_slice_impl<'a, const UPPER: bool>(
+    input: &[u8],
+    output: &'a mut [u8],
+) -> Result<&'a str, EncodeError> {
+    if output.len() != input.len() * 2 {
+        return Err(EncodeError);
+    }
+
+    for (&byte, pair) in input.iter().zip(output.chunks_exact_mut(2)) {
+        pair.copy_from_slice(&byte_to_hex::<UPPER>(byte));
+    }
+
+    Ok(crate::output::hex_str_mut(output))
+}
+
+/// Returns `1` if `hex` is the hex encoding of `bytes` (in either case), otherwise `0`.
+fn eq_impl(bytes: &[u8], hex: &[u8]) -> u8 {
+    // The lengths are public, so this is allowed to exit early.
+    if hex.len() != bytes.len() * 2 {
+        return 0;
+    }
+
+    let mask = bytes
+        .iter()
+        .zip(hex.chunks_exact(2))
+        .fold(0xff, |mask, (&byte, pair)| {
+            let [lower_high, lower_low] = byte_to_hex::<false>(byte);
+            let [upper_high, upper_low] = byte_to_hex::<true>(byte);
+
+            mask & (eq_mask(pair[0], lower_high) | eq_mask(pair[0], upper_high))
+                & (eq_mask(pair[1], lower_low) | eq_mask(pair[1], upper_low))
+        });
+
+    mask & 1
+}
+
+/// Encode `input` to a lowercase hex string, in constant time.
+///
+/// For convenience, this returns a [`&str`](str), backed by `output`.
+///
+/// # Errors
+/// - [`EncodeError`] if the output is too big or too small.
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 6];
+///
+/// assert_eq!(hector::ct::encode_to_slice([0xde, 0xca, 0xff], &mut storage), Ok("decaff"));
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn encode_to_slice<T: AsRef<[u8]>>(input: T, output: &mut [u8]) -> Result<&str, EncodeError> {
+    encode_to_slice_impl::<false>(input.as_ref(), output)
+}
+
+/// Encode `input` to an uppercase hex string, in constant time.
+///
+/// For convenience, this returns a [`&str`](str), backed by `output`.
+///
+/// # Errors
+/// - [`EncodeError`] if the output is too big or too small.
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 6];
+///
+/// assert_eq!(hector::ct::encode_to_slice_upper([0xde, 0xca, 0xff], &mut storage), Ok("DECAFF"));
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn encode_to_slice_upper<T: AsRef<[u8]>>(
+    input: T,
+    output: &mut [u8],
+) -> Result<&str, EncodeError> {
+    encode_to_slice_impl::<true>(input.as_ref(), output)
+}
+
+/// Returns `true` if `hex` is the hex encoding of `bytes` (in either case), in constant time.
+///
+/// This is the constant-time version of [`eq_hex`](crate::eq_hex),
+/// for checking a hex token from a request against the expected bytes, for instance.
+///
+/// # Examples
+/// ```
+/// let token = [0xde, 0xca, 0xff];
+///
+/// assert!(hector::ct::eq(token, "decaff"));
+/// assert!(hector::ct::eq(token, "DECAFF"));
+///
+/// assert!(!hector::ct::eq(token, "decafe"));
+/// assert!(!hector::ct::eq(token, "deca"));
+/// ```
+#[must_use]
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn eq<A: AsRef<[u8]>, B: AsRef<[u8]>>(bytes: A, hex: B) -> bool {
+    eq_impl(bytes.as_ref(), hex.as_ref()) == 1
+}
+
+/// Like [`eq`], but returns a [`subtle::Choice`], so that the result can stay constant-time too.
+///
+/// # Examples
+/// ```
+/// let token = [0xde, 0xca, 0xff];
+///
+/// assert!(bool::from(hector::ct::eq_choice(token, "decaff")));
+/// assert!(!bool::from(hector::ct::eq_choice(token, "decafe")));
+/// ```
+#[cfg(feature = "subtle")]
+#[must_use]
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn eq_choice<A: AsRef<[u8]>, B: AsRef<[u8]>>(bytes: A, hex: B) -> subtle::Choice {
+    subtle::Choice::from(eq_impl(bytes.as_ref(), hex.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{byte_to_hex, eq_impl};
+
+    #[test]
+    fn matches_regular_encoder() {
+        for byte in 0..=u8::MAX {
+            assert_eq!(
+                byte_to_hex::<false>(byte),
+                crate::encode::byte_to_hex::<false>(byte)
+            );
+            assert_eq!(
+                byte_to_hex::<true>(byte),
+                crate::encode::byte_to_hex::<true>(byte)
+            );
+        }
+    }
+
+    #[test]
+    fn eq_matches_eq_hex() {
+        for byte in 0..=u8::MAX {
+            for high in 0..=u8::MAX {
+                for low in [b'0', b'9', b'a', b'f', b'A', b'F', b'g', 0x10, 0x60] {
+                    let hex = [high, low];
+                    assert_eq!(
+                        eq_impl(&[byte], &hex) == 1,
+                        crate::eq_hex([byte], hex),
+                        "{byte:#04x} {hex:?}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,11 @@
+#[cfg(not(feature = "safe"))]
+use core::mem::MaybeUninit;
+
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Error {
     /// The source buffer had an odd number of characters.
     OddLength,
@@ -16,7 +20,8 @@ pub enum Error {
 
     /// The destination buffer size was incorrect for the provided source buffer.
     ///
-    /// This happens when decoding into a fixed sized buffer and `source_len != dest_len * 2`
+    /// This happens when decoding into a fixed sized buffer and `source_len != dest_len * 2`,
+    /// or into a fixed capacity (or a buffer that's only partly filled) and `source_len > dest_len * 2`.
     MismatchedLength {
         /// The length of the source buffer.
         source_len: usize,
@@ -24,6 +29,35 @@ pub enum Error {
         /// The length of the destination buffer.
         dest_len: usize,
     },
+
+    /// The source buffer didn't start with `0x` (or `0X`), or the prefix of the format being decoded.
+    ///
+    /// This only happens with the functions that require a prefix, such as [`decode_prefixed`](crate::decode_prefixed).
+    MissingPrefix,
+
+    /// The source buffer was a quoted literal (`X'decaff'`) without its closing quote.
+    ///
+    /// This only happens with [`sql::decode_literal`](crate::sql::decode_literal).
+    MissingQuote,
+
+    /// The source buffer held a number too big for the integer type it was being decoded into.
+    ///
+    /// This only happens with [`decode_uint`](crate::decode_uint).
+    Overflow,
+
+    /// The source buffer held a number that wasn't in its shortest form (like `0400`), or no number at all.
+    ///
+    /// This only happens with [`decode_trimmed`](crate::decode_trimmed) (and, for no number at all, [`decode_uint`](crate::decode_uint)).
+    NonCanonical,
+
+    /// Allocating the destination buffer failed.
+    ///
+    /// This only happens with the fallible allocation functions, such as [`try_decode`](crate::try_decode),
+    /// and is the decoding counterpart of [`AllocationError`](crate::AllocationError).
+    AllocationFailed {
+        /// The length (in bytes) of the destination buffer that couldn't be allocated.
+        len: usize,
+    },
 }
 
 impl core::fmt::Display for Error {
@@ -42,6 +76,13 @@ impl core::fmt::Display for Error {
                 f,
                 "source / destination buffer length mismatch: `{source_len} != 2 * {dest_len}`"
             ),
+            Error::MissingPrefix => f.write_str("input is missing its prefix (like `0x`)"),
+            Error::MissingQuote => f.write_str("input is missing its closing quote"),
+            Error::Overflow => f.write_str("number is too big for the integer type"),
+            Error::NonCanonical => f.write_str("number has leading zeros, or is empty"),
+            Error::AllocationFailed { len } => {
+                write!(f, "failed to allocate `{len}` bytes for the output")
+            }
         }
     }
 }
@@ -50,8 +91,62 @@ impl core::fmt::Display for Error {
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
+impl Error {
+    /// Shift the offset of an [`Error::InvalidHex`] by `base`,
+    /// for when the error was found while looking at a subslice of the input that started at `base`.
+    pub(crate) fn offset_by(self, base: usize) -> Self {
+        match self {
+            Error::InvalidHex { offset, value } => Error::InvalidHex {
+                offset: base + offset,
+                value,
+            },
+            err => err,
+        }
+    }
+}
+
+// Only some of the fallback is used when there's a faster implementation available.
+#[cfg_attr(
+    all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2",
+        not(feature = "force-fallback"),
+        not(feature = "safe")
+    ),
+    allow(dead_code)
+)]
 mod fallback;
 
+pub(crate) use fallback::{decode_nibble, decode_pair};
+
+mod options;
+
+pub use options::{DecodeOptions, OddLength};
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2",
+    not(feature = "force-fallback"),
+    not(feature = "safe")
+))]
+mod sse2;
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2",
+    not(feature = "force-fallback"),
+    not(feature = "safe")
+))]
+use sse2 as imp;
+
+#[cfg(not(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2",
+    not(feature = "force-fallback"),
+    not(feature = "safe")
+)))]
+use fallback as imp;
+
 /// Decode the hex encoded `input`.
 ///
 /// This function does _not_ enforce a specific casing convention.
@@ -80,7 +175,7 @@ mod fallback;
 /// ```
 #[cfg(feature = "alloc")]
 pub fn decode<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, Error> {
-    fallback::decode(input.as_ref())
+    imp::decode(input.as_ref())
 }
 
 /// Decode the hex encoded `input`.
@@ -88,9 +183,12 @@ pub fn decode<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, Error> {
 /// This function does _not_ enforce a specific casing convention.
 ///
 /// # Errors
-/// - [`Error::OddLength`] if `input.len()` is not even.
+/// - [`Error::MismatchedLength`] if `input.len() != output.len() * 2`.
 /// - [`Error::InvalidHex`] if any character isn't a valid hex character.
 ///
+/// On error, `output` may have been partly written, and its contents are unspecified.
+/// This is the same for all of the `*_to_slice` functions, decoding isn't delayed to keep `output` intact on error.
+///
 /// # Examples
 /// ```
 /// // It works with binary strings.
@@ -124,6 +222,1499 @@ pub fn decode<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, Error> {
 /// let output = hector::decode_to_slice(b"abcde", &mut storage);
 /// assert_eq!(output, Err(DecodeError::MismatchedLength { source_len: 5, dest_len: 2 }));
 /// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
 pub fn decode_to_slice<T: AsRef<[u8]>>(input: T, output: &mut [u8]) -> Result<&[u8], Error> {
-    fallback::decode_to_slice(input.as_ref(), output)
+    imp::decode_to_slice(input.as_ref(), output)
+}
+
+/// Decode the hex encoded `input`, like [`decode_to_slice`], but returning the output mutably.
+///
+/// This is for when the decoded bytes are changed in place afterwards (like reversing or masking them),
+/// without having to borrow `output` again.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::MismatchedLength`] if `input.len() != output.len() * 2`.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 4];
+///
+/// let bytes = hector::decode_to_slice_mut("deadbeef", &mut storage)?;
+/// bytes.reverse();
+///
+/// assert_eq!(storage, [0xef, 0xbe, 0xad, 0xde]);
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn decode_to_slice_mut<T: AsRef<[u8]>>(
+    input: T,
+    output: &mut [u8],
+) -> Result<&mut [u8], Error> {
+    imp::decode_to_slice(input.as_ref(), output)?;
+
+    Ok(output)
+}
+
+/// Strip the `0x` (or `0X`) off the start of `input`.
+fn strip_prefix(input: &[u8]) -> Result<&[u8], Error> {
+    match input {
+        [b'0', b'x' | b'X', hex @ ..] => Ok(hex),
+        _ => Err(Error::MissingPrefix),
+    }
+}
+
+/// Decode the `0x` prefixed hex encoded `input`.
+///
+/// This function does _not_ enforce a specific casing convention (for the hex or the prefix).
+/// For input that may or may not be prefixed, see [`DecodeOptions::allow_prefix`].
+///
+/// # Errors
+/// - [`Error::MissingPrefix`] if `input` doesn't start with `0x` or `0X`.
+/// - [`Error::OddLength`] if the hex after the prefix has an odd length.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character,
+///   the offset is into `input` (counting the prefix).
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::decode_prefixed("0xdecaff"), Ok(vec![0xde, 0xca, 0xff]));
+/// assert_eq!(hector::decode_prefixed("0X"), Ok(vec![]));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// assert_eq!(hector::decode_prefixed("decaff"), Err(DecodeError::MissingPrefix));
+/// assert_eq!(hector::decode_prefixed("0xc0ffeg"), Err(DecodeError::InvalidHex { offset: 7, value: b'g' }));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_prefixed<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, Error> {
+    let hex = strip_prefix(input.as_ref())?;
+
+    imp::decode(hex).map_err(|err| err.offset_by(2))
+}
+
+/// Decode the `0x` prefixed hex encoded `input` into `output`.
+///
+/// This function does _not_ enforce a specific casing convention (for the hex or the prefix).
+///
+/// # Errors
+/// - [`Error::MissingPrefix`] if `input` doesn't start with `0x` or `0X`.
+/// - [`Error::MismatchedLength`] if the hex after the prefix isn't exactly `output.len() * 2` characters.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character,
+///   the offset is into `input` (counting the prefix).
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 3];
+///
+/// assert_eq!(hector::decode_prefixed_to_slice("0xdecaff", &mut storage), Ok([0xde, 0xca, 0xff].as_slice()));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+/// let mut storage = [0; 3];
+///
+/// let output = hector::decode_prefixed_to_slice("0xc0ffeg", &mut storage);
+/// assert_eq!(output, Err(DecodeError::InvalidHex { offset: 7, value: b'g' }));
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn decode_prefixed_to_slice<T: AsRef<[u8]>>(
+    input: T,
+    output: &mut [u8],
+) -> Result<&[u8], Error> {
+    let hex = strip_prefix(input.as_ref())?;
+
+    imp::decode_to_slice(hex, output).map_err(|err| err.offset_by(2))
+}
+
+/// Decode the hex encoded `input`, returning an error instead of aborting if allocating the output fails.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::OddLength`] if `input.len()` is not even.
+/// - [`Error::AllocationFailed`] if the output couldn't be allocated.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::try_decode("decaff"), Ok(vec![0xde, 0xca, 0xff]));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+/// assert_eq!(hector::try_decode(b"abc"), Err(DecodeError::OddLength));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn try_decode<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, Error> {
+    let input = input.as_ref();
+
+    if !input.len().is_multiple_of(2) {
+        return Err(Error::OddLength);
+    }
+
+    let len = input.len() / 2;
+
+    let mut output = Vec::new();
+    output
+        .try_reserve_exact(len)
+        .map_err(|_| crate::AllocationError { len })?;
+
+    // This won't need to allocate anymore.
+    decode_append(input, &mut output)?;
+
+    Ok(output)
+}
+
+/// Decode the hex encoded `input`, allocated at exactly the right size.
+///
+/// A `Box<[u8]>` has no spare capacity to carry around, which makes it a good fit for results that are stored for a long time.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::OddLength`] if `input.len()` is not even.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// let bytes: Box<[u8]> = hector::decode_boxed("decaff")?;
+///
+/// assert_eq!(&*bytes, [0xde, 0xca, 0xff]);
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_boxed<T: AsRef<[u8]>>(input: T) -> Result<alloc::boxed::Box<[u8]>, Error> {
+    // `decode` allocates exactly the right size, so this doesn't reallocate.
+    imp::decode(input.as_ref()).map(Vec::into_boxed_slice)
+}
+
+/// Decode the hex encoded `input` into a `Vec` allocated in `alloc` (like an arena), at exactly the right size.
+///
+/// This needs a nightly compiler (with the `allocator-api` feature).
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::OddLength`] if `input.len()` is not even.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::Global;
+///
+/// let bytes = hector::decode_in("decaff", Global)?;
+///
+/// assert_eq!(bytes, [0xde, 0xca, 0xff]);
+/// assert_eq!(bytes.capacity(), 3);
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[cfg(feature = "allocator-api")]
+pub fn decode_in<T: AsRef<[u8]>, A: core::alloc::Allocator>(
+    input: T,
+    alloc: A,
+) -> Result<alloc::vec::Vec<u8, A>, Error> {
+    let input = input.as_ref();
+
+    if !input.len().is_multiple_of(2) {
+        return Err(Error::OddLength);
+    }
+
+    let out_len = input.len() / 2;
+
+    let mut output = alloc::vec::Vec::with_capacity_in(out_len, alloc);
+    crate::output::try_append_in(&mut output, out_len, |spare| {
+        imp::decode_uninit(input, spare)
+    })?;
+
+    Ok(output)
+}
+
+/// Decode the hex encoded `input`, appending the decoded bytes to `output`.
+///
+/// Returns the number of bytes appended.
+///
+/// Unlike [`decode`], this reuses `output`'s allocation (growing it if needed),
+/// which avoids an allocation per call when accumulating the output of many inputs.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::OddLength`] if `input.len()` is not even.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// If this returns an error, `output` is left as it was (though it may have more capacity).
+///
+/// # Examples
+/// ```
+/// let mut output = vec![0x00];
+///
+/// assert_eq!(hector::decode_append("decaff", &mut output), Ok(3));
+/// assert_eq!(hector::decode_append("C0Ffee", &mut output), Ok(3));
+///
+/// assert_eq!(output, [0x00, 0xde, 0xca, 0xff, 0xc0, 0xff, 0xee]);
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+/// let mut output = vec![0x00];
+///
+/// assert_eq!(hector::decode_append("c0ffeg", &mut output), Err(DecodeError::InvalidHex { offset: 5, value: b'g' }));
+/// assert_eq!(output, [0x00]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_append<T: AsRef<[u8]>>(input: T, output: &mut Vec<u8>) -> Result<usize, Error> {
+    let input = input.as_ref();
+
+    if !input.len().is_multiple_of(2) {
+        return Err(Error::OddLength);
+    }
+
+    let out_len = input.len() / 2;
+
+    crate::output::try_append(output, out_len, |spare| imp::decode_uninit(input, spare))?;
+
+    Ok(out_len)
+}
+
+/// Decode the hex encoded `input` into possibly uninitialized memory.
+///
+/// This is useful for decoding directly into memory that hasn't been initialized yet,
+/// such as the spare capacity of a `Vec`, without having to zero it first.
+///
+/// For convenience, this returns the (now initialized) output.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::MismatchedLength`] if `input.len() != output.len() * 2`.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// If this returns an error, some of `output` may have been written to, but it is not safe to assume any of it is initialized.
+///
+/// # Examples
+/// ```
+/// use core::mem::MaybeUninit;
+///
+/// let mut storage = [MaybeUninit::uninit(); 3];
+///
+/// assert_eq!(hector::decode_to_uninit_slice("decaff", &mut storage), Ok([0xde, 0xca, 0xff].as_slice()));
+/// ```
+///
+/// ```
+/// let input = "48656c6c6f2c20776f726c6421";
+/// let mut output = Vec::with_capacity(input.len() / 2);
+///
+/// hector::decode_to_uninit_slice(input, &mut output.spare_capacity_mut()[..input.len() / 2])?;
+///
+/// // Safety: `decode_to_uninit_slice` succeeded, so it initialized all the bytes.
+/// unsafe { output.set_len(input.len() / 2) };
+/// assert_eq!(output, b"Hello, world!");
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+// There's no safe way to go from initialized `MaybeUninit`s back to bytes, so this doesn't exist with the `safe` feature.
+#[cfg(not(feature = "safe"))]
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn decode_to_uninit_slice<T: AsRef<[u8]>>(
+    input: T,
+    output: &mut [MaybeUninit<u8>],
+) -> Result<&[u8], Error> {
+    let input = input.as_ref();
+
+    if input.len() != output.len() * 2 {
+        return Err(Error::MismatchedLength {
+            source_len: input.len(),
+            dest_len: output.len(),
+        });
+    }
+
+    imp::decode_uninit(input, output)?;
+
+    // Safety: `decode_uninit` returned `Ok`, so it initialized all of `output`,
+    // and `MaybeUninit<u8>` has the same layout as `u8`.
+    Ok(unsafe { &*(output as *const [MaybeUninit<u8>] as *const [u8]) })
+}
+
+/// Decode the hex encoded `buffer` into its own first half.
+///
+/// Returns the decoded prefix of `buffer`, what's left after it is unspecified.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::OddLength`] if `buffer.len()` is not even.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// If this returns an error, `buffer` is left unchanged.
+///
+/// # Examples
+/// ```
+/// let mut buffer = *b"decaff";
+///
+/// assert_eq!(hector::decode_in_place(&mut buffer), Ok([0xde, 0xca, 0xff].as_slice()));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+/// let mut buffer = *b"c0ffeg";
+///
+/// assert_eq!(hector::decode_in_place(&mut buffer), Err(DecodeError::InvalidHex { offset: 5, value: b'g' }));
+/// assert_eq!(&buffer, b"c0ffeg");
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn decode_in_place(buffer: &mut [u8]) -> Result<&[u8], Error> {
+    validate(&*buffer)?;
+
+    let len = fallback::decode_in_place_trusted(buffer);
+
+    Ok(&buffer[..len])
+}
+
+/// Decode the hex encoded `input`, reusing its allocation for the output.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::OddLength`] if `input.len()` is not even.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// Along with the error, the original (unchanged) `input` is returned.
+///
+/// # Examples
+/// ```
+/// let message = b"decaff".to_vec();
+///
+/// assert_eq!(hector::decode_vec(message), Ok(vec![0xde, 0xca, 0xff]));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// let message = b"c0ffeg".to_vec();
+/// let (error, message) = hector::decode_vec(message).unwrap_err();
+///
+/// assert_eq!(error, DecodeError::InvalidHex { offset: 5, value: b'g' });
+/// assert_eq!(message, b"c0ffeg");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_vec(mut input: Vec<u8>) -> Result<Vec<u8>, (Error, Vec<u8>)> {
+    match decode_in_place(&mut input) {
+        Ok(output) => {
+            let len = output.len();
+            input.truncate(len);
+            Ok(input)
+        }
+        Err(err) => Err((err, input)),
+    }
+}
+
+/// Decode the hex encoded `input`, extending `output` with the bytes, and returning how many there were.
+///
+/// This works with any byte collection (like a `BTreeSet<u8>`, or a `VecDeque<u8>` used as a ring buffer),
+/// without an intermediate buffer. `input` is checked before `output` is extended, so it's left unchanged on error.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::OddLength`] if `input.len()` is not even.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// use std::collections::{BTreeSet, VecDeque};
+///
+/// let mut seen = BTreeSet::new();
+/// assert_eq!(hector::decode_extend("c0ffee", &mut seen), Ok(3));
+/// assert!(seen.into_iter().eq([0xc0, 0xee, 0xff]));
+///
+/// let mut ring = VecDeque::from([0x01]);
+/// hector::decode_extend("decaff", &mut ring)?;
+/// assert!(ring.into_iter().eq([0x01, 0xde, 0xca, 0xff]));
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// let mut output = vec![0x01];
+///
+/// assert_eq!(hector::decode_extend("decafg", &mut output), Err(DecodeError::InvalidHex { offset: 5, value: b'g' }));
+/// assert_eq!(output, [0x01]);
+/// ```
+pub fn decode_extend<T: AsRef<[u8]>, E: Extend<u8> + ?Sized>(
+    input: T,
+    output: &mut E,
+) -> Result<usize, Error> {
+    let input = input.as_ref();
+
+    crate::decode_into(input, &mut crate::sink::ExtendSink(output))?;
+
+    Ok(input.len() / 2)
+}
+
+/// Decode the hex encoded `input` into an `arrayvec::ArrayVec` with a fixed capacity of `CAP` on the stack (without `alloc`).
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::OddLength`] if `input.len()` is not even.
+/// - [`Error::MismatchedLength`] if `input` decodes to more than `CAP` bytes, with `dest_len` as `CAP`.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// let bytes: arrayvec::ArrayVec<u8, 8> = hector::decode_arrayvec("decaff")?;
+/// assert_eq!(bytes.as_slice(), [0xde, 0xca, 0xff]);
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// let output = hector::decode_arrayvec::<2>("decaff");
+/// assert_eq!(output, Err(DecodeError::MismatchedLength { source_len: 6, dest_len: 2 }));
+/// ```
+#[cfg(feature = "arrayvec")]
+pub fn decode_arrayvec<const CAP: usize>(
+    input: impl AsRef<[u8]>,
+) -> Result<arrayvec::ArrayVec<u8, CAP>, Error> {
+    let mut output = arrayvec::ArrayVec::new();
+    crate::decode_into(input, &mut output)?;
+
+    Ok(output)
+}
+
+/// Decode the hex encoded `input` into a `heapless::Vec` with a fixed capacity of `N` (without `alloc`).
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::OddLength`] if `input.len()` is not even.
+/// - [`Error::MismatchedLength`] if `input` decodes to more than `N` bytes, with `dest_len` as `N`.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// let bytes: heapless::Vec<u8, 8> = hector::decode_heapless("decaff")?;
+/// assert_eq!(bytes, [0xde, 0xca, 0xff]);
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// let output = hector::decode_heapless::<2>("decaff");
+/// assert_eq!(output, Err(DecodeError::MismatchedLength { source_len: 6, dest_len: 2 }));
+/// ```
+#[cfg(feature = "heapless")]
+pub fn decode_heapless<const N: usize>(
+    input: impl AsRef<[u8]>,
+) -> Result<heapless::Vec<u8, N>, Error> {
+    let mut output = heapless::Vec::new();
+    crate::decode_into(input, &mut output)?;
+
+    Ok(output)
+}
+
+/// Decode the hex encoded `input` into a `smallvec::SmallVec`, which only allocates if the output doesn't fit in `A`.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::OddLength`] if `input.len()` is not even.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+/// - [`Error::AllocationFailed`] if the output doesn't fit in `A`, and couldn't be allocated.
+///
+/// # Examples
+/// ```
+/// use smallvec::SmallVec;
+///
+/// let digest: SmallVec<[u8; 32]> = hector::decode_smallvec("decaff")?;
+/// assert_eq!(digest.as_slice(), [0xde, 0xca, 0xff]);
+/// assert!(!digest.spilled());
+///
+/// let long: SmallVec<[u8; 2]> = hector::decode_smallvec("decaff")?;
+/// assert!(long.spilled());
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[cfg(feature = "smallvec")]
+pub fn decode_smallvec<A: smallvec::Array<Item = u8>>(
+    input: impl AsRef<[u8]>,
+) -> Result<smallvec::SmallVec<A>, Error> {
+    let mut output = smallvec::SmallVec::new();
+    crate::decode_into(input, &mut output)?;
+
+    Ok(output)
+}
+
+/// Decode the hex encoded `input` into an array.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::MismatchedLength`] if `input.len() != N * 2`.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::decode_to_array("decaff"), Ok([0xde, 0xca, 0xff]));
+/// assert_eq!(hector::decode_to_array(b"C0Ffee"), Ok([0xc0, 0xff, 0xee]));
+///
+/// let digest: [u8; 4] = hector::decode_to_array("deadbeef")?;
+/// assert_eq!(digest, [0xde, 0xad, 0xbe, 0xef]);
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// let output = hector::decode_to_array::<_, 2>("abcdef");
+/// assert_eq!(output, Err(DecodeError::MismatchedLength { source_len: 6, dest_len: 2 }));
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn decode_to_array<T: AsRef<[u8]>, const N: usize>(input: T) -> Result<[u8; N], Error> {
+    let mut output = [0; N];
+
+    decode_into_array(input, &mut output)?;
+
+    Ok(output)
+}
+
+/// Decode the hex encoded `input` into `output`.
+///
+/// For convenience, this returns a reference to `output`.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::MismatchedLength`] if `input.len() != N * 2`.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 3];
+///
+/// assert_eq!(hector::decode_into_array("decaff", &mut storage), Ok(&[0xde, 0xca, 0xff]));
+/// assert_eq!(storage, [0xde, 0xca, 0xff]);
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+/// let mut storage = [0; 3];
+///
+/// let output = hector::decode_into_array("c0ffeg", &mut storage);
+/// assert_eq!(output, Err(DecodeError::InvalidHex { offset: 5, value: b'g' }));
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn decode_into_array<T: AsRef<[u8]>, const N: usize>(
+    input: T,
+    output: &mut [u8; N],
+) -> Result<&[u8; N], Error> {
+    imp::decode_to_slice(input.as_ref(), output)?;
+
+    Ok(output)
+}
+
+/// Decode the hex number in `input` into an array, right-aligned, filling the start with zeros.
+///
+/// This is for big-endian numbers that are written without their leading zeros (like `1f4` for a 32-byte word),
+/// an odd number of hex characters decodes as if there was an extra `0` at the start.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::MismatchedLength`] if `input.len() > N * 2`.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::decode_right_aligned("1f4"), Ok([0x00, 0x01, 0xf4]));
+/// assert_eq!(hector::decode_right_aligned("decaff"), Ok([0xde, 0xca, 0xff]));
+/// assert_eq!(hector::decode_right_aligned(""), Ok([0; 3]));
+///
+/// let word: [u8; 32] = hector::decode_right_aligned("de0b6b3a7640000")?;
+/// assert_eq!(word[24..], 1_000_000_000_000_000_000_u64.to_be_bytes());
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// let output = hector::decode_right_aligned::<_, 2>("abcde");
+/// assert_eq!(output, Err(DecodeError::MismatchedLength { source_len: 5, dest_len: 2 }));
+///
+/// let output = hector::decode_right_aligned::<_, 2>("c0g");
+/// assert_eq!(output, Err(DecodeError::InvalidHex { offset: 2, value: b'g' }));
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn decode_right_aligned<T: AsRef<[u8]>, const N: usize>(input: T) -> Result<[u8; N], Error> {
+    let input = input.as_ref();
+
+    if input.len() > N * 2 {
+        return Err(Error::MismatchedLength {
+            source_len: input.len(),
+            dest_len: N,
+        });
+    }
+
+    let mut output = [0; N];
+
+    let (odd, pairs) = input.split_at(input.len() % 2);
+    let (padding, bytes) = output.split_at_mut(N - pairs.len() / 2);
+
+    // The odd character is the low nibble of the byte before the rest.
+    if let (Some(byte), &[digit]) = (padding.last_mut(), odd) {
+        *byte = fallback::decode_nibble(digit, 0)?;
+    }
+
+    imp::decode_to_slice(pairs, bytes).map_err(|err| err.offset_by(odd.len()))?;
+
+    Ok(output)
+}
+
+/// Decode the hex encoded `input` into an array, in a const context.
+///
+/// This is [`decode_to_array`] for `const` and `static` initializers,
+/// it's slower, so prefer [`decode_to_array`] at runtime.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::MismatchedLength`] if `input.len() != N * 2`.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// const KEY: [u8; 3] = match hector::decode_array(b"decaff") {
+///     Ok(key) => key,
+///     Err(_) => panic!("invalid key"),
+/// };
+///
+/// assert_eq!(KEY, [0xde, 0xca, 0xff]);
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// const OUTPUT: Result<[u8; 3], DecodeError> = hector::decode_array("c0ffeg".as_bytes());
+/// assert_eq!(OUTPUT, Err(DecodeError::InvalidHex { offset: 5, value: b'g' }));
+/// ```
+pub const fn decode_array<const N: usize>(input: &[u8]) -> Result<[u8; N], Error> {
+    fallback::decode_array_const(input)
+}
+
+/// Decode the hex encoded `input`, without checking that it's valid.
+///
+/// This skips validation entirely, which is useful for data that is already known to be valid,
+/// such as data that has already been through [`validate`] or was produced by [`encode`](crate::encode).
+///
+/// # Safety
+/// `input` must be valid hex, that is, [`validate(input)`](validate) must return `Ok`.
+///
+/// # Examples
+/// ```
+/// let encoded = hector::encode([0xde, 0xca, 0xff]);
+///
+/// // Safety: `encode` always produces valid hex.
+/// let decoded = unsafe { hector::decode_unchecked(&encoded) };
+///
+/// assert_eq!(decoded, [0xde, 0xca, 0xff]);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg(not(feature = "safe"))]
+#[must_use]
+pub unsafe fn decode_unchecked<T: AsRef<[u8]>>(input: T) -> Vec<u8> {
+    let input = input.as_ref();
+    let out_len = input.len() / 2;
+
+    let mut output = Vec::with_capacity(out_len);
+
+    // Since `input` is valid, it has an even length, so this is all of it.
+    crate::output::append(&mut output, out_len, |spare| {
+        imp::decode_uninit_trusted(&input[..out_len * 2], spare);
+    });
+
+    output
+}
+
+/// Decode the hex encoded `input` into `output`, without checking that `input` is valid.
+///
+/// For convenience, this returns a reference to `output`.
+///
+/// This skips validation entirely, which is useful for data that is already known to be valid,
+/// such as data that has already been through [`validate`] or was produced by [`encode_to_slice`](crate::encode_to_slice).
+///
+/// # Safety
+/// `input` must be valid hex, that is, [`validate(input)`](validate) must return `Ok`.
+///
+/// # Errors
+/// - [`Error::MismatchedLength`] if `input.len() != output.len() * 2`, the lengths are still checked.
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 3];
+///
+/// // Safety: `"decaff"` is valid hex.
+/// let decoded = unsafe { hector::decode_to_slice_unchecked("decaff", &mut storage) };
+///
+/// assert_eq!(decoded, Ok(&[0xde, 0xca, 0xff][..]));
+/// ```
+#[cfg(not(feature = "safe"))]
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub unsafe fn decode_to_slice_unchecked<T: AsRef<[u8]>>(
+    input: T,
+    output: &mut [u8],
+) -> Result<&[u8], Error> {
+    let input = input.as_ref();
+
+    if input.len() != output.len() * 2 {
+        return Err(Error::MismatchedLength {
+            source_len: input.len(),
+            dest_len: output.len(),
+        });
+    }
+
+    decode_trusted_to_slice(input, output);
+
+    Ok(output)
+}
+
+/// Decode `input` into `output`, without checking that `input` is valid.
+///
+/// `input` *must* already be validated, and be exactly twice as long as `output`,
+/// otherwise the contents of `output` are unspecified.
+pub(crate) fn decode_trusted_to_slice(input: &[u8], output: &mut [u8]) {
+    #[cfg(not(feature = "safe"))]
+    // Safety: `MaybeUninit<u8>` has the same layout as `u8`, and `decode_uninit_trusted` never writes uninitialized bytes.
+    let output = unsafe { &mut *(output as *mut [u8] as *mut [MaybeUninit<u8>]) };
+
+    // With `safe` there's no SIMD, and the fallback can write straight to bytes.
+    imp::decode_uninit_trusted(input, output);
+}
+
+/// Check that `input` is valid hex, without decoding it.
+///
+/// This accepts exactly the inputs that [`decode`](crate::decode) would, and fails with the same errors,
+/// so it's useful for when hex needs to be stored verbatim (after being checked).
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::OddLength`] if `input.len()` is not even.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::validate("decaff"), Ok(()));
+/// assert_eq!(hector::validate("C0Ffee"), Ok(()));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// assert_eq!(hector::validate("abc"), Err(DecodeError::OddLength));
+/// assert_eq!(hector::validate("c0ffeg"), Err(DecodeError::InvalidHex { offset: 5, value: b'g' }));
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn validate<T: AsRef<[u8]>>(input: T) -> Result<(), Error> {
+    imp::validate(input.as_ref())
+}
+
+/// Find the first character of `input` that isn't a valid hex character, returning its offset and value.
+///
+/// Unlike [`validate`], this doesn't care about the length of `input`, only the characters in it.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::find_invalid("c0ffee"), None);
+/// assert_eq!(hector::find_invalid("c0ffeg"), Some((5, b'g')));
+///
+/// // Odd lengths are fine.
+/// assert_eq!(hector::find_invalid("abc"), None);
+/// ```
+#[must_use]
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn find_invalid<T: AsRef<[u8]>>(input: T) -> Option<(usize, u8)> {
+    match imp::validate_hex(input.as_ref()) {
+        Err(Error::InvalidHex { offset, value }) => Some((offset, value)),
+        _ => None,
+    }
+}
+
+/// Returns `true` if the hex encoding of `bytes` starts with `prefix` (in either case).
+///
+/// `prefix` can be an odd length, in which case its last character only has to match half a byte,
+/// this is what's needed for abbreviated identifiers (like git's object IDs).
+///
+/// # Errors
+/// - [`Error::InvalidHex`] if any character of `prefix` isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// let id = [0xde, 0xca, 0xff];
+///
+/// assert_eq!(hector::starts_with_hex_prefix(id, "deca"), Ok(true));
+/// assert_eq!(hector::starts_with_hex_prefix(id, "DEC"), Ok(true));
+/// assert_eq!(hector::starts_with_hex_prefix(id, ""), Ok(true));
+///
+/// assert_eq!(hector::starts_with_hex_prefix(id, "ded"), Ok(false));
+/// assert_eq!(hector::starts_with_hex_prefix(id, "decaff0"), Ok(false));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// assert_eq!(
+///     hector::starts_with_hex_prefix([0xde], "dx"),
+///     Err(DecodeError::InvalidHex { offset: 1, value: b'x' })
+/// );
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn starts_with_hex_prefix<A: AsRef<[u8]>, B: AsRef<[u8]>>(
+    bytes: A,
+    prefix: B,
+) -> Result<bool, Error> {
+    let (bytes, prefix) = (bytes.as_ref(), prefix.as_ref());
+
+    imp::validate_hex(prefix)?;
+
+    if prefix.len() > bytes.len() * 2 {
+        return Ok(false);
+    }
+
+    let (whole, half) = prefix.split_at(prefix.len() & !1);
+
+    if !crate::eq_hex(&bytes[..whole.len() / 2], whole) {
+        return Ok(false);
+    }
+
+    Ok(match half {
+        // Decoding the last character as the high half of a byte,
+        // the offset doesn't matter, since `prefix` was already validated.
+        &[char] => decode_pair([char, b'0'], 0)? >> 4 == bytes[whole.len() / 2] >> 4,
+        _ => true,
+    })
+}
+
+/// Returns `true` if `input` is valid hex.
+///
+/// This is a shorthand for [`validate(input).is_ok()`](validate).
+///
+/// # Examples
+/// ```
+/// assert!(hector::is_hex("decaff"));
+/// assert!(hector::is_hex(""));
+///
+/// assert!(!hector::is_hex("abc"));
+/// assert!(!hector::is_hex("hector"));
+/// ```
+#[must_use]
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn is_hex<T: AsRef<[u8]>>(input: T) -> bool {
+    validate(input).is_ok()
+}
+
+/// Decode the hex characters in `input`, skipping everything else.
+///
+/// This pairs up the hex characters that are left, like `xxd -r -p`, so it's for messy, human-produced input
+/// (copied from a terminal or a document, for instance), where an error isn't helpful.
+/// A hex character left over at the end is dropped.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::decode_lossy("de ca ff"), vec![0xde, 0xca, 0xff]);
+/// // Including the `0`s of prefixes.
+/// assert_eq!(hector::decode_lossy("0xC0, 0xFF, 0xEE"), vec![0x0c, 0x00, 0xff, 0x0e]);
+///
+/// // It's *lossy*, pairs don't have to be next to each other.
+/// assert_eq!(hector::decode_lossy("d-e:c.a f f!a"), vec![0xde, 0xca, 0xff]);
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn decode_lossy<T: AsRef<[u8]>>(input: T) -> Vec<u8> {
+    let input = input.as_ref();
+
+    let mut output = Vec::with_capacity(input.len() / 2);
+    output.extend(fallback::decode_lossy_bytes(input));
+
+    output
+}
+
+/// Decode the hex characters in `input` into `output`, skipping everything else, returning the number of bytes written.
+///
+/// See [`decode_lossy`] for the details, this stops once `output` is full.
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 4];
+///
+/// let len = hector::decode_lossy_to_slice("de ca ff", &mut storage);
+/// assert_eq!(&storage[..len], [0xde, 0xca, 0xff]);
+///
+/// let len = hector::decode_lossy_to_slice("c0 ff ee de ca ff", &mut storage);
+/// assert_eq!(&storage[..len], [0xc0, 0xff, 0xee, 0xde]);
+/// ```
+#[must_use]
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn decode_lossy_to_slice<T: AsRef<[u8]>>(input: T, output: &mut [u8]) -> usize {
+    let bytes = fallback::decode_lossy_bytes(input.as_ref());
+
+    let mut len = 0;
+    for (output, byte) in output.iter_mut().zip(bytes) {
+        *output = byte;
+        len += 1;
+    }
+
+    len
+}
+
+/// Decode the hex encoded `input`, in reverse byte order (`ffcade` is `[0xde, 0xca, 0xff]`).
+///
+/// This is the counterpart to [`encode_reverse`](crate::encode_reverse), for reading little-endian hashes
+/// (like Bitcoin's transaction and block IDs) the way they're usually shown, in a single pass.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::OddLength`] if `input.len()` is not even.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::decode_reverse("ffcade"), Ok(vec![0xde, 0xca, 0xff]));
+/// assert_eq!(hector::decode_reverse(hector::encode_reverse(b"hi!")), Ok(b"hi!".to_vec()));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// assert_eq!(hector::decode_reverse("ffcadg"), Err(DecodeError::InvalidHex { offset: 5, value: b'g' }));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_reverse<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, Error> {
+    let input = input.as_ref();
+
+    if !input.len().is_multiple_of(2) {
+        return Err(Error::OddLength);
+    }
+
+    let mut output = alloc::vec![0; input.len() / 2];
+    fallback::decode_reverse_into(input, &mut output)?;
+
+    Ok(output)
+}
+
+/// Decode the hex encoded `input` into `output`, in reverse byte order.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::MismatchedLength`] if `input.len() != output.len() * 2`.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character,
+///   `output` may have been partly written (like with [`decode_to_slice`]).
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 4];
+///
+/// hector::decode_reverse_to_slice("04030201", &mut storage)?;
+/// assert_eq!(u32::from_le_bytes(storage), 0x0403_0201);
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn decode_reverse_to_slice<T: AsRef<[u8]>>(
+    input: T,
+    output: &mut [u8],
+) -> Result<&[u8], Error> {
+    let input = input.as_ref();
+
+    if input.len() != output.len() * 2 {
+        return Err(Error::MismatchedLength {
+            source_len: input.len(),
+            dest_len: output.len(),
+        });
+    }
+
+    fallback::decode_reverse_into(input, output)?;
+
+    Ok(output)
+}
+
+/// Decode `input`, with the nibbles of each byte swapped, as in the semi-octet (TBCD) strings of GSM and SIM cards.
+///
+/// This is the counterpart to [`encode_swapped`](crate::encode_swapped), `123456` is `[0x21, 0x43, 0x65]`.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::OddLength`] if `input.len()` is not even, for a filler `f` at the end, see [`decode_swapped_filler`].
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::decode_swapped("123456"), Ok(vec![0x21, 0x43, 0x65]));
+/// assert_eq!(hector::decode_swapped("123F"), Ok(vec![0x21, 0xf3]));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// assert_eq!(hector::decode_swapped("123"), Err(DecodeError::OddLength));
+/// assert_eq!(hector::decode_swapped("12g4"), Err(DecodeError::InvalidHex { offset: 2, value: b'g' }));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_swapped<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, Error> {
+    let input = input.as_ref();
+
+    let mut output = Vec::with_capacity(input.len() / 2);
+    fallback::decode_swapped_with::<false>(input, |byte| output.push(byte))?;
+
+    Ok(output)
+}
+
+/// Decode `input`, with the nibbles of each byte swapped, filling the last nibble with `f` if there's an odd number of characters.
+///
+/// This is how semi-octet strings with an odd number of digits (like phone numbers in GSM TS 23.040) are stored,
+/// so `123` is `[0x21, 0xf3]`, and it's the counterpart to [`encode_swapped_filler`](crate::encode_swapped_filler).
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::decode_swapped_filler("31641600986"), Ok(vec![0x13, 0x46, 0x61, 0x00, 0x89, 0xf6]));
+/// assert_eq!(hector::decode_swapped_filler("1234"), Ok(vec![0x21, 0x43]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_swapped_filler<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, Error> {
+    let input = input.as_ref();
+
+    let mut output = Vec::with_capacity(input.len().div_ceil(2));
+    fallback::decode_swapped_with::<true>(input, |byte| output.push(byte))?;
+
+    Ok(output)
+}
+
+/// Decode `input` into `output`, with the nibbles of each byte swapped, see [`decode_swapped`].
+///
+/// # Errors
+/// - [`Error::MismatchedLength`] if `input.len() != output.len() * 2`.
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character,
+///   `output` may have been partly written (like with [`decode_to_slice`]).
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 3];
+///
+/// assert_eq!(hector::decode_swapped_to_slice("890141", &mut storage), Ok([0x98, 0x10, 0x14].as_slice()));
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn decode_swapped_to_slice<T: AsRef<[u8]>>(
+    input: T,
+    output: &mut [u8],
+) -> Result<&[u8], Error> {
+    let input = input.as_ref();
+
+    if input.len() != output.len() * 2 {
+        return Err(Error::MismatchedLength {
+            source_len: input.len(),
+            dest_len: output.len(),
+        });
+    }
+
+    let mut bytes = output.iter_mut();
+    fallback::decode_swapped_with::<false>(input, |byte| {
+        if let Some(output) = bytes.next() {
+            *output = byte;
+        }
+    })?;
+
+    Ok(output)
+}
+
+/// Decode the hex number in `input` to big-endian bytes, rejecting leading zeros.
+///
+/// This is the strict counterpart to [`encode_trimmed`](crate::encode_trimmed),
+/// for formats like Ethereum's JSON-RPC quantities (after the `0x`), where every number has exactly one encoding.
+/// An odd number of hex characters decodes as if there was an extra `0` at the start, so `abc` is `[0x0a, 0xbc]` and `0` is `[0x00]`.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::NonCanonical`] if `input` is empty, or starts with a `0` (other than `0` itself).
+/// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// use hector::DecodeError;
+///
+/// assert_eq!(hector::decode_trimmed("400"), Ok(vec![0x04, 0x00]));
+/// assert_eq!(hector::decode_trimmed("0"), Ok(vec![0x00]));
+///
+/// assert_eq!(hector::decode_trimmed("0400"), Err(DecodeError::NonCanonical));
+/// assert_eq!(hector::decode_trimmed(""), Err(DecodeError::NonCanonical));
+///
+/// let quantity = "0x41";
+/// assert_eq!(hector::decode_trimmed(&quantity[2..]), Ok(vec![0x41]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_trimmed<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, Error> {
+    let input = input.as_ref();
+
+    if let [] | [b'0', _, ..] = input {
+        return Err(Error::NonCanonical);
+    }
+
+    DecodeOptions::new()
+        .odd_length(OddLength::PadStart)
+        .decode(input)
+}
+
+/// Decode `input`, made of `\xNN` escapes (like in Rust and Python byte strings), `\xde\xca\xff` is `[0xde, 0xca, 0xff]`.
+///
+/// This is the counterpart to [`encode_escaped`](crate::encode_escaped),
+/// to allow literal ascii in between the escapes, see [`decode_escaped_ascii`].
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::InvalidHex`] if a character isn't part of an escape, or an escape has an invalid hex character,
+///   the offset is into `input`.
+/// - [`Error::OddLength`] if `input` ends partway through an escape.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::decode_escaped(r"\xde\xca\xff"), Ok(vec![0xde, 0xca, 0xff]));
+/// assert_eq!(hector::decode_escaped(r"\x31\xC0\xC3"), Ok(vec![0x31, 0xc0, 0xc3]));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// assert_eq!(hector::decode_escaped(r"\xde\xcz"), Err(DecodeError::InvalidHex { offset: 7, value: b'z' }));
+/// assert_eq!(hector::decode_escaped(r"\xdeca"), Err(DecodeError::InvalidHex { offset: 4, value: b'c' }));
+/// assert_eq!(hector::decode_escaped(r"\xde\xc"), Err(DecodeError::OddLength));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_escaped<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, Error> {
+    let input = input.as_ref();
+
+    // Exact for valid input, every byte is 4 characters.
+    let mut output = Vec::with_capacity(input.len() / 4);
+    fallback::decode_escaped_with::<false>(input, |byte| output.push(byte))?;
+
+    Ok(output)
+}
+
+/// Decode `input`, made of `\xNN` escapes and printable ascii, like a byte string copied out of source code or a debugger.
+///
+/// The escapes that Rust and Python share for characters that can't be written as is
+/// (`\\`, `\'`, `\"`, `\0`, `\t`, `\n`, and `\r`) are decoded too,
+/// so this is the counterpart to both [`encode_escaped_ascii`](crate::encode_escaped_ascii) and [`escape_ascii`](slice::escape_ascii).
+///
+/// # Errors
+/// - [`Error::InvalidHex`] if a character isn't printable ascii, a `\` isn't followed by a known escape,
+///   or an escape has an invalid hex character, the offset is into `input`.
+/// - [`Error::OddLength`] if `input` ends partway through an escape.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::decode_escaped_ascii(r"GET /\x0d\x0a\xff"), Ok(b"GET /\r\n\xff".to_vec()));
+/// assert_eq!(hector::decode_escaped_ascii(r#"say \"hi\"\n"#), Ok(b"say \"hi\"\n".to_vec()));
+///
+/// let escaped = b"\x00ok\x7f".escape_ascii().to_string();
+/// assert_eq!(hector::decode_escaped_ascii(escaped), Ok(b"\x00ok\x7f".to_vec()));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// assert_eq!(hector::decode_escaped_ascii(r"ok\q"), Err(DecodeError::InvalidHex { offset: 3, value: b'q' }));
+/// assert_eq!(hector::decode_escaped_ascii("tab\t"), Err(DecodeError::InvalidHex { offset: 3, value: b'\t' }));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_escaped_ascii<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, Error> {
+    let mut output = Vec::new();
+    fallback::decode_escaped_with::<true>(input.as_ref(), |byte| output.push(byte))?;
+
+    Ok(output)
+}
+
+/// Decode `input`, which has exactly one `separator` between each `group` bytes, passing each byte to `push`.
+fn decode_delimited_with(
+    input: &[u8],
+    separator: char,
+    group: usize,
+    mut push: impl FnMut(u8) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let mut buffer = [0; 4];
+    let separator = separator.encode_utf8(&mut buffer).as_bytes();
+
+    let mut rest = input;
+    let mut len = 0_usize;
+
+    while !rest.is_empty() {
+        let offset = input.len() - rest.len();
+
+        if len != 0 && len.is_multiple_of(group) {
+            rest = match rest.strip_prefix(separator) {
+                // A separator at the end is missing the byte after it.
+                Some([]) => return Err(Error::OddLength),
+                Some(rest) => rest,
+                None => {
+                    let value = rest.first().copied().unwrap_or_default();
+                    return Err(Error::InvalidHex { offset, value });
+                }
+            };
+        }
+
+        let offset = input.len() - rest.len();
+
+        let [high, low, tail @ ..] = rest else {
+            return Err(Error::OddLength);
+        };
+
+        push(decode_pair([*high, *low], offset)?)?;
+        rest = tail;
+        len += 1;
+    }
+
+    Ok(())
+}
+
+/// Decode `input`, which has exactly one `separator` between each byte (`aa:bb:cc`).
+///
+/// This is the counterpart to [`encode_delimited`](crate::encode_delimited),
+/// for more relaxed input (separators anywhere, whitespace, ...), see [`DecodeOptions`].
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`Error::InvalidHex`] if a character isn't a valid hex character, or a separator is missing or out of place,
+///   the offset is into `input` (counting separators).
+/// - [`Error::OddLength`] if `input` ends halfway through a byte (or right after a separator).
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::decode_delimited("de:ca:ff", ':'), Ok(vec![0xde, 0xca, 0xff]));
+/// assert_eq!(hector::decode_delimited("DE CA FF", ' '), Ok(vec![0xde, 0xca, 0xff]));
+/// assert_eq!(hector::decode_delimited("", ':'), Ok(vec![]));
+///
+/// let fingerprint = hector::encode_delimited([0xc0, 0xff, 0xee], '→');
+/// assert_eq!(hector::decode_delimited(fingerprint, '→'), Ok(vec![0xc0, 0xff, 0xee]));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// assert_eq!(hector::decode_delimited("de:caff", ':'), Err(DecodeError::InvalidHex { offset: 5, value: b'f' }));
+/// assert_eq!(hector::decode_delimited("de::ca", ':'), Err(DecodeError::InvalidHex { offset: 3, value: b':' }));
+/// assert_eq!(hector::decode_delimited("de:ca:", ':'), Err(DecodeError::OddLength));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_delimited<T: AsRef<[u8]>>(input: T, separator: char) -> Result<Vec<u8>, Error> {
+    let input = input.as_ref();
+
+    // Exact for valid input, with `n` bytes that's `2n` hex characters and `n - 1` separators.
+    let separator_len = separator.len_utf8();
+    let mut output = Vec::with_capacity((input.len() + separator_len) / (2 + separator_len));
+
+    decode_delimited_with(input, separator, 1, |byte| {
+        output.push(byte);
+        Ok(())
+    })?;
+
+    Ok(output)
+}
+
+/// Decode `input`, which has exactly one `separator` between each byte (`aa:bb:cc`), into `output`.
+///
+/// See [`decode_delimited`] for the details.
+///
+/// # Errors
+/// - [`Error::InvalidHex`] if a character isn't a valid hex character, or a separator is missing or out of place,
+///   the offset is into `input` (counting separators).
+/// - [`Error::OddLength`] if `input` ends halfway through a byte (or right after a separator).
+/// - [`Error::MismatchedLength`] if `input` doesn't decode to exactly `output.len()` bytes,
+///   `source_len` is the number of hex characters (not counting separators).
+///
+/// On error, `output` may have been partly written (like with [`decode_to_slice`]).
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 3];
+///
+/// assert_eq!(hector::decode_delimited_to_slice("de-ca-ff", '-', &mut storage), Ok([0xde, 0xca, 0xff].as_slice()));
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn decode_delimited_to_slice<T: AsRef<[u8]>>(
+    input: T,
+    separator: char,
+    output: &mut [u8],
+) -> Result<&[u8], Error> {
+    decode_grouped_to_slice(input.as_ref(), separator, 1, output)
+}
+
+/// Decode `input`, which has exactly one `separator` between each `group` bytes (`aabb.ccdd`), into `output`.
+pub(crate) fn decode_grouped_to_slice<'a>(
+    input: &[u8],
+    separator: char,
+    group: usize,
+    output: &'a mut [u8],
+) -> Result<&'a [u8], Error> {
+    // The length is only known at the end, so bytes past the end of `output` are just counted.
+    let mut len = 0;
+    decode_delimited_with(input, separator, group, |byte| {
+        if let Some(output) = output.get_mut(len) {
+            *output = byte;
+        }
+
+        len += 1;
+        Ok(())
+    })?;
+
+    if len != output.len() {
+        return Err(Error::MismatchedLength {
+            source_len: len * 2,
+            dest_len: output.len(),
+        });
+    }
+
+    Ok(output)
+}
+
+/// The length of the longest even length prefix of `input` that's valid hex.
+fn valid_hex_len(input: &[u8]) -> usize {
+    let len = match imp::validate_hex(input) {
+        Err(Error::InvalidHex { offset, .. }) => offset,
+        _ => input.len(),
+    };
+
+    len & !1
+}
+
+/// Decode the longest (even length) hex prefix of `input`, returning the decoded bytes and the rest of `input`.
+///
+/// This is for pulling a hex token out of a larger buffer, without finding where it ends first.
+/// A hex character left over at the end of the prefix is part of the rest.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::take_hex(b"decaff rest"), (vec![0xde, 0xca, 0xff], b" rest".as_slice()));
+/// assert_eq!(hector::take_hex(b"abc,"), (vec![0xab], b"c,".as_slice()));
+/// assert_eq!(hector::take_hex(b"xyz"), (vec![], b"xyz".as_slice()));
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn take_hex(input: &[u8]) -> (Vec<u8>, &[u8]) {
+    let (hex, rest) = input.split_at(valid_hex_len(input));
+    let mut output = Vec::with_capacity(hex.len() / 2);
+
+    // `hex` was just validated, so there's no need to do it again.
+    crate::output::append(&mut output, hex.len() / 2, |spare| {
+        imp::decode_uninit_trusted(hex, spare);
+    });
+
+    (output, rest)
+}
+
+/// Decode the longest (even length) hex prefix of `input` that fits into `output`,
+/// returning the number of bytes written, and the number of characters of `input` used.
+///
+/// Like [`take_hex`], but this stops once `output` is full.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 4];
+/// let input = b"decaff;c0ffee";
+///
+/// let (written, consumed) = hector::take_hex_to_slice(input, &mut storage);
+/// assert_eq!(&storage[..written], [0xde, 0xca, 0xff]);
+/// assert_eq!(&input[consumed..], b";c0ffee");
+///
+/// let (written, consumed) = hector::take_hex_to_slice(b"c0ffeedecaff", &mut storage);
+/// assert_eq!(&storage[..written], [0xc0, 0xff, 0xee, 0xde]);
+/// assert_eq!(consumed, 8);
+/// ```
+#[must_use]
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn take_hex_to_slice(input: &[u8], output: &mut [u8]) -> (usize, usize) {
+    let len = valid_hex_len(input).min(output.len() * 2);
+
+    let (Some(hex), Some(output)) = (input.get(..len), output.get_mut(..len / 2)) else {
+        return (0, 0);
+    };
+
+    // `hex` was just validated, and `output` is the right length for it, so there's no need to do it again.
+    decode_trusted_to_slice(hex, output);
+
+    (output.len(), len)
+}
+
+/// Decoding into a container chosen by the caller, for generic code.
+///
+/// These don't enforce a specific casing convention.
+///
+/// # Examples
+/// ```
+/// use hector::FromHex;
+///
+/// assert_eq!(Vec::from_hex("decaff"), Ok(vec![0xde, 0xca, 0xff]));
+/// assert_eq!(<[u8; 3]>::from_hex("decaff"), Ok([0xde, 0xca, 0xff]));
+///
+/// fn parse_key<K: FromHex>(hex: &str) -> Option<K> {
+///     K::from_hex(hex).ok()
+/// }
+///
+/// let key: Option<Box<[u8]>> = parse_key("C0FFEE");
+/// assert_eq!(key.as_deref(), Some(&[0xc0, 0xff, 0xee][..]));
+/// ```
+pub trait FromHex: Sized {
+    /// Decode the hex encoded `input` into `Self`.
+    ///
+    /// # Errors
+    /// - [`Error::OddLength`] if `input.len()` is not even.
+    /// - [`Error::MismatchedLength`] if `Self` is fixed size, and `input` is the wrong length for it.
+    /// - [`Error::InvalidHex`] if any character isn't a valid hex character.
+    fn from_hex<T: AsRef<[u8]>>(input: T) -> Result<Self, Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl FromHex for alloc::vec::Vec<u8> {
+    fn from_hex<T: AsRef<[u8]>>(input: T) -> Result<Self, Error> {
+        decode(input)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromHex for alloc::boxed::Box<[u8]> {
+    fn from_hex<T: AsRef<[u8]>>(input: T) -> Result<Self, Error> {
+        decode(input).map(alloc::vec::Vec::into_boxed_slice)
+    }
+}
+
+/// # Examples
+/// ```
+/// use hector::{DecodeError, FromHex};
+///
+/// assert_eq!(
+///     <[u8; 2]>::from_hex("decaff"),
+///     Err(DecodeError::MismatchedLength { source_len: 6, dest_len: 2 })
+/// );
+/// ```
+impl<const N: usize> FromHex for [u8; N] {
+    fn from_hex<T: AsRef<[u8]>>(input: T) -> Result<Self, Error> {
+        decode_to_array(input)
+    }
 }
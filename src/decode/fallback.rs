@@ -1,27 +1,271 @@
 use super::Error;
+use crate::output::OutputByte;
 
-/// Decodes 4-bits worth of data
+/// The value [`decode_char`] returns for characters that aren't valid hex.
 ///
-/// this function assumes that the input is already a hex char.
-fn decode_trusted_char(nibble: u8) -> u8 {
-    if nibble > b'9' {
-        // Mask out the "lowercase" bit, subtract the start of the valid range (b'A'), then we still need to add 10.
-        (nibble & !0x20) - b'A' + 10
+/// Every valid nibble is `<= 0xf`, so this can be detected after or-ing a bunch of decoded characters together.
+const INVALID: u8 = 0xff;
+
+/// Maps every byte to the value of the hex character it represents, or [`INVALID`] if it isn't a hex character.
+#[cfg(feature = "lut")]
+static DECODE_LUT: [u8; 256] = {
+    let mut lut = [INVALID; 256];
+
+    let mut value = 0;
+    while value < 10 {
+        lut[(b'0' + value) as usize] = value;
+        value += 1;
+    }
+
+    let mut value = 0;
+    while value < 6 {
+        lut[(b'a' + value) as usize] = value + 10;
+        lut[(b'A' + value) as usize] = value + 10;
+        value += 1;
+    }
+
+    lut
+};
+
+/// Returns `true` if `value` is a hex character.
+#[inline(always)]
+fn is_hex_char(value: u8) -> bool {
+    #[cfg(feature = "lut")]
+    {
+        DECODE_LUT[value as usize] != INVALID
+    }
+
+    #[cfg(not(feature = "lut"))]
+    {
+        value.is_ascii_hexdigit()
+    }
+}
+
+/// Decodes 4-bits worth of data, or returns [`INVALID`] if `value` isn't a hex character.
+#[cfg(feature = "lut")]
+#[inline(always)]
+fn decode_char(value: u8) -> u8 {
+    DECODE_LUT[value as usize]
+}
+
+/// Decodes 4-bits worth of data, or returns [`INVALID`] if `value` isn't a hex character.
+#[cfg(not(feature = "lut"))]
+#[inline(always)]
+fn decode_char(value: u8) -> u8 {
+    decode_char_arithmetic(value)
+}
+
+/// Decodes 4-bits worth of data without a lookup table, or returns [`INVALID`] if `value` isn't a hex character.
+///
+/// This is a `const fn`, so that it can be used in [`decode_array_const`].
+#[inline(always)]
+const fn decode_char_arithmetic(value: u8) -> u8 {
+    // `0-9` map to `0..=9`, everything else wraps around to something bigger.
+    let digit = value.wrapping_sub(b'0');
+
+    // Setting the "lowercase" bit lets us handle both cases at once, then `a-f` map to `0..=5`.
+    let letter = (value | 0x20).wrapping_sub(b'a');
+
+    if digit < 10 {
+        digit
+    } else if letter < 6 {
+        letter + 10
     } else {
-        nibble - b'0'
+        INVALID
     }
-    // todo: Profile different versions such as:
-    // (nibble & 0xf) + (nibble >> 6) + ((nibble >> 6) << 3)
 }
 
+/// Decode `input` into an array, in a const context.
+///
+/// None of the faster paths work in const contexts, so this decodes a character at a time.
+///
+/// Errors are the same as [`decode_to_array`](crate::decode_to_array).
+pub(crate) const fn decode_array_const<const N: usize>(input: &[u8]) -> Result<[u8; N], Error> {
+    if input.len() != N * 2 {
+        return Err(Error::MismatchedLength {
+            source_len: input.len(),
+            dest_len: N,
+        });
+    }
+
+    let mut output = [0; N];
+
+    let mut index = 0;
+    while index < N {
+        let offset = index * 2;
+        let high = decode_char_arithmetic(input[offset]);
+        let low = decode_char_arithmetic(input[offset + 1]);
+
+        if high == INVALID {
+            return Err(Error::InvalidHex {
+                offset,
+                value: input[offset],
+            });
+        }
+
+        if low == INVALID {
+            return Err(Error::InvalidHex {
+                offset: offset + 1,
+                value: input[offset + 1],
+            });
+        }
+
+        output[index] = (high << 4) | low;
+        index += 1;
+    }
+
+    Ok(output)
+}
+
+/// Decode a single hex character into 4 bits.
+///
+/// `offset` is the offset of `value` in the input, for errors.
+pub(crate) fn decode_nibble(value: u8, offset: usize) -> Result<u8, Error> {
+    match decode_char(value) {
+        INVALID => Err(Error::InvalidHex { offset, value }),
+        nibble => Ok(nibble),
+    }
+}
+
+/// Decode the two hex characters in `pair` into a byte.
+///
+/// `offset` is the offset of `pair` in the input, for errors.
+pub(crate) fn decode_pair(pair: [u8; 2], offset: usize) -> Result<u8, Error> {
+    let high = decode_char(pair[0]);
+    let low = decode_char(pair[1]);
+
+    if high == INVALID {
+        return Err(Error::InvalidHex {
+            offset,
+            value: pair[0],
+        });
+    }
+
+    if low == INVALID {
+        return Err(Error::InvalidHex {
+            offset: offset + 1,
+            value: pair[1],
+        });
+    }
+
+    Ok(high << 4 | low)
+}
+
+/// Decode `input` into `output` in reverse byte order (the last pair of `input` is the first byte of `output`),
+/// `output` has to be exactly `input.len() / 2` bytes.
+pub(super) fn decode_reverse_into(input: &[u8], output: &mut [u8]) -> Result<(), Error> {
+    let pairs = input.chunks_exact(2).zip(output.iter_mut().rev());
+
+    for (index, (pair, output)) in pairs.enumerate() {
+        *output = decode_pair([pair[0], pair[1]], index * 2)?;
+    }
+
+    Ok(())
+}
+
+/// Decode `input`, with the nibbles of each byte swapped (`1234` is `[0x21, 0x43]`), passing each byte to `push`.
+///
+/// With `FILLER`, an odd number of characters is allowed, the last byte is filled with an `f` (so `123` is `[0x21, 0xf3]`).
+pub(super) fn decode_swapped_with<const FILLER: bool>(
+    input: &[u8],
+    mut push: impl FnMut(u8),
+) -> Result<(), Error> {
+    if !FILLER && !input.len().is_multiple_of(2) {
+        return Err(Error::OddLength);
+    }
+
+    let mut pairs = input.chunks_exact(2);
+    for (index, pair) in pairs.by_ref().enumerate() {
+        // Decoding as is keeps errors in order, the nibbles are swapped after.
+        push(decode_pair([pair[0], pair[1]], index * 2)?.rotate_left(4));
+    }
+
+    if let [value] = *pairs.remainder() {
+        push(decode_pair([value, b'f'], input.len() - 1)?.rotate_left(4));
+    }
+
+    Ok(())
+}
+
+/// Decode `input`, made of `\xNN` escapes, passing each byte to `push`.
+///
+/// With `ASCII`, printable ascii is also allowed (as itself), along with the escapes Rust and Python share
+/// for the characters that can't be written as is (`\\`, `\'`, `\"`, `\0`, `\t`, `\n`, and `\r`).
+#[cfg(feature = "alloc")]
+pub(super) fn decode_escaped_with<const ASCII: bool>(
+    input: &[u8],
+    mut push: impl FnMut(u8),
+) -> Result<(), Error> {
+    let mut rest = input;
+
+    while let Some((&value, tail)) = rest.split_first() {
+        let offset = input.len() - rest.len();
+
+        if value != b'\\' {
+            if !(ASCII && (value.is_ascii_graphic() || value == b' ')) {
+                return Err(Error::InvalidHex { offset, value });
+            }
+
+            push(value);
+            rest = tail;
+            continue;
+        }
+
+        let (byte, tail) = match tail {
+            [b'x', high, low, tail @ ..] => (decode_pair([*high, *low], offset + 2)?, tail),
+            [b'x', ..] | [] => return Err(Error::OddLength),
+            [value, tail @ ..] if ASCII => {
+                let byte = match value {
+                    b'\\' | b'\'' | b'"' => *value,
+                    b'0' => b'\0',
+                    b't' => b'\t',
+                    b'n' => b'\n',
+                    b'r' => b'\r',
+                    _ => {
+                        return Err(Error::InvalidHex {
+                            offset: offset + 1,
+                            value: *value,
+                        })
+                    }
+                };
+
+                (byte, tail)
+            }
+            [value, ..] => {
+                return Err(Error::InvalidHex {
+                    offset: offset + 1,
+                    value: *value,
+                })
+            }
+        };
+
+        push(byte);
+        rest = tail;
+    }
+
+    Ok(())
+}
+
+/// The bytes that the hex characters in `input` decode to, skipping everything else.
+///
+/// A digit left over at the end is dropped.
+pub(super) fn decode_lossy_bytes(input: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    let mut nibbles = input
+        .iter()
+        .map(|&char| decode_char(char))
+        .filter(|&nibble| nibble != INVALID);
+
+    core::iter::from_fn(move || Some((nibbles.next()? << 4) | nibbles.next()?))
+}
+
+/// The number of bytes checked at a time before looking for the precise location of an error.
+const BLOCK_LEN: usize = 64;
+
 // note: This function is effectively optimal for "errors are common, precise location of error is required"
-// better functions could easily exist for "errors are rare" and/or "location of error is not required"
-// notably, if errors are rare you could iterate through the list in chunks looking for an error,
-// and look again for where that error is, if one is found.
-// if the location of the error is not required you can do a SIMD fold.
-fn validate_hex(input: &[u8]) -> Result<(), Error> {
+// (which is exactly the situation it's in when a bad block has been found).
+fn find_invalid_hex(input: &[u8]) -> Result<(), Error> {
     input.iter().enumerate().try_for_each(|(offset, &value)| {
-        if value.is_ascii_hexdigit() {
+        if is_hex_char(value) {
             Ok(())
         } else {
             Err(Error::InvalidHex { offset, value })
@@ -29,31 +273,48 @@ fn validate_hex(input: &[u8]) -> Result<(), Error> {
     })
 }
 
-// todo: Faster impl
+// Errors are assumed to be rare, so this folds a validity flag over each block (which doesn't need the location of the error,
+// so it can be vectorized), and only goes back to look for the exact location of the error when a block is invalid.
+pub(super) fn validate_hex(input: &[u8]) -> Result<(), Error> {
+    let mut blocks = input.chunks_exact(BLOCK_LEN);
+
+    for (index, block) in (&mut blocks).enumerate() {
+        // `&` rather than `&&` so that there's no early exit.
+        let valid = block
+            .iter()
+            .fold(true, |valid, &value| valid & is_hex_char(value));
+
+        if !valid {
+            return find_invalid_hex(block).map_err(|err| err.offset_by(index * BLOCK_LEN));
+        }
+    }
+
+    let remainder = blocks.remainder();
+    find_invalid_hex(remainder).map_err(|err| err.offset_by(input.len() - remainder.len()))
+}
+
+pub(super) fn validate(input: &[u8]) -> Result<(), Error> {
+    if !input.len().is_multiple_of(2) {
+        return Err(Error::OddLength);
+    }
+
+    validate_hex(input)
+}
+
 #[cfg(feature = "alloc")]
 pub(super) fn decode(input: &[u8]) -> Result<alloc::vec::Vec<u8>, Error> {
-    if input.len() % 2 != 0 {
+    if !input.len().is_multiple_of(2) {
         return Err(Error::OddLength);
     }
 
-    validate_hex(input)?;
+    let out_len = input.len() / 2;
 
-    let output = input
-        .chunks_exact(2)
-        .map(|bytes| decode_trusted_nibbles([bytes[0], bytes[1]]))
-        .collect();
+    let mut output = alloc::vec::Vec::with_capacity(out_len);
+    crate::output::try_append(&mut output, out_len, |spare| decode_uninit(input, spare))?;
 
     Ok(output)
 }
 
-#[inline(always)]
-fn decode_trusted_nibbles(nibbles: [u8; 2]) -> u8 {
-    let high = decode_trusted_char(nibbles[0]);
-    let low = decode_trusted_char(nibbles[1]);
-
-    high << 4 | low
-}
-
 pub(super) fn decode_to_slice<'a>(input: &[u8], output: &'a mut [u8]) -> Result<&'a [u8], Error> {
     if input.len() != output.len() * 2 {
         return Err(Error::MismatchedLength {
@@ -62,20 +323,95 @@ pub(super) fn decode_to_slice<'a>(input: &[u8], output: &'a mut [u8]) -> Result<
         });
     }
 
-    validate_hex(input)?;
+    decode_uninit(input, output)?;
+
+    Ok(output)
+}
+
+/// Decode `input` into `output` in a single pass, returning `false` if any character of `input` wasn't valid hex.
+///
+/// All of `output` is written regardless, but its contents are unspecified if this returns `false`.
+#[inline(always)]
+fn decode_block<O: OutputByte>(input: &[u8], output: &mut [O]) -> bool {
+    let mut decoded = 0;
 
     for (out, nibbles) in output.iter_mut().zip(input.chunks_exact(2)) {
-        *out = decode_trusted_nibbles([nibbles[0], nibbles[1]]);
+        let high = decode_char(nibbles[0]);
+        let low = decode_char(nibbles[1]);
+
+        decoded |= high | low;
+        *out = O::new(high << 4 | low);
     }
 
-    Ok(output)
+    decoded & !0xf == 0
+}
+
+/// Decode `input` into `output`.
+///
+/// `output.len() * 2` must equal `input.len()`.
+///
+/// It is safe to assume that all of `output` is initialized after this function returns `Ok`.
+// Validation is fused into decoding (rather than being a separate pass over `input`) to save memory bandwidth,
+// but like `validate_hex`, errors are assumed to be rare.
+pub(super) fn decode_uninit<O: OutputByte>(input: &[u8], output: &mut [O]) -> Result<(), Error> {
+    debug_assert_eq!(input.len(), output.len() * 2);
+
+    let mut input_blocks = input.chunks_exact(BLOCK_LEN);
+    let mut output_blocks = output.chunks_exact_mut(BLOCK_LEN / 2);
+
+    for (index, (input, output)) in (&mut input_blocks).zip(&mut output_blocks).enumerate() {
+        if !decode_block(input, output) {
+            find_invalid_hex(input).map_err(|err| err.offset_by(index * BLOCK_LEN))?;
+        }
+    }
+
+    let remainder = input_blocks.remainder();
+    if !decode_block(remainder, output_blocks.into_remainder()) {
+        find_invalid_hex(remainder).map_err(|err| err.offset_by(input.len() - remainder.len()))?;
+    }
+
+    Ok(())
+}
+
+/// Decode `input` into `output`, without checking that `input` is valid hex.
+///
+/// `output.len() * 2` must equal `input.len()`.
+///
+/// All of `output` is initialized after this function returns, but its contents are unspecified if `input` wasn't valid hex.
+pub(super) fn decode_uninit_trusted<O: OutputByte>(input: &[u8], output: &mut [O]) {
+    debug_assert_eq!(input.len(), output.len() * 2);
+
+    // The compiler is more than happy to get rid of the validity check when nothing looks at it.
+    decode_block(input, output);
+}
+
+/// Decode `buffer` into its own first half, without checking that it's valid hex.
+///
+/// Returns the length of the decoded prefix, its contents are unspecified if `buffer` wasn't valid hex.
+pub(super) fn decode_in_place_trusted(buffer: &mut [u8]) -> usize {
+    let len = buffer.len() / 2;
+
+    // Every byte written is at an index that has already been read from (`index <= index * 2`),
+    // so going front to back never overwrites a character that's still needed.
+    for index in 0..len {
+        let high = decode_char(buffer[index * 2]);
+        let low = decode_char(buffer[index * 2 + 1]);
+
+        buffer[index] = high << 4 | low;
+    }
+
+    len
 }
 
 #[cfg(test)]
 mod tests {
     use crate::decode::fallback::decode_to_slice;
 
-    use super::{decode, validate_hex};
+    use super::{
+        decode, decode_array_const, decode_char, decode_escaped_with, decode_in_place_trusted,
+        decode_lossy_bytes, decode_reverse_into, decode_swapped_with, find_invalid_hex,
+        validate_hex, BLOCK_LEN, INVALID,
+    };
 
     fn hex_chars() -> impl Iterator<Item = u8> {
         (b'0'..=b'9').chain(b'A'..=b'F').chain(b'a'..=b'f')
@@ -93,7 +429,7 @@ mod tests {
 
     #[test]
     fn invalid_hex_properly_invalid() {
-        // the irony of this test is that we currently *use* `is_ascii_hexdigit`, but that might not be true forever.
+        // the irony of this test is that we *use* `is_ascii_hexdigit` (without the `lut` feature), but that might not be true forever.
         let iter = (u8::MIN..=u8::MAX).filter(|it| !it.is_ascii_hexdigit());
 
         for a in iter.clone() {
@@ -138,6 +474,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decode_is_exact_size() {
+        let input = [b'a'; 80];
+
+        for len in (0..=input.len()).step_by(2) {
+            let output = decode(&input[..len]).unwrap();
+            assert_eq!(output.capacity(), output.len(), "len: {len}");
+        }
+    }
+
+    #[test]
+    fn decode_array_const_matches_decode_to_array() {
+        for high in u8::MIN..=u8::MAX {
+            for low in u8::MIN..=u8::MAX {
+                let input = [high, low];
+                assert_eq!(
+                    decode_array_const::<1>(&input),
+                    crate::decode_to_array(input),
+                    "high: {high:#02x}, low: {low:#02x}"
+                );
+            }
+        }
+
+        assert_eq!(
+            decode_array_const::<2>(b"abcdef"),
+            crate::decode_to_array::<_, 2>(b"abcdef")
+        );
+    }
+
+    #[test]
+    fn decode_lossy_skips_everything_else() {
+        let hex = b"0123456789abcdefABCDEF";
+        let expected = decode(hex).unwrap();
+
+        for junk in (u8::MIN..=u8::MAX).filter(|it| !it.is_ascii_hexdigit()) {
+            let input: std::vec::Vec<u8> = hex.iter().flat_map(|&char| [junk, char]).collect();
+
+            assert!(decode_lossy_bytes(&input).eq(expected.iter().copied()));
+        }
+
+        // The leftover digit is dropped.
+        assert!(decode_lossy_bytes(b"abc").eq([0xab]));
+    }
+
     #[test]
     fn decode_slice_matches_decode() {
         for value in u16::MIN..=u16::MAX {
@@ -160,4 +540,134 @@ mod tests {
             assert_eq!(a, b);
         }
     }
+
+    #[test]
+    fn validate_blocks_match_precise() {
+        let input = b"0123456789abcdefABCDEF".repeat(BLOCK_LEN / 8);
+
+        for offset in 0..input.len() {
+            for value in [b'g', b'G', b' ', b'/', b':', 0xff] {
+                let mut input = input.clone();
+                input[offset] = value;
+
+                assert_eq!(
+                    validate_hex(&input),
+                    find_invalid_hex(&input),
+                    "offset: {offset}"
+                );
+                assert_eq!(
+                    validate_hex(&input),
+                    Err(super::Error::InvalidHex { offset, value })
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn decode_char_matches_std() {
+        for value in u8::MIN..=u8::MAX {
+            let expected = (value as char).to_digit(16).map_or(INVALID, |it| it as u8);
+
+            assert_eq!(decode_char(value), expected, "char: {value:#02x}");
+        }
+    }
+
+    #[test]
+    fn decode_invalid_at_every_offset() {
+        let input = b"0123456789abcdefABCDEF".repeat(BLOCK_LEN / 8);
+
+        for offset in 0..input.len() {
+            let mut input = input.clone();
+            input[offset] = b'g';
+
+            assert_eq!(
+                decode(&input),
+                Err(super::Error::InvalidHex {
+                    offset,
+                    value: b'g'
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn decode_in_place_matches_decode() {
+        for value in u16::MIN..=u16::MAX {
+            let mut buffer = alloc::format!("{value:04x}").into_bytes();
+
+            let len = decode_in_place_trusted(&mut buffer);
+
+            assert_eq!(&buffer[..len], value.to_be_bytes());
+        }
+    }
+
+    #[test]
+    fn decode_escaped_round_trips() {
+        let input: std::vec::Vec<u8> = (0..=u8::MAX).collect();
+
+        for (encoded, ascii) in [
+            (crate::encode_escaped(&input), false),
+            (crate::encode_escaped_ascii(&input), true),
+        ] {
+            let mut output = std::vec::Vec::new();
+            let result = if ascii {
+                decode_escaped_with::<true>(encoded.as_bytes(), |byte| output.push(byte))
+            } else {
+                decode_escaped_with::<false>(encoded.as_bytes(), |byte| output.push(byte))
+            };
+
+            assert_eq!(result, Ok(()));
+            assert_eq!(output, input);
+        }
+
+        // Rust's own escapes decode too.
+        let escaped = std::string::ToString::to_string(&input.escape_ascii());
+        let mut output = std::vec::Vec::new();
+        decode_escaped_with::<true>(escaped.as_bytes(), |byte| output.push(byte)).unwrap();
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    fn decode_reverse_matches_decode() {
+        let input: std::vec::Vec<u8> = (0..=u8::MAX).collect();
+        let encoded = crate::encode_reverse(&input);
+
+        let mut output = std::vec![0; input.len()];
+        assert_eq!(decode_reverse_into(encoded.as_bytes(), &mut output), Ok(()));
+        assert_eq!(output, input);
+
+        for offset in 0..6 {
+            let mut input = *b"f00f00";
+            input[offset] = b'g';
+
+            let expected = decode(&input).map(drop);
+            assert_eq!(decode_reverse_into(&input, &mut [0; 3]), expected);
+        }
+    }
+
+    #[test]
+    fn decode_swapped_matches_decode() {
+        let input: std::vec::Vec<u8> = (0..=u8::MAX).collect();
+        let encoded = crate::encode_swapped(&input);
+
+        let mut output = std::vec::Vec::new();
+        assert_eq!(
+            decode_swapped_with::<false>(encoded.as_bytes(), |byte| output.push(byte)),
+            Ok(())
+        );
+        assert_eq!(output, input);
+
+        // Every error has the offset of the first invalid character, like `decode`.
+        for offset in 0..6 {
+            let mut input = *b"f00f00";
+            input[offset] = b'g';
+
+            let expected = decode(&input).map(drop);
+            assert_eq!(decode_swapped_with::<false>(&input, drop), expected);
+
+            // The last character isn't part of the filled input.
+            let expected = if offset < 5 { expected } else { Ok(()) };
+            assert_eq!(decode_swapped_with::<true>(&input[..5], drop), expected);
+        }
+    }
 }
@@ -0,0 +1,477 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use super::Error;
+use crate::Case;
+
+/// What [`DecodeOptions`] does with an odd number of hex characters.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum OddLength {
+    /// Return [`Error::OddLength`], like the rest of the crate does.
+    #[default]
+    Reject,
+
+    /// Decode as if there was an extra `0` at the start, so `fff` decodes to `[0x0f, 0xff]`.
+    ///
+    /// This is how hex numbers (`0xabc`) are usually written.
+    PadStart,
+}
+
+/// Decoding for the many "almost hex" formats, without having to clean up the input first.
+///
+/// By default, this is as strict as [`decode`](crate::decode), each option relaxes (or tightens) it a bit.
+/// Every character is checked before the length, so an invalid character is reported even when the length is odd too.
+///
+/// Errors always report offsets into the original input, including any prefix, whitespace, or separators.
+///
+/// # Examples
+/// ```
+/// use hector::DecodeOptions;
+///
+/// let options = DecodeOptions::new().allow_prefix(true).ignore_separator(b':');
+///
+/// assert_eq!(options.decode("0xde:ca:ff"), Ok(vec![0xde, 0xca, 0xff]));
+/// assert_eq!(options.decode("decaff"), Ok(vec![0xde, 0xca, 0xff]));
+/// ```
+///
+/// ```
+/// use hector::{DecodeError, DecodeOptions};
+///
+/// let options = DecodeOptions::new().ignore_whitespace(true);
+///
+/// assert_eq!(options.decode("de ca\nff"), Ok(vec![0xde, 0xca, 0xff]));
+/// assert_eq!(options.decode("de ca fg"), Err(DecodeError::InvalidHex { offset: 7, value: b'g' }));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DecodeOptions {
+    prefix: bool,
+    whitespace: bool,
+    separator: Option<u8>,
+    underscores: bool,
+    case: Case,
+    odd_length: OddLength,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DecodeOptions {
+    /// Options that accept exactly what [`decode`](crate::decode) does.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            prefix: false,
+            whitespace: false,
+            separator: None,
+            underscores: false,
+            case: Case::Mixed,
+            odd_length: OddLength::Reject,
+        }
+    }
+
+    /// Whether to skip a leading `0x` (or `0X`), if there is one.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::DecodeOptions::new().allow_prefix(true);
+    ///
+    /// assert_eq!(options.decode("0xc0ffee"), Ok(vec![0xc0, 0xff, 0xee]));
+    /// assert_eq!(options.decode("0Xc0ffee"), Ok(vec![0xc0, 0xff, 0xee]));
+    /// assert_eq!(options.decode("c0ffee"), Ok(vec![0xc0, 0xff, 0xee]));
+    /// ```
+    #[must_use]
+    pub const fn allow_prefix(mut self, allow: bool) -> Self {
+        self.prefix = allow;
+        self
+    }
+
+    /// Whether to skip ascii whitespace between (or in the middle of) bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::DecodeOptions::new().ignore_whitespace(true);
+    ///
+    /// assert_eq!(options.decode(" c0 ff\tee\r\n"), Ok(vec![0xc0, 0xff, 0xee]));
+    /// ```
+    #[must_use]
+    pub const fn ignore_whitespace(mut self, ignore: bool) -> Self {
+        self.whitespace = ignore;
+        self
+    }
+
+    /// Skip `separator` wherever it appears, such as the `:` in `c0:ff:ee`.
+    ///
+    /// Only one separator can be ignored (setting another replaces it),
+    /// and if it's a hex character, it's treated as a separator, not as hex.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::DecodeOptions::new().ignore_separator(b'-');
+    ///
+    /// assert_eq!(options.decode("c0-ff-ee"), Ok(vec![0xc0, 0xff, 0xee]));
+    /// ```
+    #[must_use]
+    pub const fn ignore_separator(mut self, separator: u8) -> Self {
+        self.separator = Some(separator);
+        self
+    }
+
+    /// Whether to skip `_` between bytes, like in Rust (and Python) numeric literals (`dead_beef`).
+    ///
+    /// Unlike a [`separator`](Self::ignore_separator), an `_` is never allowed in the middle of a byte
+    /// (with [`OddLength::PadStart`], bytes are counted from the padding).
+    ///
+    /// # Examples
+    /// ```
+    /// use hector::{DecodeError, DecodeOptions};
+    ///
+    /// let options = DecodeOptions::new().allow_prefix(true).allow_underscores(true);
+    ///
+    /// assert_eq!(options.decode("0xdead_beef"), Ok(vec![0xde, 0xad, 0xbe, 0xef]));
+    /// assert_eq!(options.decode("0x_c0_ffee_"), Ok(vec![0xc0, 0xff, 0xee]));
+    /// assert_eq!(options.decode("dea_dbeef"), Err(DecodeError::InvalidHex { offset: 3, value: b'_' }));
+    /// ```
+    #[must_use]
+    pub const fn allow_underscores(mut self, allow: bool) -> Self {
+        self.underscores = allow;
+        self
+    }
+
+    /// Only accept letters in `case`.
+    ///
+    /// [`Case::Mixed`] (the default) accepts either case, and [`Case::None`] doesn't accept letters at all.
+    ///
+    /// # Examples
+    /// ```
+    /// use hector::{Case, DecodeError, DecodeOptions};
+    ///
+    /// let options = DecodeOptions::new().case(Case::Lower);
+    ///
+    /// assert_eq!(options.decode("c0ffee"), Ok(vec![0xc0, 0xff, 0xee]));
+    /// assert_eq!(options.decode("c0FFee"), Err(DecodeError::InvalidHex { offset: 2, value: b'F' }));
+    /// ```
+    #[must_use]
+    pub const fn case(mut self, case: Case) -> Self {
+        self.case = case;
+        self
+    }
+
+    /// What to do with an odd number of hex characters.
+    ///
+    /// # Examples
+    /// ```
+    /// use hector::{DecodeError, DecodeOptions, OddLength};
+    ///
+    /// let options = DecodeOptions::new().odd_length(OddLength::PadStart);
+    ///
+    /// assert_eq!(options.decode("abc"), Ok(vec![0x0a, 0xbc]));
+    /// assert_eq!(DecodeOptions::new().decode("abc"), Err(DecodeError::OddLength));
+    /// ```
+    #[must_use]
+    pub const fn odd_length(mut self, policy: OddLength) -> Self {
+        self.odd_length = policy;
+        self
+    }
+
+    /// Decode `input` with these options.
+    ///
+    /// # Errors
+    /// - [`Error::OddLength`] if there's an odd number of hex characters, and that's rejected.
+    /// - [`Error::InvalidHex`] if any character isn't a hex character (in the right case), or one that's skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::DecodeOptions::new().ignore_whitespace(true);
+    ///
+    /// assert_eq!(options.decode("de ca ff"), Ok(vec![0xde, 0xca, 0xff]));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decode<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, Error> {
+        let input = input.as_ref();
+
+        let (digits, len) = self.check(input)?;
+        let mut output = alloc::vec![0; len];
+
+        self.write(input, digits, &mut output);
+
+        Ok(output)
+    }
+
+    /// Decode `input` into `output` with these options.
+    ///
+    /// # Errors
+    /// - [`Error::OddLength`] if there's an odd number of hex characters, and that's rejected.
+    /// - [`Error::InvalidHex`] if any character isn't a hex character (in the right case), or one that's skipped.
+    /// - [`Error::MismatchedLength`] if `output` isn't exactly the decoded length,
+    ///   `source_len` is the number of hex characters (counting padding).
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::DecodeOptions::new().ignore_separator(b':');
+    /// let mut storage = [0; 3];
+    ///
+    /// assert_eq!(options.decode_to_slice("de:ca:ff", &mut storage), Ok([0xde, 0xca, 0xff].as_slice()));
+    /// ```
+    ///
+    /// ```
+    /// use hector::{DecodeError, DecodeOptions};
+    ///
+    /// let options = DecodeOptions::new().ignore_separator(b':');
+    /// let mut storage = [0; 2];
+    ///
+    /// let output = options.decode_to_slice("de:ca:ff", &mut storage);
+    /// assert_eq!(output, Err(DecodeError::MismatchedLength { source_len: 6, dest_len: 2 }));
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn decode_to_slice<'a, T: AsRef<[u8]>>(
+        &self,
+        input: T,
+        output: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let input = input.as_ref();
+
+        let (digits, len) = self.check(input)?;
+
+        if output.len() != len {
+            return Err(Error::MismatchedLength {
+                source_len: len * 2,
+                dest_len: output.len(),
+            });
+        }
+
+        self.write(input, digits, output);
+
+        Ok(output)
+    }
+
+    /// The offset that the hex characters start at, after the prefix (if it's allowed).
+    fn start(&self, input: &[u8]) -> usize {
+        match input {
+            [b'0', b'x' | b'X', ..] if self.prefix => 2,
+            _ => 0,
+        }
+    }
+
+    fn is_skipped(&self, char: u8) -> bool {
+        (self.whitespace && char.is_ascii_whitespace())
+            || self.separator == Some(char)
+            || (self.underscores && char == b'_')
+    }
+
+    /// Decode a single hex character, or `None` if it isn't one (in the right case).
+    fn decode_char(&self, char: u8) -> Option<u8> {
+        let allow_lower = matches!(self.case, Case::Lower | Case::Mixed);
+        let allow_upper = matches!(self.case, Case::Upper | Case::Mixed);
+
+        match char {
+            b'0'..=b'9' => Some(char - b'0'),
+            b'a'..=b'f' if allow_lower => Some(char - b'a' + 10),
+            b'A'..=b'F' if allow_upper => Some(char - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    /// Every 4-bit value in `input`, or the first character that's invalid.
+    fn nibbles<'a>(&'a self, input: &'a [u8]) -> impl Iterator<Item = Result<u8, Error>> + 'a {
+        input
+            .iter()
+            .copied()
+            .enumerate()
+            .skip(self.start(input))
+            .filter(|&(_, char)| !self.is_skipped(char))
+            .map(|(offset, value)| {
+                self.decode_char(value)
+                    .ok_or(Error::InvalidHex { offset, value })
+            })
+    }
+
+    /// Validate `input`, returning the number of hex characters in it.
+    fn count_digits(&self, input: &[u8]) -> Result<usize, Error> {
+        self.nibbles(input)
+            .try_fold(0, |digits, nibble| nibble.map(|_| digits + 1))
+    }
+
+    /// Validate `input`, returning the number of hex characters in it, and the decoded length.
+    fn check(&self, input: &[u8]) -> Result<(usize, usize), Error> {
+        let digits = self.count_digits(input)?;
+
+        // The padding (if any) is only known once all the digits are counted.
+        let padding =
+            usize::from(!digits.is_multiple_of(2) && self.odd_length == OddLength::PadStart);
+        self.check_underscores(input, padding)?;
+
+        let len = self.decoded_len(digits)?;
+
+        Ok((digits, len))
+    }
+
+    /// Check that every `_` in `input` is between bytes, given the number of padding characters.
+    fn check_underscores(&self, input: &[u8], padding: usize) -> Result<(), Error> {
+        if !self.underscores {
+            return Ok(());
+        }
+
+        let mut digits = padding;
+
+        for (offset, value) in input.iter().copied().enumerate().skip(self.start(input)) {
+            match value {
+                b'_' if !digits.is_multiple_of(2) => {
+                    return Err(Error::InvalidHex { offset, value });
+                }
+                _ if self.is_skipped(value) => {}
+                _ => digits += 1,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn decoded_len(&self, digits: usize) -> Result<usize, Error> {
+        match self.odd_length {
+            _ if digits.is_multiple_of(2) => Ok(digits / 2),
+            OddLength::Reject => Err(Error::OddLength),
+            OddLength::PadStart => Ok(digits / 2 + 1),
+        }
+    }
+
+    /// Decode `input` (which has `digits` hex characters) into `output`, which must be the right length.
+    ///
+    /// `input` must already have been validated (invalid characters are skipped, rather than panicking).
+    fn write(&self, input: &[u8], digits: usize, output: &mut [u8]) {
+        let mut nibbles = self.nibbles(input).flatten();
+        let mut output = output.iter_mut();
+
+        // The padding `0` is the high half of the first byte, so that byte only gets one character.
+        if !digits.is_multiple_of(2) {
+            if let (Some(byte), Some(low)) = (output.next(), nibbles.next()) {
+                *byte = low;
+            }
+        }
+
+        for byte in output {
+            if let (Some(high), Some(low)) = (nibbles.next(), nibbles.next()) {
+                *byte = (high << 4) | low;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DecodeOptions, OddLength};
+    use crate::{Case, DecodeError};
+
+    #[test]
+    fn default_matches_decode() {
+        for input in ["", "decaff", "C0Ffee", "abc", "c0ffeg", "0xab", " a"] {
+            assert_eq!(
+                DecodeOptions::new().decode(input),
+                crate::decode(input),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn offsets_are_into_the_original_input() {
+        let options = DecodeOptions::new()
+            .allow_prefix(true)
+            .ignore_whitespace(true)
+            .ignore_separator(b':');
+
+        assert_eq!(
+            options.decode("0x de:ca:fg"),
+            Err(DecodeError::InvalidHex {
+                offset: 10,
+                value: b'g'
+            })
+        );
+
+        // The prefix is only skipped at the start.
+        assert_eq!(
+            options.decode("de0xad"),
+            Err(DecodeError::InvalidHex {
+                offset: 3,
+                value: b'x'
+            })
+        );
+    }
+
+    #[test]
+    fn case() {
+        let upper = DecodeOptions::new().case(Case::Upper);
+        assert_eq!(upper.decode("C0FFEE"), Ok(std::vec![0xc0, 0xff, 0xee]));
+        assert_eq!(
+            upper.decode("C0FFEe"),
+            Err(DecodeError::InvalidHex {
+                offset: 5,
+                value: b'e'
+            })
+        );
+
+        let digits = DecodeOptions::new().case(Case::None);
+        assert_eq!(digits.decode("1234"), Ok(std::vec![0x12, 0x34]));
+        assert_eq!(
+            digits.decode("12a4"),
+            Err(DecodeError::InvalidHex {
+                offset: 2,
+                value: b'a'
+            })
+        );
+    }
+
+    #[test]
+    fn underscores() {
+        let options = DecodeOptions::new().allow_underscores(true);
+
+        assert_eq!(options.decode("__"), Ok(std::vec![]));
+        assert_eq!(options.decode("de__ad"), Ok(std::vec![0xde, 0xad]));
+        assert_eq!(
+            options.decode("d_ead"),
+            Err(DecodeError::InvalidHex {
+                offset: 1,
+                value: b'_'
+            })
+        );
+
+        // The underscores are checked before the length.
+        assert_eq!(
+            options.decode("d_e_a"),
+            Err(DecodeError::InvalidHex {
+                offset: 1,
+                value: b'_'
+            })
+        );
+        assert_eq!(options.decode("de_a"), Err(DecodeError::OddLength));
+
+        let padded = options.odd_length(OddLength::PadStart);
+        assert_eq!(padded.decode("f_ffff"), Ok(std::vec![0x0f, 0xff, 0xff]));
+        assert_eq!(
+            padded.decode("ff_fff"),
+            Err(DecodeError::InvalidHex {
+                offset: 2,
+                value: b'_'
+            })
+        );
+    }
+
+    #[test]
+    fn pad_start() {
+        let options = DecodeOptions::new()
+            .odd_length(OddLength::PadStart)
+            .ignore_separator(b'_');
+
+        assert_eq!(options.decode("f"), Ok(std::vec![0x0f]));
+        assert_eq!(options.decode("f_ff_ff"), Ok(std::vec![0x0f, 0xff, 0xff]));
+        assert_eq!(options.decode(""), Ok(std::vec![]));
+
+        let mut storage = [0; 2];
+        assert_eq!(
+            options.decode_to_slice("abc", &mut storage),
+            Ok([0x0a, 0xbc].as_slice())
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! SSE2 implementation of decoding.
+//!
+//! SSE2 is part of the `x86_64` baseline, so this is statically selected rather than runtime detected.
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::{
+    __m128i, _mm_add_epi8, _mm_and_si128, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_min_epu8,
+    _mm_movemask_epi8, _mm_or_si128, _mm_packus_epi16, _mm_set1_epi16, _mm_set1_epi8,
+    _mm_setzero_si128, _mm_slli_epi16, _mm_srli_epi16, _mm_storel_epi64, _mm_sub_epi8,
+};
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::{
+    __m128i, _mm_add_epi8, _mm_and_si128, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_min_epu8,
+    _mm_movemask_epi8, _mm_or_si128, _mm_packus_epi16, _mm_set1_epi16, _mm_set1_epi8,
+    _mm_setzero_si128, _mm_slli_epi16, _mm_srli_epi16, _mm_storel_epi64, _mm_sub_epi8,
+};
+use core::mem::MaybeUninit;
+
+use super::{fallback, Error};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The number of hex characters processed per iteration.
+const CHUNK_LEN: usize = 16;
+
+/// Returns a mask of which bytes in `x` are `<= max` (treating both as unsigned).
+#[inline]
+#[target_feature(enable = "sse2")]
+fn le_epu8(x: __m128i, max: u8) -> __m128i {
+    // there's no unsigned compare in SSE2, but `min(x, max) == x` is equivalent to `x <= max`.
+    _mm_cmpeq_epi8(_mm_min_epu8(x, _mm_set1_epi8(max as i8)), x)
+}
+
+/// The intermediate values used for both validating and decoding hex characters.
+struct Classified {
+    /// `0-9` mapped to `0..=9`.
+    digits: __m128i,
+    /// Mask of which bytes are in `0-9`.
+    is_digit: __m128i,
+    /// `a-f` and `A-F` mapped to `0..=5`.
+    letters: __m128i,
+    /// Mask of which bytes are in `a-f` or `A-F`.
+    is_letter: __m128i,
+}
+
+impl Classified {
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    fn new(chars: __m128i) -> Self {
+        // `0-9` map to `0..=9`, everything else wraps around to something bigger.
+        let digits = _mm_sub_epi8(chars, _mm_set1_epi8(b'0' as i8));
+
+        // Same trick as the scalar decoder: setting the "lowercase" bit lets us handle both cases at once.
+        let letters = _mm_sub_epi8(
+            _mm_or_si128(chars, _mm_set1_epi8(0x20)),
+            _mm_set1_epi8(b'a' as i8),
+        );
+
+        Self {
+            digits,
+            is_digit: le_epu8(digits, 9),
+            letters,
+            is_letter: le_epu8(letters, 5),
+        }
+    }
+
+    /// Mask of which bytes are valid hex characters.
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    fn valid(&self) -> __m128i {
+        _mm_or_si128(self.is_digit, self.is_letter)
+    }
+}
+
+/// Load 16 bytes of `input` into a vector.
+#[inline]
+#[target_feature(enable = "sse2")]
+fn load(input: &[u8; CHUNK_LEN]) -> __m128i {
+    // Safety: `input` is exactly 16 bytes long and `loadu` doesn't have any alignment requirements.
+    unsafe { _mm_loadu_si128(input.as_ptr().cast()) }
+}
+
+/// Check whether all 32 bytes of `input` are valid hex characters.
+#[inline]
+#[target_feature(enable = "sse2")]
+fn validate_chunk(input: &[u8; CHUNK_LEN * 2]) -> bool {
+    let (low, high) = input.split_at(CHUNK_LEN);
+    let low = Classified::new(load(low.try_into().unwrap())).valid();
+    let high = Classified::new(load(high.try_into().unwrap())).valid();
+
+    _mm_movemask_epi8(_mm_and_si128(low, high)) == 0xffff
+}
+
+/// Decode 16 hex characters into 8 bytes.
+///
+/// The output is *always* written, but if this returns `false` (meaning at least one character wasn't valid hex),
+/// the contents of `output` are unspecified.
+#[inline]
+#[target_feature(enable = "sse2")]
+fn decode_chunk(input: &[u8; CHUNK_LEN], output: &mut [MaybeUninit<u8>; CHUNK_LEN / 2]) -> bool {
+    let Classified {
+        digits,
+        is_digit,
+        letters,
+        is_letter,
+    } = Classified::new(load(input));
+
+    let valid = _mm_movemask_epi8(_mm_or_si128(is_digit, is_letter)) == 0xffff;
+
+    let nibbles = _mm_or_si128(
+        _mm_and_si128(is_digit, digits),
+        _mm_and_si128(is_letter, _mm_add_epi8(letters, _mm_set1_epi8(10))),
+    );
+
+    // Each 16-bit lane is now `low << 8 | high` (x86 is little endian), so swap them into place, and...
+    let high = _mm_slli_epi16(_mm_and_si128(nibbles, _mm_set1_epi16(0xff)), 4);
+    let low = _mm_srli_epi16(nibbles, 8);
+
+    // ... pack the lanes down to bytes, every lane is `<= 0xff` so the saturation doesn't matter.
+    let bytes = _mm_packus_epi16(_mm_or_si128(high, low), _mm_setzero_si128());
+
+    // Safety: `output` is exactly 8 bytes long and `storel` doesn't have any alignment requirements.
+    unsafe { _mm_storel_epi64(output.as_mut_ptr().cast(), bytes) };
+
+    valid
+}
+
+/// Decode `input` into `output`.
+///
+/// `output.len() * 2` must equal `input.len()`.
+///
+/// It is safe to assume that all of `output` is initialized after this function returns `Ok`.
+pub(super) fn decode_uninit(input: &[u8], output: &mut [MaybeUninit<u8>]) -> Result<(), Error> {
+    debug_assert_eq!(input.len(), output.len() * 2);
+
+    let mut input_chunks = input.chunks_exact(CHUNK_LEN);
+    let mut output_chunks = output.chunks_exact_mut(CHUNK_LEN / 2);
+
+    for (index, (input, output)) in (&mut input_chunks).zip(&mut output_chunks).enumerate() {
+        let input = input.try_into().unwrap();
+
+        // Safety: this module is only compiled when `sse2` is statically enabled.
+        let valid = unsafe { decode_chunk(input, output.try_into().unwrap()) };
+
+        if !valid {
+            // Errors are rare, so it's fine to go back over the chunk to find out exactly where the error is.
+            fallback::validate_hex(input).map_err(|err| err.offset_by(index * CHUNK_LEN))?;
+        }
+    }
+
+    let remainder = input_chunks.remainder();
+    fallback::decode_uninit(remainder, output_chunks.into_remainder())
+        .map_err(|err| err.offset_by(input.len() - remainder.len()))
+}
+
+/// Decode `input` into `output`, without checking that `input` is valid hex.
+///
+/// `output.len() * 2` must equal `input.len()`.
+///
+/// All of `output` is initialized after this function returns, but its contents are unspecified if `input` wasn't valid hex.
+pub(super) fn decode_uninit_trusted(input: &[u8], output: &mut [MaybeUninit<u8>]) {
+    debug_assert_eq!(input.len(), output.len() * 2);
+
+    let mut input_chunks = input.chunks_exact(CHUNK_LEN);
+    let mut output_chunks = output.chunks_exact_mut(CHUNK_LEN / 2);
+
+    for (input, output) in (&mut input_chunks).zip(&mut output_chunks) {
+        // Safety: this module is only compiled when `sse2` is statically enabled.
+        unsafe { decode_chunk(input.try_into().unwrap(), output.try_into().unwrap()) };
+    }
+
+    fallback::decode_uninit_trusted(input_chunks.remainder(), output_chunks.into_remainder());
+}
+
+pub(super) fn validate(input: &[u8]) -> Result<(), Error> {
+    if !input.len().is_multiple_of(2) {
+        return Err(Error::OddLength);
+    }
+
+    validate_hex(input)
+}
+
+/// Check that every character of `input` is valid hex, unlike [`validate`], `input` can be any length.
+pub(super) fn validate_hex(input: &[u8]) -> Result<(), Error> {
+    let mut chunks = input.chunks_exact(CHUNK_LEN * 2);
+
+    for (index, chunk) in (&mut chunks).enumerate() {
+        // Safety: this module is only compiled when `sse2` is statically enabled.
+        let valid = unsafe { validate_chunk(chunk.try_into().unwrap()) };
+
+        if !valid {
+            // Errors are rare, so it's fine to go back over the chunk to find out exactly where the error is.
+            fallback::validate_hex(chunk).map_err(|err| err.offset_by(index * CHUNK_LEN * 2))?;
+        }
+    }
+
+    let remainder = chunks.remainder();
+    fallback::validate_hex(remainder).map_err(|err| err.offset_by(input.len() - remainder.len()))
+}
+
+#[cfg(feature = "alloc")]
+pub(super) fn decode(input: &[u8]) -> Result<Vec<u8>, Error> {
+    if !input.len().is_multiple_of(2) {
+        return Err(Error::OddLength);
+    }
+
+    let out_len = input.len() / 2;
+
+    let mut output = Vec::with_capacity(out_len);
+    crate::output::try_append(&mut output, out_len, |spare| decode_uninit(input, spare))?;
+
+    Ok(output)
+}
+
+pub(super) fn decode_to_slice<'a>(input: &[u8], output: &'a mut [u8]) -> Result<&'a [u8], Error> {
+    if input.len() != output.len() * 2 {
+        return Err(Error::MismatchedLength {
+            source_len: input.len(),
+            dest_len: output.len(),
+        });
+    }
+
+    // Safety: `MaybeUninit<u8>` has the same layout as `u8`, and `decode_uninit` never writes uninitialized bytes.
+    let uninit = unsafe { &mut *(output as *mut [u8] as *mut [MaybeUninit<u8>]) };
+
+    decode_uninit(input, uninit)?;
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem::MaybeUninit;
+
+    use super::{
+        decode, decode_to_slice, decode_uninit_trusted, fallback, validate, validate_hex, CHUNK_LEN,
+    };
+
+    /// A hex string long enough to cover a couple of full chunks plus a remainder.
+    const INPUT: &[u8; 4 * CHUNK_LEN + 6] =
+        b"0123456789abcdefABCDEF0123456789c0ffee0123456789abcdefABCDEF0123456789";
+
+    #[test]
+    fn matches_fallback() {
+        for len in (0..=INPUT.len()).step_by(2) {
+            let input = &INPUT[..len];
+            assert_eq!(decode(input), fallback::decode(input), "len: {len}");
+            assert_eq!(validate(input), fallback::validate(input), "len: {len}");
+        }
+
+        for len in 0..=INPUT.len() {
+            let mut input = *INPUT;
+            input[len.saturating_sub(1)] = b'x';
+            let input = &input[..len];
+
+            assert_eq!(
+                validate_hex(input),
+                fallback::validate_hex(input),
+                "len: {len}"
+            );
+        }
+    }
+
+    #[test]
+    fn decode_is_exact_size() {
+        for len in (0..=INPUT.len()).step_by(2) {
+            let output = decode(&INPUT[..len]).unwrap();
+            assert_eq!(output.capacity(), output.len(), "len: {len}");
+        }
+    }
+
+    #[test]
+    fn all_chunk_bytes_decode() {
+        for value in u8::MIN..=u8::MAX {
+            let input = std::format!("{value:02x}").repeat(CHUNK_LEN / 2);
+            let mut output = [0; CHUNK_LEN / 2];
+
+            assert_eq!(
+                decode_to_slice(input.as_bytes(), &mut output),
+                Ok([value; CHUNK_LEN / 2].as_slice())
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_at_every_offset() {
+        for offset in 0..INPUT.len() {
+            for value in (u8::MIN..=u8::MAX).filter(|it| !it.is_ascii_hexdigit()) {
+                let mut input = *INPUT;
+                input[offset] = value;
+
+                let expected = super::Error::InvalidHex { offset, value };
+
+                assert_eq!(
+                    decode(&input),
+                    Err(expected),
+                    "offset: {offset}, value: {value:#02x}"
+                );
+                assert_eq!(
+                    validate(&input),
+                    Err(expected),
+                    "offset: {offset}, value: {value:#02x}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn trusted_matches_checked() {
+        for len in (0..=INPUT.len()).step_by(2) {
+            let input = &INPUT[..len];
+            let mut output = [MaybeUninit::new(0); INPUT.len() / 2];
+            let output = &mut output[..len / 2];
+
+            decode_uninit_trusted(input, output);
+
+            // Safety: `output` was initialized to begin with.
+            let output: std::vec::Vec<u8> = output
+                .iter()
+                .map(|it| unsafe { it.assume_init() })
+                .collect();
+            assert_eq!(Ok(output), decode(input), "len: {len}");
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! Formatting bytes as hex without allocating.
+
+use core::fmt::{self, Write};
+
+use crate::encode::byte_to_hex;
+
+/// Wrap `input` so that it formats as hex, see [`HexSlice`].
+///
+/// # Examples
+/// ```
+/// let digest = [0xde, 0xca, 0xff];
+///
+/// assert_eq!(format!("digest: {}", hector::display(&digest)), "digest: decaff");
+/// ```
+#[must_use]
+pub fn display<T: AsRef<[u8]> + ?Sized>(input: &T) -> HexSlice<'_> {
+    HexSlice::new(input.as_ref())
+}
+
+/// A byte slice that formats as hex.
+///
+/// [`Display`](fmt::Display) and [`LowerHex`](fmt::LowerHex) format as lowercase, [`UpperHex`](fmt::UpperHex) as uppercase,
+/// and [`Debug`](fmt::Debug) is the same as `Display` (so it's readable in derived `Debug` impls).
+///
+/// Formatter flags work like they do for strings and integers:
+/// - `#` adds a `0x` prefix.
+/// - width, fill, and alignment pad the output (left aligned by default), `0` pads with zeros after the prefix.
+/// - precision limits the number of hex characters.
+///
+/// # Examples
+/// ```
+/// let bytes = hector::display(&[0xde, 0xca, 0xff]);
+///
+/// assert_eq!(format!("{bytes:x}"), "decaff");
+/// assert_eq!(format!("{bytes:X}"), "DECAFF");
+/// assert_eq!(format!("{bytes:#x}"), "0xdecaff");
+/// assert_eq!(format!("{bytes:>10}"), "    decaff");
+/// assert_eq!(format!("{bytes:*^10}"), "**decaff**");
+/// assert_eq!(format!("{bytes:#012x}"), "0x0000decaff");
+/// assert_eq!(format!("{bytes:.3}"), "dec");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HexSlice<'a>(&'a [u8]);
+
+impl<'a> HexSlice<'a> {
+    /// Wrap `input` so that it formats as hex.
+    #[must_use]
+    pub fn new(input: &'a [u8]) -> Self {
+        Self(input)
+    }
+
+    /// Get the wrapped bytes.
+    #[must_use]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    fn fmt_hex(&self, f: &mut fmt::Formatter<'_>, upper: bool, prefixed: bool) -> fmt::Result {
+        let prefix = if prefixed { "0x" } else { "" };
+
+        let digits = self.0.len() * 2;
+        let digits = f
+            .precision()
+            .map_or(digits, |precision| precision.min(digits));
+
+        let padding = f
+            .width()
+            .map_or(0, |width| width.saturating_sub(prefix.len() + digits));
+
+        if f.sign_aware_zero_pad() {
+            f.write_str(prefix)?;
+            write_fill(f, '0', padding)?;
+            return write_digits(f, &self.0[..digits.div_ceil(2)], digits, upper);
+        }
+
+        let (before, after) = match f.align() {
+            None | Some(fmt::Alignment::Left) => (0, padding),
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        };
+
+        let fill = f.fill();
+        write_fill(f, fill, before)?;
+        f.write_str(prefix)?;
+        write_digits(f, &self.0[..digits.div_ceil(2)], digits, upper)?;
+        write_fill(f, fill, after)
+    }
+}
+
+/// Write `count` copies of `fill`.
+fn write_fill(f: &mut fmt::Formatter<'_>, fill: char, count: usize) -> fmt::Result {
+    (0..count).try_for_each(|_| f.write_char(fill))
+}
+
+/// Write the first `digits` hex characters of `input`, `digits` can be odd (cutting the last byte in half).
+fn write_digits(
+    f: &mut fmt::Formatter<'_>,
+    input: &[u8],
+    digits: usize,
+    upper: bool,
+) -> fmt::Result {
+    let (whole, half) = input.split_at(digits / 2);
+
+    match upper {
+        false => crate::encode_to_fmt(whole, f)?,
+        true => crate::encode_to_fmt_upper(whole, f)?,
+    }
+
+    match (half.first(), upper) {
+        (Some(&byte), false) => f.write_char(byte_to_hex::<false>(byte)[0] as char),
+        (Some(&byte), true) => f.write_char(byte_to_hex::<true>(byte)[0] as char),
+        (None, _) => Ok(()),
+    }
+}
+
+impl fmt::Display for HexSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_hex(f, false, f.alternate())
+    }
+}
+
+impl fmt::LowerHex for HexSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_hex(f, false, f.alternate())
+    }
+}
+
+impl fmt::UpperHex for HexSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_hex(f, true, f.alternate())
+    }
+}
+
+impl fmt::Debug for HexSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `#` means pretty printing for `Debug`, not a prefix.
+        self.fmt_hex(f, false, false)
+    }
+}
+
+/// Wrap `input` so that it formats as hex, showing at most `max` bytes, see [`HexTruncate`].
+///
+/// # Examples
+/// ```
+/// let buffer = [0xde, 0xad, 0xbe, 0xef, 0, 0, 0, 0];
+///
+/// assert_eq!(format!("{}", hector::display_truncated(&buffer, 4)), "deadbeef…(+4 bytes)");
+/// assert_eq!(format!("{}", hector::display_truncated(&buffer, 8)), "deadbeef00000000");
+/// ```
+#[must_use]
+pub fn display_truncated<T: AsRef<[u8]> + ?Sized>(input: &T, max: usize) -> HexTruncate<'_> {
+    HexTruncate::new(input.as_ref(), max)
+}
+
+/// A byte slice that formats as hex, truncated to at most a given number of bytes,
+/// for putting large buffers into `Debug` impls and logs.
+///
+/// When truncated, an ellipsis is put where bytes were left out, and the number of left out bytes is added at the end,
+/// by default, only the start of the input is shown, use [`tail`](Self::tail) to also show the end.
+///
+/// [`Display`](fmt::Display), [`LowerHex`](fmt::LowerHex), and [`Debug`](fmt::Debug) format as lowercase,
+/// [`UpperHex`](fmt::UpperHex) as uppercase, formatter flags are ignored.
+///
+/// # Examples
+/// ```
+/// let buffer = [0xde, 0xad, 0, 0, 0, 0, 0xbe, 0xef];
+/// let truncated = hector::display_truncated(&buffer, 4).tail(2);
+///
+/// assert_eq!(format!("{truncated}"), "dead…beef(+4 bytes)");
+/// assert_eq!(format!("{truncated:X}"), "DEAD…BEEF(+4 bytes)");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HexTruncate<'a> {
+    input: &'a [u8],
+    head: usize,
+    tail: usize,
+}
+
+impl<'a> HexTruncate<'a> {
+    /// Wrap `input` so that it formats as hex, showing at most `max` bytes (all from the start).
+    #[must_use]
+    pub fn new(input: &'a [u8], max: usize) -> Self {
+        Self {
+            input,
+            head: max,
+            tail: 0,
+        }
+    }
+
+    /// Show the last `tail` bytes (out of the maximum) when truncating, rather than only the start.
+    ///
+    /// `tail` is capped to the maximum, in which case only the end is shown.
+    #[must_use]
+    pub fn tail(self, tail: usize) -> Self {
+        let max = self.head + self.tail;
+        let tail = tail.min(max);
+
+        Self {
+            head: max - tail,
+            tail,
+            ..self
+        }
+    }
+
+    fn fmt_hex(&self, f: &mut fmt::Formatter<'_>, upper: bool) -> fmt::Result {
+        let write = |f: &mut fmt::Formatter<'_>, input: &[u8]| match upper {
+            false => crate::encode_to_fmt(input, f),
+            true => crate::encode_to_fmt_upper(input, f),
+        };
+
+        let omitted = self.input.len().saturating_sub(self.head + self.tail);
+        if omitted == 0 {
+            return write(f, self.input);
+        }
+
+        write(f, &self.input[..self.head])?;
+        f.write_char('…')?;
+        write(f, &self.input[self.input.len() - self.tail..])?;
+        match omitted {
+            1 => f.write_str("(+1 byte)"),
+            _ => write!(f, "(+{omitted} bytes)"),
+        }
+    }
+}
+
+impl fmt::Display for HexTruncate<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_hex(f, false)
+    }
+}
+
+impl fmt::LowerHex for HexTruncate<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_hex(f, false)
+    }
+}
+
+impl fmt::UpperHex for HexTruncate<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_hex(f, true)
+    }
+}
+
+impl fmt::Debug for HexTruncate<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_hex(f, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::format;
+
+    use super::{display, display_truncated};
+
+    #[test]
+    fn flags() {
+        let bytes = display(&[0xde, 0xca, 0xff]);
+
+        assert_eq!(format!("{bytes:<8X}|"), "DECAFF  |");
+        assert_eq!(format!("{bytes:^9}|"), " decaff  |");
+        assert_eq!(format!("{bytes:#.3x}"), "0xdec");
+        assert_eq!(format!("{bytes:.100}"), "decaff");
+        assert_eq!(format!("{bytes:08.1}"), "0000000d");
+        assert_eq!(format!("{bytes:2}"), "decaff");
+        assert_eq!(format!("{bytes:#?}"), "decaff");
+        assert_eq!(format!("{:?}", display(&[])), "");
+    }
+
+    #[test]
+    fn truncate() {
+        let input: std::vec::Vec<u8> = (0..=255).collect();
+
+        assert_eq!(
+            format!("{:?}", display_truncated(&input, 2)),
+            "0001…(+254 bytes)"
+        );
+        assert_eq!(
+            format!("{}", display_truncated(&input, 2).tail(5)),
+            "…feff(+254 bytes)"
+        );
+        assert_eq!(format!("{}", display_truncated(&input, 0)), "…(+256 bytes)");
+        assert_eq!(
+            format!("{}", display_truncated(&input[..3], 2).tail(1)),
+            "00…02(+1 byte)"
+        );
+        assert_eq!(
+            format!("{}", display_truncated(&input[..3], 3).tail(1)),
+            "000102"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Hexdumps, in the same layout as `xxd`.
+//!
+//! Each line is the offset of its first byte, 16 bytes of hex in pairs, then those bytes as ascii
+//! (with `.` in place of anything that isn't printable):
+//! ```text
+//! 00000000: 4865 6c6c 6f2c 2077 6f72 6c64 0a00 01ff  Hello, world....
+//! 00000010: 2061 6263                                 abc
+//! ```
+//!
+//! Other layouts (including those of `od` and `hexdump -C`) are available with [`DumpOptions`],
+//! and dumps can be read back into bytes with [`parse`].
+//!
+//! # Examples
+//! ```
+//! let bytes = b"Hello, world\n\x00\x01\xff abc";
+//!
+//! assert_eq!(
+//!     hector::dump(bytes).to_string(),
+//!     "00000000: 4865 6c6c 6f2c 2077 6f72 6c64 0a00 01ff  Hello, world....\n\
+//!      00000010: 2061 6263                                 abc\n"
+//! );
+//! ```
+
+use core::fmt;
+use core::ops::Range;
+
+mod color;
+mod options;
+#[cfg(feature = "alloc")]
+mod parse;
+#[cfg(feature = "std")]
+mod writer;
+
+pub use options::{DumpOptions, OffsetStyle};
+#[cfg(feature = "alloc")]
+pub use parse::{parse, ParseError, ParseErrorKind};
+#[cfg(feature = "std")]
+pub use writer::DumpWriter;
+
+/// Wrap `input` so that it formats as a hexdump, see [`Dump`].
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::dump(b"hi!").to_string(), "00000000: 6869 21                                  hi!\n");
+/// ```
+#[must_use]
+pub fn dump<T: AsRef<[u8]> + ?Sized>(input: &T) -> Dump<'_> {
+    Dump::new(input.as_ref())
+}
+
+/// A byte slice that formats as a hexdump (see the [module docs](self) for the layout, and [`DumpOptions`] for others).
+///
+/// Every line ends with a newline, and empty input formats as nothing at all.
+///
+/// # Examples
+/// ```
+/// let dump = hector::dump::Dump::new(&[0xde, 0xca, 0xff]);
+///
+/// assert_eq!(format!("{dump}"), "00000000: deca ff                                  ...\n");
+/// assert_eq!(format!("{}", hector::dump(&[])), "");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Dump<'a> {
+    input: &'a [u8],
+    options: DumpOptions,
+    highlights: &'a [Range<usize>],
+}
+
+impl<'a> Dump<'a> {
+    /// Wrap `input` so that it formats as a hexdump.
+    #[must_use]
+    pub fn new(input: &'a [u8]) -> Self {
+        Self::with_options(input, DumpOptions::new())
+    }
+
+    /// Wrap `input` so that it formats as a hexdump with `options`, see [`DumpOptions::dump`].
+    #[must_use]
+    pub fn with_options(input: &'a [u8], options: DumpOptions) -> Self {
+        Self {
+            input,
+            options,
+            highlights: &[],
+        }
+    }
+
+    /// Highlight the bytes in each of `ranges` (which are offsets into the input), with ANSI escapes.
+    ///
+    /// Highlights are shown in reverse video, on top of [colors](DumpOptions::color) if there are any.
+    ///
+    /// # Examples
+    /// ```
+    /// let ranges = [1..3];
+    /// let dump = hector::dump(b"abcd").highlight(&ranges);
+    ///
+    /// assert_eq!(
+    ///     dump.to_string(),
+    ///     "00000000: 61\x1b[7m62 63\x1b[0m64                                \
+    ///      a\x1b[7mbc\x1b[0md\n"
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn highlight(mut self, ranges: &'a [Range<usize>]) -> Self {
+        self.highlights = ranges;
+        self
+    }
+
+    /// Get the wrapped bytes.
+    #[must_use]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.input
+    }
+}
+
+impl fmt::Display for Dump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.options
+            .write_dump(self.input, self.highlights, |chunk| f.write_str(chunk))
+    }
+}
+
+/// Write a hexdump of `input` to `output`.
+///
+/// Since the dump is written a chunk at a time, `output` should be buffered if writes to it are expensive.
+/// To dump a stream as it's written, use [`DumpWriter`].
+///
+/// # Errors
+/// - Any [`io::Error`](std::io::Error) from writing to `output`.
+///
+/// # Examples
+/// ```
+/// let mut output = Vec::new();
+///
+/// hector::dump::dump_to_writer(b"hi!", &mut output)?;
+/// assert_eq!(output, b"00000000: 6869 21                                  hi!\n");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "std")]
+pub fn dump_to_writer<T: AsRef<[u8]>, W: std::io::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> std::io::Result<()> {
+    DumpOptions::new().dump_to_writer(input, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dump;
+    use std::string::ToString;
+
+    #[test]
+    fn matches_xxd() {
+        let input = b"Hello, world\n\x00\x01\xff abcdefghijklmnopqrstuvwxyz";
+
+        assert_eq!(
+            dump(input).to_string(),
+            "00000000: 4865 6c6c 6f2c 2077 6f72 6c64 0a00 01ff  Hello, world....\n\
+             00000010: 2061 6263 6465 6667 6869 6a6b 6c6d 6e6f   abcdefghijklmno\n\
+             00000020: 7071 7273 7475 7677 7879 7a              pqrstuvwxyz\n"
+        );
+    }
+
+    #[test]
+    fn full_lines_only() {
+        let output = dump(&[0x7f; 32]).to_string();
+
+        assert_eq!(output.lines().count(), 2);
+        assert!(output.lines().all(|line| line.len() == 67));
+    }
+}
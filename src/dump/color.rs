@@ -0,0 +1,78 @@
+/// Turns off every style.
+const RESET: &[u8] = b"\x1b[0m";
+
+/// How a byte is shown in a dump: the color of its kind (if dumps are colored), and whether it's highlighted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(super) struct Style {
+    color: Option<&'static [u8]>,
+    highlighted: bool,
+}
+
+impl Style {
+    /// No style at all, as in uncolored dumps.
+    pub(super) const PLAIN: Self = Self {
+        color: None,
+        highlighted: false,
+    };
+
+    /// The longest that the escapes before a byte can be, the reset of the last style and the start of the next one.
+    #[cfg(any(feature = "std", test))]
+    pub(super) const MAX_LEN: usize = RESET.len() + b"\x1b[7;90m".len();
+
+    /// The style of `byte`.
+    pub(super) fn new(byte: u8, color: bool, highlighted: bool) -> Self {
+        Self {
+            color: color.then(|| color_of(byte)),
+            highlighted,
+        }
+    }
+
+    /// Write the escapes that start this style.
+    pub(super) fn start<E>(self, push: &mut impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        if self == Self::PLAIN {
+            return Ok(());
+        }
+
+        push(b"\x1b[")?;
+
+        if self.highlighted {
+            // Reversed, so the highlight shows up whatever the terminal's colors are.
+            push(b"7")?;
+        }
+
+        if let Some(color) = self.color {
+            if self.highlighted {
+                push(b";")?;
+            }
+
+            push(color)?;
+        }
+
+        push(b"m")
+    }
+
+    /// Write the escape that ends this style.
+    pub(super) fn end<E>(self, push: &mut impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        if self == Self::PLAIN {
+            return Ok(());
+        }
+
+        push(RESET)
+    }
+}
+
+/// The color of each kind of byte, as the parameter of an ANSI escape.
+fn color_of(byte: u8) -> &'static [u8] {
+    match byte {
+        // Bright black.
+        0 => b"90",
+        // Green.
+        b'\t' | b'\n' | 0x0b | 0x0c | b'\r' | b' ' => b"32",
+        // Cyan.
+        _ if byte.is_ascii_graphic() => b"36",
+        // Magenta, for the rest of the control characters.
+        _ if byte.is_ascii() => b"35",
+        // Yellow.
+        _ => b"33",
+    }
+}
@@ -0,0 +1,754 @@
+use core::num::NonZeroUsize;
+use core::ops::Range;
+
+use super::color::Style;
+use super::Dump;
+use crate::encode::byte_to_hex;
+use crate::output;
+
+/// The longest an offset can be, a 64 bit offset in decimal.
+const MAX_OFFSET_LEN: usize = 20;
+
+/// How many bytes of a dump are gathered up before being written out.
+const STAGING_LEN: usize = 256;
+
+/// Spaces to pad short lines with, a chunk at a time.
+const SPACES: &[u8] = b"                ";
+
+/// How the offset at the start of each line is written.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum OffsetStyle {
+    /// In lowercase hex, like every hexdump tool does by default.
+    #[default]
+    Hex,
+
+    /// In decimal, like `xxd -d` and `od -A d`.
+    Decimal,
+
+    /// Not at all, each line is just the bytes.
+    None,
+}
+
+/// The tool whose layout a dump follows, for the details that aren't options of their own.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Layout {
+    Xxd,
+    Od,
+    Hexdump,
+}
+
+impl Layout {
+    /// The least number of digits in an offset.
+    fn offset_len(self, style: OffsetStyle) -> usize {
+        match (self, style) {
+            (Layout::Od, OffsetStyle::Hex) => 6,
+            (Layout::Od, _) => 7,
+            _ => 8,
+        }
+    }
+
+    /// What goes between the offset and the hex.
+    fn offset_separator(self) -> &'static [u8] {
+        match self {
+            Layout::Xxd => b": ",
+            Layout::Od => b" ",
+            Layout::Hexdump => b"  ",
+        }
+    }
+
+    /// What goes before and after the ascii gutter.
+    fn gutter(self) -> (&'static [u8], &'static [u8]) {
+        match self {
+            Layout::Xxd => (b"  ", b""),
+            Layout::Od => (b"  >", b"<"),
+            Layout::Hexdump => (b"  |", b"|"),
+        }
+    }
+}
+
+/// The layout of a hexdump, by default the same as [`dump`](crate::dump()) (and `xxd`).
+///
+/// There are presets for the layouts of the usual tools, [`xxd`](Self::xxd), [`od`](Self::od), and [`hexdump`](Self::hexdump),
+/// which can be tweaked further with the other options.
+///
+/// # Examples
+/// ```
+/// use hector::dump::{DumpOptions, OffsetStyle};
+///
+/// let options = DumpOptions::new().bytes_per_line(4).group_size(1).offset(OffsetStyle::Decimal);
+///
+/// assert_eq!(
+///     options.dump(b"Hello!").to_string(),
+///     "00000000: 48 65 6c 6c  Hell\n\
+///      00000004: 6f 21        o!\n"
+/// );
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DumpOptions {
+    layout: Layout,
+    bytes_per_line: NonZeroUsize,
+    group: NonZeroUsize,
+    upper: bool,
+    offset: OffsetStyle,
+    gutter: bool,
+    replacement: u8,
+    color: bool,
+}
+
+impl Default for DumpOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DumpOptions {
+    /// Options that dump exactly like [`dump`](crate::dump()).
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            layout: Layout::Xxd,
+            bytes_per_line: NonZeroUsize::MIN,
+            group: NonZeroUsize::MIN,
+            upper: false,
+            offset: OffsetStyle::Hex,
+            gutter: true,
+            replacement: b'.',
+            color: false,
+        }
+        .bytes_per_line(16)
+        .group_size(2)
+    }
+
+    /// The layout of `xxd`, which is the default.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::dump::DumpOptions::xxd();
+    ///
+    /// assert_eq!(options.dump(b"hi!").to_string(), "00000000: 6869 21                                  hi!\n");
+    /// ```
+    #[must_use]
+    pub const fn xxd() -> Self {
+        Self::new()
+    }
+
+    /// The layout of `od -A x -t x1z`.
+    ///
+    /// Like `od`, this ends with a line with just the offset of the end of the input.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::dump::DumpOptions::od();
+    ///
+    /// assert_eq!(
+    ///     options.dump(b"hi!").to_string(),
+    ///     "000000 68 69 21                                         >hi!<\n\
+    ///      000003\n"
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn od() -> Self {
+        let mut options = Self::new().group_size(1);
+        options.layout = Layout::Od;
+        options
+    }
+
+    /// The layout of `hexdump -C`, with an extra space between the two halves of each line.
+    ///
+    /// Like `hexdump`, this ends with a line with just the offset of the end of the input (unless the input is empty).
+    /// Unlike `hexdump`, repeated lines aren't collapsed into a `*`.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::dump::DumpOptions::hexdump();
+    ///
+    /// assert_eq!(
+    ///     options.dump(b"hi!").to_string(),
+    ///     "00000000  68 69 21                                          |hi!|\n\
+    ///      00000003\n"
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn hexdump() -> Self {
+        let mut options = Self::new().group_size(1);
+        options.layout = Layout::Hexdump;
+        options
+    }
+
+    /// How many bytes go on each line, `16` by default.
+    ///
+    /// # Panics
+    /// If `len` is `0`.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::dump::DumpOptions::new().bytes_per_line(2);
+    ///
+    /// assert_eq!(options.dump(b"hi!").to_string(), "00000000: 6869  hi\n00000002: 21    !\n");
+    /// ```
+    #[must_use]
+    pub const fn bytes_per_line(mut self, len: usize) -> Self {
+        self.bytes_per_line = match NonZeroUsize::new(len) {
+            Some(len) => len,
+            None => panic!("a dump needs at least 1 byte per line"),
+        };
+
+        self
+    }
+
+    /// How many bytes of hex go between each space, `2` by default.
+    ///
+    /// # Panics
+    /// If `size` is `0`.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::dump::DumpOptions::new().group_size(4);
+    ///
+    /// assert_eq!(options.dump(b"Hello!").to_string(), "00000000: 48656c6c 6f21                        Hello!\n");
+    /// ```
+    #[must_use]
+    pub const fn group_size(mut self, size: usize) -> Self {
+        self.group = match NonZeroUsize::new(size) {
+            Some(size) => size,
+            None => panic!("the group size must be at least 1"),
+        };
+
+        self
+    }
+
+    /// Whether to use uppercase hex characters for the bytes (offsets are always lowercase).
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::dump::DumpOptions::new().uppercase(true);
+    ///
+    /// assert_eq!(options.dump(&[0xde, 0xca, 0xff]).to_string(), "00000000: DECA FF                                  ...\n");
+    /// ```
+    #[must_use]
+    pub const fn uppercase(mut self, upper: bool) -> Self {
+        self.upper = upper;
+        self
+    }
+
+    /// How to write the offset at the start of each line.
+    ///
+    /// # Examples
+    /// ```
+    /// use hector::dump::{DumpOptions, OffsetStyle};
+    ///
+    /// let options = DumpOptions::new().bytes_per_line(8).offset(OffsetStyle::Decimal);
+    /// assert_eq!(options.dump(&[0; 12]).to_string().lines().last(), Some("00000008: 0000 0000            ...."));
+    ///
+    /// let options = DumpOptions::new().offset(OffsetStyle::None);
+    /// assert_eq!(options.dump(b"hi!").to_string(), "6869 21                                  hi!\n");
+    /// ```
+    #[must_use]
+    pub const fn offset(mut self, style: OffsetStyle) -> Self {
+        self.offset = style;
+        self
+    }
+
+    /// Whether to show the bytes as ascii at the end of each line.
+    ///
+    /// Without the gutter, short lines aren't padded.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::dump::DumpOptions::new().gutter(false);
+    ///
+    /// assert_eq!(options.dump(b"hi!").to_string(), "00000000: 6869 21\n");
+    /// ```
+    #[must_use]
+    pub const fn gutter(mut self, gutter: bool) -> Self {
+        self.gutter = gutter;
+        self
+    }
+
+    /// What to show in the gutter in place of bytes that aren't printable ascii, `.` by default.
+    ///
+    /// # Panics
+    /// If `replacement` isn't printable ascii itself.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::dump::DumpOptions::new().replacement(b'?');
+    ///
+    /// assert_eq!(options.dump(b"hi\n").to_string(), "00000000: 6869 0a                                  hi?\n");
+    /// ```
+    #[must_use]
+    pub const fn replacement(mut self, replacement: u8) -> Self {
+        assert!(
+            printable(replacement),
+            "the replacement must be printable ascii"
+        );
+
+        self.replacement = replacement;
+        self
+    }
+
+    /// Whether to color the bytes (in both the hex and the gutter) with ANSI escapes, by what kind of byte they are.
+    ///
+    /// `NUL` is gray, whitespace is green, the rest of printable ascii is cyan,
+    /// other control characters are magenta, and non-ascii bytes are yellow.
+    /// The offsets and padding aren't colored, so the layout is the same as without colors.
+    ///
+    /// This is for showing dumps in a terminal, there's no check that the output is one.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::dump::DumpOptions::new().color(true);
+    ///
+    /// assert_eq!(
+    ///     options.dump(b"hi\0").to_string(),
+    ///     "00000000: \x1b[36m6869\x1b[0m \x1b[90m00\x1b[0m                                  \
+    ///      \x1b[36mhi\x1b[0m\x1b[90m.\x1b[0m\n"
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub const fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Wrap `input` so that it formats as a hexdump with these options.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::dump::DumpOptions::new().uppercase(true);
+    ///
+    /// println!("{}", options.dump(&[0xde, 0xca, 0xff]));
+    /// ```
+    #[must_use]
+    pub fn dump<'a, T: AsRef<[u8]> + ?Sized>(&self, input: &'a T) -> Dump<'a> {
+        Dump::with_options(input.as_ref(), *self)
+    }
+
+    /// Write a hexdump of `input` to `output` with these options.
+    ///
+    /// Since the dump is written a chunk at a time, `output` should be buffered if writes to it are expensive.
+    ///
+    /// # Errors
+    /// - Any [`io::Error`](std::io::Error) from writing to `output`.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut output = Vec::new();
+    ///
+    /// hector::dump::DumpOptions::od().dump_to_writer(b"hi!", &mut output)?;
+    /// assert_eq!(output, b"000000 68 69 21                                         >hi!<\n000003\n");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn dump_to_writer<T: AsRef<[u8]>, W: std::io::Write + ?Sized>(
+        &self,
+        input: T,
+        output: &mut W,
+    ) -> std::io::Result<()> {
+        self.write_dump(input.as_ref(), &[], |chunk| {
+            output.write_all(chunk.as_bytes())
+        })
+    }
+
+    #[cfg(feature = "std")]
+    pub(super) fn bytes_per_line_len(&self) -> usize {
+        self.bytes_per_line.get()
+    }
+
+    /// The longest any line of the dump can be.
+    #[cfg(any(feature = "std", test))]
+    pub(super) fn max_line_len(&self) -> usize {
+        let (open, close) = self.layout.gutter();
+
+        MAX_OFFSET_LEN
+            + self.layout.offset_separator().len()
+            + self.hex_len()
+            + open.len()
+            + self.bytes_per_line.get()
+            + close.len()
+            + 1
+            + if self.color {
+                // Each byte can start a new style, in the hex and the gutter, and both end with a reset.
+                self.bytes_per_line.get() * Style::MAX_LEN * 2 + Style::MAX_LEN * 2
+            } else {
+                0
+            }
+    }
+
+    /// The width of the hex columns of a full line.
+    fn hex_len(&self) -> usize {
+        let len = self.bytes_per_line.get();
+
+        len * 2 + (len - 1) / self.group + usize::from(self.layout == Layout::Hexdump && len > 1)
+    }
+
+    /// The number of spaces before the byte at `index` in a line.
+    fn spaces_before(&self, index: usize) -> usize {
+        if index == 0 {
+            return 0;
+        }
+
+        let half = self.layout == Layout::Hexdump && index == self.bytes_per_line.get() / 2;
+
+        usize::from(index % self.group == 0) + usize::from(half)
+    }
+
+    /// Write the whole dump of `input`, passing it to `write` a chunk at a time.
+    pub(super) fn write_dump<E>(
+        &self,
+        input: &[u8],
+        highlights: &[Range<usize>],
+        write: impl FnMut(&str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let mut staging = Staging {
+            buffer: [0; STAGING_LEN],
+            len: 0,
+            write,
+        };
+
+        let mut push = |piece: &[u8]| staging.push(piece);
+
+        let len = self.bytes_per_line.get();
+        for (index, line) in input.chunks(len).enumerate() {
+            self.write_line(index.wrapping_mul(len), line, highlights, &mut push)?;
+        }
+
+        self.write_end(input.len(), &mut push)?;
+
+        staging.flush()
+    }
+
+    /// Write the line of (up to [`bytes_per_line`](Self::bytes_per_line)) bytes at `offset`, a piece at a time.
+    ///
+    /// The bytes whose offsets are in any of `highlights` are highlighted.
+    pub(super) fn write_line<E>(
+        &self,
+        offset: usize,
+        line: &[u8],
+        highlights: &[Range<usize>],
+        push: &mut impl FnMut(&[u8]) -> Result<(), E>,
+    ) -> Result<(), E> {
+        if self.write_offset(offset, push)? {
+            push(self.layout.offset_separator())?;
+        }
+
+        let style = |index: usize, byte| {
+            let offset = offset.wrapping_add(index);
+            let highlighted = highlights.iter().any(|range| range.contains(&offset));
+
+            Style::new(byte, self.color, highlighted)
+        };
+
+        // Styles only change where they need to, so runs of the same kind of byte share one escape.
+        let mut current = Style::PLAIN;
+
+        let mut hex_len = 0;
+        for (index, &byte) in line.iter().enumerate() {
+            let next = style(index, byte);
+            if next != current {
+                current.end(push)?;
+            }
+
+            // The spaces between styles are left plain.
+            let spaces = self.spaces_before(index);
+            push(&SPACES[..spaces])?;
+
+            if next != current {
+                next.start(push)?;
+                current = next;
+            }
+
+            let pair = if self.upper {
+                byte_to_hex::<true>(byte)
+            } else {
+                byte_to_hex::<false>(byte)
+            };
+            push(&pair)?;
+
+            hex_len += spaces + 2;
+        }
+
+        current.end(push)?;
+        current = Style::PLAIN;
+
+        if self.gutter {
+            // Short lines are padded so the gutter still lines up.
+            let mut padding = self.hex_len() - hex_len;
+            while padding != 0 {
+                let len = padding.min(SPACES.len());
+                push(&SPACES[..len])?;
+                padding -= len;
+            }
+
+            let (open, close) = self.layout.gutter();
+            push(open)?;
+
+            for (index, &byte) in line.iter().enumerate() {
+                let next = style(index, byte);
+                if next != current {
+                    current.end(push)?;
+                    next.start(push)?;
+                    current = next;
+                }
+
+                push(&[if printable(byte) {
+                    byte
+                } else {
+                    self.replacement
+                }])?;
+            }
+
+            current.end(push)?;
+            push(close)?;
+        }
+
+        push(b"\n")
+    }
+
+    /// Write the line (if any) that goes after the dump of `len` bytes.
+    pub(super) fn write_end<E>(
+        &self,
+        len: usize,
+        push: &mut impl FnMut(&[u8]) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let ends = match self.layout {
+            Layout::Xxd => false,
+            Layout::Od => true,
+            Layout::Hexdump => len != 0,
+        };
+
+        if ends && self.write_offset(len, push)? {
+            push(b"\n")?;
+        }
+
+        Ok(())
+    }
+
+    /// Write `offset` (if offsets are shown), returning whether it was.
+    fn write_offset<E>(
+        &self,
+        offset: usize,
+        push: &mut impl FnMut(&[u8]) -> Result<(), E>,
+    ) -> Result<bool, E> {
+        let radix = match self.offset {
+            OffsetStyle::Hex => 16,
+            OffsetStyle::Decimal => 10,
+            OffsetStyle::None => return Ok(false),
+        };
+
+        let mut digits = [b'0'; MAX_OFFSET_LEN];
+        let mut start = digits.len();
+        let mut rest = offset;
+
+        for digit in digits.iter_mut().rev() {
+            let [_, char] = byte_to_hex::<false>((rest % radix) as u8);
+            *digit = char;
+            start -= 1;
+
+            rest /= radix;
+            if rest == 0 {
+                break;
+            }
+        }
+
+        let start = start.min(MAX_OFFSET_LEN - self.layout.offset_len(self.offset));
+        push(&digits[start..])?;
+
+        Ok(true)
+    }
+}
+
+/// Whether `byte` is printable ascii, and goes in the gutter as is.
+const fn printable(byte: u8) -> bool {
+    byte.is_ascii_graphic() || byte == b' '
+}
+
+/// Gathers up the small pieces that a dump is written in, passing them on to `write` in bigger chunks.
+struct Staging<F> {
+    buffer: [u8; STAGING_LEN],
+    len: usize,
+    write: F,
+}
+
+impl<F> Staging<F> {
+    fn push<E>(&mut self, piece: &[u8]) -> Result<(), E>
+    where
+        F: FnMut(&str) -> Result<(), E>,
+    {
+        if self.buffer.len() - self.len < piece.len() {
+            self.flush()?;
+        }
+
+        self.buffer[self.len..][..piece.len()].copy_from_slice(piece);
+        self.len += piece.len();
+
+        Ok(())
+    }
+
+    fn flush<E>(&mut self) -> Result<(), E>
+    where
+        F: FnMut(&str) -> Result<(), E>,
+    {
+        let len = core::mem::take(&mut self.len);
+
+        (self.write)(output::hex_str(&self.buffer[..len]))
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<F> Drop for Staging<F> {
+    fn drop(&mut self) {
+        crate::scratch::wipe(&mut self.buffer[..]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DumpOptions, OffsetStyle};
+    use std::string::ToString;
+
+    const INPUT: &[u8] = b"Hello, world\n\x00\x01\xff abcdefghijklmnopqrstuvwxyz";
+
+    #[test]
+    fn default_matches_dump() {
+        for len in [0, 1, 16, 17, INPUT.len()] {
+            assert_eq!(
+                DumpOptions::new().dump(&INPUT[..len]).to_string(),
+                crate::dump(&INPUT[..len]).to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn matches_xxd() {
+        let options = DumpOptions::xxd()
+            .group_size(1)
+            .bytes_per_line(8)
+            .uppercase(true);
+
+        let output = options.dump(INPUT).to_string();
+        let mut lines = output.lines();
+
+        assert_eq!(
+            lines.next(),
+            Some("00000000: 48 65 6C 6C 6F 2C 20 77  Hello, w")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("00000008: 6F 72 6C 64 0A 00 01 FF  orld....")
+        );
+    }
+
+    #[test]
+    fn matches_od() {
+        assert_eq!(
+            DumpOptions::od().dump(INPUT).to_string(),
+            "000000 48 65 6c 6c 6f 2c 20 77 6f 72 6c 64 0a 00 01 ff  >Hello, world....<\n\
+             000010 20 61 62 63 64 65 66 67 68 69 6a 6b 6c 6d 6e 6f  > abcdefghijklmno<\n\
+             000020 70 71 72 73 74 75 76 77 78 79 7a                 >pqrstuvwxyz<\n\
+             00002b\n"
+        );
+
+        let output = DumpOptions::od()
+            .offset(OffsetStyle::Decimal)
+            .dump(INPUT)
+            .to_string();
+        assert_eq!(
+            output.lines().nth(1),
+            Some("0000016 20 61 62 63 64 65 66 67 68 69 6a 6b 6c 6d 6e 6f  > abcdefghijklmno<")
+        );
+
+        assert_eq!(DumpOptions::od().dump(&[]).to_string(), "000000\n");
+    }
+
+    #[test]
+    fn matches_hexdump() {
+        assert_eq!(
+            DumpOptions::hexdump().dump(&INPUT[..20]).to_string(),
+            "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 0a 00 01 ff  |Hello, world....|\n\
+             00000010  20 61 62 63                                       | abc|\n\
+             00000014\n"
+        );
+
+        assert_eq!(DumpOptions::hexdump().dump(&[]).to_string(), "");
+    }
+
+    /// Format a single line, for checking the edge cases that would need huge inputs.
+    fn line(options: DumpOptions, offset: usize, bytes: &[u8]) -> std::vec::Vec<u8> {
+        let mut line = std::vec::Vec::new();
+
+        let result = options.write_line(offset, bytes, &[], &mut |piece| {
+            line.extend_from_slice(piece);
+            Ok::<(), core::convert::Infallible>(())
+        });
+        let Ok(()) = result;
+
+        line
+    }
+
+    #[test]
+    fn long_offsets() {
+        assert_eq!(
+            line(DumpOptions::new(), 0x1_2345_6789, b"a"),
+            b"123456789: 61                                       a\n"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn colors_keep_layout() {
+        let input: std::vec::Vec<u8> = (0..=255).collect();
+        let ranges = [0..1, 5..40, 100..101, 255..1000];
+
+        for options in [
+            DumpOptions::xxd(),
+            DumpOptions::od(),
+            DumpOptions::hexdump(),
+        ] {
+            let plain = options.dump(&input).to_string();
+            let colored = options
+                .color(true)
+                .dump(&input)
+                .highlight(&ranges)
+                .to_string();
+
+            // Every escape is `\x1b[`, some digits and `;`s, then `m`.
+            let mut stripped = std::string::String::new();
+            let mut escaped = false;
+            for char in colored.chars() {
+                match char {
+                    '\x1b' => escaped = true,
+                    'm' if escaped => escaped = false,
+                    _ if escaped => {}
+                    _ => stripped.push(char),
+                }
+            }
+
+            assert_eq!(stripped, plain, "{options:?}");
+        }
+    }
+
+    #[test]
+    fn lines_fit() {
+        for options in [
+            DumpOptions::xxd(),
+            DumpOptions::od(),
+            DumpOptions::hexdump(),
+        ] {
+            for len in [1, 2, 3, 7, 8, 40] {
+                let options = options.bytes_per_line(len).offset(OffsetStyle::Decimal);
+                let longest = line(options, usize::MAX, &[0; 40][..len]);
+                assert_eq!(longest.len(), options.max_line_len(), "{options:?}");
+
+                // Alternating kinds of bytes need the most escapes.
+                #[cfg(feature = "std")]
+                {
+                    let options = options.color(true);
+                    let bytes: std::vec::Vec<u8> = (0..len).map(|it| [0, 0xff][it % 2]).collect();
+                    assert!(line(options, 0, &bytes).len() <= options.max_line_len());
+                }
+            }
+        }
+    }
+}
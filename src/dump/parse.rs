@@ -0,0 +1,256 @@
+use core::fmt;
+
+use alloc::vec::Vec;
+
+/// What went wrong while [parsing](parse) a hexdump.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The line didn't start with a hex offset followed by `:`.
+    InvalidOffset,
+
+    /// The character at the error's column was not a valid hex character (or a space between groups).
+    InvalidHex {
+        /// The character in question.
+        value: u8,
+    },
+
+    /// The line's offset didn't follow on from the line before it.
+    OffsetMismatch {
+        /// The offset that the line should have had.
+        expected: usize,
+
+        /// The offset that the line had.
+        found: usize,
+    },
+}
+
+/// An error from [parsing](parse) a hexdump, and where in the dump it was.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line (starting from 1) that the error occurred on.
+    pub line: usize,
+
+    /// The column (in bytes, starting from 1) that the error occurred at.
+    pub column: usize,
+
+    /// What went wrong.
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+
+        match self.kind {
+            ParseErrorKind::InvalidOffset => {
+                f.write_str("line doesn't start with a valid hex offset")
+            }
+            ParseErrorKind::InvalidHex { value } => write!(
+                f,
+                "character `{char_value}` ({value:#04x}) is not a valid hex character",
+                char_value = value as char
+            ),
+            ParseErrorKind::OffsetMismatch { expected, found } => {
+                write!(f, "expected offset `{expected:#x}`, found `{found:#x}`")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Parse a hexdump (like the ones [`dump`](crate::dump()) and `xxd` write) back into bytes, like `xxd -r`.
+///
+/// Each line is an offset and `:`, then groups of hex separated by single spaces,
+/// the hex ends at the first run of 2 or more spaces (where the ascii gutter starts) or the end of the line.
+/// Lines can end with `\n` or `\r\n`, and blank lines are skipped.
+///
+/// The first line can start at any offset, but each line after it has to start where the line before it ended.
+///
+/// Only the `xxd` layout (the default [`DumpOptions`](super::DumpOptions)) with hex offsets is accepted.
+/// The `od` and `hexdump -C` layouts, and dumps without offsets, are rejected with [`ParseErrorKind::InvalidOffset`],
+/// and decimal offsets are rejected with [`ParseErrorKind::OffsetMismatch`] (from the second line,
+/// a single line at offset `0` reads the same either way).
+///
+/// # Errors
+/// - [`ParseError`] if the dump is malformed, with the line and column where.
+///
+/// # Examples
+/// ```
+/// let text = "\
+///     00000000: 4865 6c6c 6f2c 2077 6f72 6c64 0a00 01ff  Hello, world....\n\
+///     00000010: 2061 6263                                 abc\n";
+///
+/// assert_eq!(hector::dump::parse(text)?, b"Hello, world\n\x00\x01\xff abc");
+/// # Ok::<(), hector::dump::ParseError>(())
+/// ```
+///
+/// ```
+/// use hector::dump::{parse, ParseError, ParseErrorKind};
+///
+/// assert_eq!(
+///     parse("00000000: 6869\n00000004: 21"),
+///     Err(ParseError { line: 2, column: 1, kind: ParseErrorKind::OffsetMismatch { expected: 2, found: 4 } })
+/// );
+/// assert_eq!(
+///     parse("00000000: 68g9"),
+///     Err(ParseError { line: 1, column: 13, kind: ParseErrorKind::InvalidHex { value: b'g' } })
+/// );
+/// ```
+pub fn parse<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, ParseError> {
+    let mut output = Vec::new();
+    let mut start = None;
+
+    for (index, line) in input.as_ref().split(|&it| it == b'\n').enumerate() {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+        let error = |column, kind| ParseError {
+            line: index + 1,
+            column: column + 1,
+            kind,
+        };
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let (offset, hex) = split_offset(line).ok_or_else(|| {
+            let column = line.iter().take_while(|it| it.is_ascii_hexdigit()).count();
+            error(column, ParseErrorKind::InvalidOffset)
+        })?;
+
+        let start = *start.get_or_insert(offset);
+        let expected = start.wrapping_add(output.len());
+        if offset != expected {
+            return Err(error(
+                0,
+                ParseErrorKind::OffsetMismatch {
+                    expected,
+                    found: offset,
+                },
+            ));
+        }
+
+        // The hex starts after the offset and `:`.
+        let base = line.len() - hex.len();
+        parse_hex(hex, |byte| output.push(byte)).map_err(|(column, value)| {
+            error(base + column, ParseErrorKind::InvalidHex { value })
+        })?;
+    }
+
+    Ok(output)
+}
+
+/// Split the offset off of `line`, returning it and the rest of the line after the `:`.
+fn split_offset(line: &[u8]) -> Option<(usize, &[u8])> {
+    let colon = line.iter().position(|&it| it == b':')?;
+    let (digits, rest) = line.split_at(colon);
+
+    if digits.is_empty() {
+        return None;
+    }
+
+    let offset = digits.iter().try_fold(0_usize, |offset, &digit| {
+        let nibble = char::from(digit).to_digit(16)?;
+        offset.checked_mul(16)?.checked_add(nibble as usize)
+    })?;
+
+    Some((offset, &rest[1..]))
+}
+
+/// Parse the hex columns at the start of `hex`, passing each byte to `push`,
+/// on error, returns the column (from the start of `hex`) and the character.
+fn parse_hex(hex: &[u8], mut push: impl FnMut(u8)) -> Result<(), (usize, u8)> {
+    let mut column = 0;
+
+    loop {
+        // A single space goes between groups, and 2 (or the end of the line) end the hex.
+        match hex.get(column..) {
+            None | Some([] | [b' '] | [b' ', b' ', ..]) => return Ok(()),
+            Some([b' ', ..]) => column += 1,
+            Some(_) => {}
+        }
+
+        // A byte can't be split by a space, or the end of the line.
+        let nibble = |column: usize| {
+            let value = hex.get(column).copied().unwrap_or(b' ');
+            let nibble = char::from(value).to_digit(16).ok_or((column, value))?;
+            Ok(nibble as u8)
+        };
+
+        while hex.get(column).is_some_and(|&it| it != b' ') {
+            push(nibble(column)? << 4 | nibble(column + 1)?);
+            column += 2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, ParseError, ParseErrorKind};
+    use crate::dump;
+    use crate::dump::{DumpOptions, OffsetStyle};
+    use std::string::ToString;
+
+    #[test]
+    fn round_trip() {
+        let input: std::vec::Vec<u8> = (0..=255).chain(0..77).collect();
+
+        for len in [0, 1, 2, 15, 16, 17, 32, 100, input.len()] {
+            let input = &input[..len];
+            assert_eq!(parse(dump(input).to_string()).as_deref(), Ok(input));
+        }
+    }
+
+    #[test]
+    fn without_gutter() {
+        assert_eq!(
+            parse("00000010: 6869\r\n\r\n00000012: 21\r\n").as_deref(),
+            Ok(&b"hi!"[..])
+        );
+
+        // `xxd` always writes a space after the `:`, but it isn't needed.
+        assert_eq!(parse("00000000:6869").as_deref(), Ok(&b"hi"[..]));
+    }
+
+    #[test]
+    fn other_layouts_are_rejected() {
+        let input = b"Hello, world: 0123456789";
+        let kind = |options: DumpOptions| parse(options.dump(input).to_string()).unwrap_err().kind;
+
+        assert_eq!(kind(DumpOptions::od()), ParseErrorKind::InvalidOffset);
+        assert_eq!(kind(DumpOptions::hexdump()), ParseErrorKind::InvalidOffset);
+        assert_eq!(
+            kind(DumpOptions::new().offset(OffsetStyle::None)),
+            ParseErrorKind::InvalidOffset
+        );
+        assert_eq!(
+            kind(DumpOptions::new().offset(OffsetStyle::Decimal)),
+            ParseErrorKind::OffsetMismatch {
+                expected: 0x10,
+                found: 0x16
+            }
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = |line, column, kind| Err(ParseError { line, column, kind });
+
+        assert_eq!(
+            parse("0000zz: 00"),
+            error(1, 5, ParseErrorKind::InvalidOffset)
+        );
+        assert_eq!(parse("6869"), error(1, 5, ParseErrorKind::InvalidOffset));
+        assert_eq!(parse(": 00"), error(1, 1, ParseErrorKind::InvalidOffset));
+        assert_eq!(
+            parse("00000000: 686"),
+            error(1, 14, ParseErrorKind::InvalidHex { value: b' ' })
+        );
+        assert_eq!(
+            parse("00000000: 68 6 21"),
+            error(1, 15, ParseErrorKind::InvalidHex { value: b' ' })
+        );
+    }
+}
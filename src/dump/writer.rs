@@ -0,0 +1,305 @@
+use core::convert::Infallible;
+use std::boxed::Box;
+use std::io::{self, Write};
+use std::vec;
+
+use super::DumpOptions;
+use crate::io::{BUFFER_LEN, TAKEN};
+use crate::scratch::Scratch;
+
+/// A writer that writes a hexdump of everything written to it to an inner writer, a line at a time.
+///
+/// Each line's offset is counted from the first byte written, so a stream can be dumped
+/// without holding all of it (or its dump) in memory.
+///
+/// The dump is staged in an internal buffer, and only written to the inner writer when the buffer fills up,
+/// on [`flush`](Write::flush), or when this is dropped (where errors are ignored, like [`BufWriter`](io::BufWriter)).
+/// A line isn't finished until it's full, so [`flush`](Write::flush) leaves a partial line buffered,
+/// it's written by [`into_inner`](Self::into_inner) or when this is dropped.
+///
+/// # Examples
+/// ```
+/// use std::io::Write;
+///
+/// let mut writer = hector::dump::DumpWriter::new(Vec::new());
+///
+/// writer.write_all(b"Hello, ")?;
+/// writer.write_all(b"world\n\x00\x01\xff abc")?;
+///
+/// assert_eq!(
+///     writer.into_inner().map_err(|(err, _)| err)?,
+///     b"00000000: 4865 6c6c 6f2c 2077 6f72 6c64 0a00 01ff  Hello, world....\n\
+///       00000010: 2061 6263                                 abc\n"
+/// );
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct DumpWriter<W: Write> {
+    /// Only `None` once [`into_inner`](Self::into_inner) has taken it, when there's nothing left to write.
+    inner: Option<W>,
+    /// Dump text that hasn't been written to `inner` yet is `buffer[..len]`.
+    buffer: Scratch<Box<[u8]>>,
+    len: usize,
+    /// The bytes of the current line, which aren't in the dump yet, are `line[..pending]`.
+    line: Scratch<Box<[u8]>>,
+    pending: usize,
+    /// The offset of the current line.
+    offset: usize,
+    /// Whether the dump has been finished (so the last lines aren't written twice).
+    finished: bool,
+    options: DumpOptions,
+}
+
+impl<W: Write> DumpWriter<W> {
+    /// Create a new writer, writing a hexdump to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self::with_options(inner, DumpOptions::new())
+    }
+
+    /// Create a new writer, writing a hexdump with `options` to `inner`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::io::Write;
+    ///
+    /// use hector::dump::{DumpOptions, DumpWriter};
+    ///
+    /// let mut writer = DumpWriter::with_options(Vec::new(), DumpOptions::hexdump());
+    /// writer.write_all(b"hi!")?;
+    ///
+    /// assert_eq!(
+    ///     writer.into_inner().map_err(|(err, _)| err)?,
+    ///     b"00000000  68 69 21                                          |hi!|\n00000003\n"
+    /// );
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn with_options(inner: W, options: DumpOptions) -> Self {
+        let buffer_len = BUFFER_LEN.max(options.max_line_len());
+
+        Self {
+            inner: Some(inner),
+            buffer: Scratch(vec![0; buffer_len].into_boxed_slice()),
+            len: 0,
+            line: Scratch(vec![0; options.bytes_per_line_len()].into_boxed_slice()),
+            pending: 0,
+            offset: 0,
+            finished: false,
+            options,
+        }
+    }
+
+    /// Get a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect(TAKEN)
+    }
+
+    /// Get a mutable reference to the inner writer.
+    ///
+    /// Writing to it directly will put the data before any of the dump that's still buffered.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.as_mut().expect(TAKEN)
+    }
+
+    /// Finish the last line, write all the buffered text to the inner writer,
+    /// and then unwrap this writer, returning the inner writer.
+    ///
+    /// # Errors
+    /// If writing the buffered text fails, the error is returned along with this writer (so nothing is lost).
+    pub fn into_inner(mut self) -> Result<W, (io::Error, Self)> {
+        if let Err(err) = self.finish() {
+            return Err((err, self));
+        }
+
+        Ok(self.inner.take().expect(TAKEN))
+    }
+
+    /// Whether there's room in the buffer for another line.
+    fn has_room(&self) -> bool {
+        self.buffer.len() - self.len >= self.options.max_line_len()
+    }
+
+    /// Add the current line to the dump, even if it isn't full, there must be room for it in the buffer.
+    fn push_line(&mut self) {
+        let result = self.options.write_line(
+            self.offset,
+            &self.line[..self.pending],
+            &[],
+            &mut append(&mut self.buffer, &mut self.len),
+        );
+
+        let Ok(()) = result;
+
+        self.offset = self.offset.wrapping_add(self.pending);
+        self.pending = 0;
+    }
+
+    /// Add the partial line (if any) and the end of the dump, and write everything that's buffered.
+    fn finish(&mut self) -> io::Result<()> {
+        if !self.finished {
+            if self.pending != 0 {
+                if !self.has_room() {
+                    self.flush_buffer()?;
+                }
+
+                self.push_line();
+            }
+
+            if !self.has_room() {
+                self.flush_buffer()?;
+            }
+
+            let result = self
+                .options
+                .write_end(self.offset, &mut append(&mut self.buffer, &mut self.len));
+
+            let Ok(()) = result;
+            self.finished = true;
+        }
+
+        self.flush_buffer()
+    }
+
+    /// Write as much of the buffered text to the inner writer as possible,
+    /// an error leaves whatever wasn't written in the buffer.
+    fn flush_buffer(&mut self) -> io::Result<()> {
+        let Some(inner) = &mut self.inner else {
+            return Ok(());
+        };
+
+        let mut written = 0;
+
+        let result = loop {
+            if written == self.len {
+                break Ok(());
+            }
+
+            match inner.write(&self.buffer[written..self.len]) {
+                Ok(0) => break Err(io::ErrorKind::WriteZero.into()),
+                Ok(len) => written += len,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => break Err(err),
+            }
+        };
+
+        self.buffer.copy_within(written..self.len, 0);
+        self.len -= written;
+
+        result
+    }
+}
+
+/// Append the pieces of a dump to `buffer[*len..]`, which must have room for them.
+fn append<'a>(
+    buffer: &'a mut [u8],
+    len: &'a mut usize,
+) -> impl FnMut(&[u8]) -> Result<(), Infallible> + 'a {
+    move |piece| {
+        buffer[*len..][..piece.len()].copy_from_slice(piece);
+        *len += piece.len();
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for DumpWriter<W> {
+    fn write(&mut self, input: &[u8]) -> io::Result<usize> {
+        if input.is_empty() {
+            return Ok(0);
+        }
+
+        if !self.has_room() {
+            self.flush_buffer()?;
+        }
+
+        // Take as many lines as there's room for in the buffer.
+        let mut taken = 0;
+        while taken < input.len() && self.has_room() {
+            let len = (input.len() - taken).min(self.line.len() - self.pending);
+            self.line[self.pending..][..len].copy_from_slice(&input[taken..][..len]);
+
+            self.pending += len;
+            taken += len;
+
+            if self.pending == self.line.len() {
+                self.push_line();
+            }
+        }
+
+        Ok(taken)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_buffer()?;
+        self.get_mut().flush()
+    }
+}
+
+impl<W: Write> Drop for DumpWriter<W> {
+    fn drop(&mut self) {
+        // There's nowhere to report an error to, call `into_inner` first to see them.
+        drop(self.finish());
+    }
+}
+
+impl<W: Write + core::fmt::Debug> core::fmt::Debug for DumpWriter<W> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DumpWriter")
+            .field("inner", self.get_ref())
+            .field("buffered", &self.len)
+            .field("pending", &self.pending)
+            .field("offset", &self.offset)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    use super::DumpWriter;
+    use crate::dump::DumpOptions;
+    use crate::io::BUFFER_LEN;
+
+    #[test]
+    fn matches_dump() {
+        let input = crate::test_util::bytes(BUFFER_LEN);
+
+        let layouts = [
+            DumpOptions::new(),
+            DumpOptions::od().bytes_per_line(7).color(true),
+            DumpOptions::hexdump().bytes_per_line(BUFFER_LEN),
+        ];
+
+        for options in layouts {
+            for chunk_len in [1, 5, 16, 17, 1000, BUFFER_LEN] {
+                let mut writer = DumpWriter::with_options(Vec::new(), options);
+
+                for chunk in input.chunks(chunk_len) {
+                    writer.write_all(chunk).unwrap();
+                }
+
+                let output = writer.into_inner().unwrap();
+                assert_eq!(
+                    output,
+                    options.dump(&input).to_string().as_bytes(),
+                    "chunk_len: {chunk_len}, {options:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn flush_keeps_partial_line() {
+        let mut output = Vec::new();
+        let mut writer = DumpWriter::new(&mut output);
+
+        writer.write_all(&[0; 20]).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(writer.get_ref().len(), 68);
+
+        // Dropping finishes the line.
+        drop(writer);
+        assert_eq!(output.len(), 68 + 56);
+    }
+}
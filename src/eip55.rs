@@ -0,0 +1,226 @@
+//! Ethereum addresses with EIP-55 checksums, where the case of each letter is a bit of checksum
+//! (`0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed`).
+//!
+//! The checksum comes from the Keccak-256 hash of the lowercase hex of the address,
+//! which can be passed in (to use whichever Keccak implementation is already around),
+//! or with the `keccak` feature, is computed here.
+//!
+//! # Examples
+//! ```
+//! let address = hector::decode_to_array("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")?;
+//! let hash = hector::decode_to_array("d385650ce8fdc6db7ee3a091d34814dbc4ce18219ffae52182efff4034d707e5")?;
+//!
+//! let checksummed = hector::eip55::encode_with_hash(&address, &hash);
+//! assert_eq!(checksummed, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+//!
+//! assert!(hector::eip55::verify_with_hash(&checksummed, &hash));
+//! assert!(!hector::eip55::verify_with_hash("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed", &hash));
+//! # Ok::<(), hector::DecodeError>(())
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// The length of a checksummed address, `0x` and 40 hex characters.
+const CHECKSUMMED_LEN: usize = 2 + 40;
+
+/// Encode `address` with `0x` at the start, uppercasing each letter whose nibble in `hash` is 8 or more.
+fn checksummed(address: &[u8; 20], hash: &[u8; 32]) -> [u8; CHECKSUMMED_LEN] {
+    let mut output = [0; CHECKSUMMED_LEN];
+    output[..2].copy_from_slice(b"0x");
+
+    let hex = output[2..].chunks_exact_mut(2).zip(address).zip(hash);
+    for ((pair, &byte), &hash) in hex {
+        let [high, low] = crate::encode::byte_to_hex::<false>(byte);
+
+        pair[0] = if hash >> 4 >= 8 {
+            high.to_ascii_uppercase()
+        } else {
+            high
+        };
+        pair[1] = if hash & 0xf >= 8 {
+            low.to_ascii_uppercase()
+        } else {
+            low
+        };
+    }
+
+    output
+}
+
+/// Encode `address` with an EIP-55 checksum, given the Keccak-256 `hash` of its lowercase hex (without `0x`).
+///
+/// # Examples
+/// ```
+/// let address = hector::decode_to_array("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")?;
+/// let hash = hector::decode_to_array("d385650ce8fdc6db7ee3a091d34814dbc4ce18219ffae52182efff4034d707e5")?;
+///
+/// assert_eq!(hector::eip55::encode_with_hash(&address, &hash), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_with_hash(address: &[u8; 20], hash: &[u8; 32]) -> String {
+    String::from(crate::output::hex_str(&checksummed(address, hash)))
+}
+
+/// Write `address` to `output` with an EIP-55 checksum, given the Keccak-256 `hash` of its lowercase hex (without `0x`).
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+pub fn encode_with_hash_to_fmt<W: core::fmt::Write + ?Sized>(
+    address: &[u8; 20],
+    hash: &[u8; 32],
+    output: &mut W,
+) -> core::fmt::Result {
+    output.write_str(crate::output::hex_str(&checksummed(address, hash)))
+}
+
+/// Check that `input` is an address with a valid EIP-55 checksum,
+/// given the Keccak-256 `hash` of its lowercase hex (without `0x`).
+///
+/// The `0x` at the start is optional, but the case of every letter has to match the checksum,
+/// so addresses without one (all lowercase or uppercase) aren't valid.
+///
+/// # Examples
+/// ```
+/// let hash = hector::decode_to_array("d385650ce8fdc6db7ee3a091d34814dbc4ce18219ffae52182efff4034d707e5")?;
+///
+/// assert!(hector::eip55::verify_with_hash("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", &hash));
+/// assert!(hector::eip55::verify_with_hash("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", &hash));
+/// assert!(!hector::eip55::verify_with_hash("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD", &hash));
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[must_use]
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn verify_with_hash<T: AsRef<[u8]>>(input: T, hash: &[u8; 32]) -> bool {
+    verify_by(input.as_ref(), |_| *hash)
+}
+
+/// Check that `input` is an address with a valid EIP-55 checksum, getting the hash of the address from `hash`.
+fn verify_by(input: &[u8], hash: impl FnOnce(&[u8; 20]) -> [u8; 32]) -> bool {
+    let hex = input.strip_prefix(b"0x").unwrap_or(input);
+
+    let Ok(address) = crate::decode_to_array::<_, 20>(hex) else {
+        return false;
+    };
+
+    checksummed(&address, &hash(&address))[2..] == *hex
+}
+
+/// The Keccak-256 hash of the lowercase hex of `address`, which the checksum is made from.
+#[cfg(feature = "keccak")]
+fn hash_of(address: &[u8; 20]) -> [u8; 32] {
+    use tiny_keccak::{Hasher, Keccak};
+
+    let mut hex = [0; 40];
+    for (pair, &byte) in hex.chunks_exact_mut(2).zip(address) {
+        pair.copy_from_slice(&crate::encode::byte_to_hex::<false>(byte));
+    }
+
+    let mut hasher = Keccak::v256();
+    hasher.update(&hex);
+
+    let mut hash = [0; 32];
+    hasher.finalize(&mut hash);
+
+    hash
+}
+
+/// Encode `address` with an EIP-55 checksum.
+///
+/// # Examples
+/// ```
+/// let address = hector::decode_to_array("fb6916095ca1df60bb79ce92ce3ea74c37c5d359")?;
+///
+/// assert_eq!(hector::eip55::encode(&address), "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359");
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[cfg(all(feature = "alloc", feature = "keccak"))]
+#[must_use]
+pub fn encode(address: &[u8; 20]) -> String {
+    encode_with_hash(address, &hash_of(address))
+}
+
+/// Write `address` to `output` with an EIP-55 checksum.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// struct Address([u8; 20]);
+///
+/// impl core::fmt::Display for Address {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         hector::eip55::encode_to_fmt(&self.0, f)
+///     }
+/// }
+///
+/// let address = Address(hector::decode_to_array("dbf03b407c01e7cd3cbea99509d93f8dddc8c6fb")?);
+/// assert_eq!(address.to_string(), "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB");
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[cfg(feature = "keccak")]
+pub fn encode_to_fmt<W: core::fmt::Write + ?Sized>(
+    address: &[u8; 20],
+    output: &mut W,
+) -> core::fmt::Result {
+    encode_with_hash_to_fmt(address, &hash_of(address), output)
+}
+
+/// Check that `input` is an address with a valid EIP-55 checksum, see [`verify_with_hash`].
+///
+/// # Examples
+/// ```
+/// assert!(hector::eip55::verify("0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb"));
+/// assert!(!hector::eip55::verify("0xd1220a0cf47c7b9be7a2e6ba89f429762e7b9adb"));
+/// assert!(!hector::eip55::verify("0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aD"));
+/// ```
+#[cfg(feature = "keccak")]
+#[must_use]
+pub fn verify<T: AsRef<[u8]>>(input: T) -> bool {
+    verify_by(input.as_ref(), hash_of)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{checksummed, verify_with_hash};
+
+    #[test]
+    fn case_follows_hash() {
+        let address = [0xab; 20];
+
+        let output = checksummed(&address, &[0x80; 32]);
+        assert_eq!(&output[..], b"0xAbAbAbAbAbAbAbAbAbAbAbAbAbAbAbAbAbAbAbAb");
+        assert!(verify_with_hash(output, &[0x80; 32]));
+
+        // Digits don't have a case, so only the letters are checked.
+        let output = checksummed(&[0x12; 20], &[0xff; 32]);
+        assert_eq!(&output[2..], b"12".repeat(20));
+        assert!(verify_with_hash(&output[2..], &[0; 32]));
+    }
+
+    #[cfg(feature = "keccak")]
+    #[test]
+    fn matches_spec() {
+        // The examples from EIP-55.
+        let addresses = [
+            "0x52908400098527886E0F7030069857D2E4169EE7",
+            "0x8617E340B3D01FA5F11F306F4090FD50E238070D",
+            "0xde709f2102306220921060314715629080e2fb77",
+            "0x27b1fdb04752bbc536007a920d24acb045561c26",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+
+        for address in addresses {
+            let bytes = crate::decode_to_array(&address[2..]).unwrap();
+
+            assert_eq!(super::encode(&bytes), address);
+            assert!(super::verify(address));
+        }
+    }
+}
@@ -0,0 +1,369 @@
+//! Adapters over [`embedded_io`] (and, with the `embedded-io-async` feature, `embedded_io_async`),
+//! for streaming hex over UART, USB, etc, without `std`.
+//!
+//! These are thin wrappers around the state machines in [`stream`](crate::stream),
+//! and only use a small fixed size buffer.
+
+use embedded_io::{ErrorKind, ErrorType, Read, Write};
+
+use crate::scratch::Scratch;
+use crate::stream::{Decoder, Encoder};
+use crate::DecodeError;
+
+/// The size of the buffers used by the adapters in this module.
+const BUFFER_LEN: usize = 64;
+
+/// An error from a [`DecodeReader`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadError<E> {
+    /// Reading from the inner reader failed.
+    Io(E),
+    /// The text read from the inner reader wasn't valid hex,
+    /// with offsets counted from the start of the stream.
+    Decode(DecodeError),
+}
+
+impl<E: embedded_io::Error> embedded_io::Error for ReadError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            ReadError::Io(err) => err.kind(),
+            ReadError::Decode(_) => ErrorKind::InvalidData,
+        }
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for ReadError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ReadError::Io(err) => err.fmt(f),
+            ReadError::Decode(err) => err.fmt(f),
+        }
+    }
+}
+
+/// A reader that decodes the hex text read from an inner reader.
+///
+/// This does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`ReadError::Io`] if reading from the inner reader fails.
+/// - [`ReadError::Decode`] if the text isn't valid hex (including the stream ending in the middle of a byte).
+///
+/// Any bytes before an invalid character are returned first, and the error is returned again on every read after it.
+///
+/// # Examples
+/// ```
+/// use embedded_io::Read;
+///
+/// let mut reader = hector::embedded::DecodeReader::new(&b"decaff"[..]);
+/// let mut output = [0; 3];
+///
+/// reader.read_exact(&mut output).unwrap();
+///
+/// assert_eq!(output, [0xde, 0xca, 0xff]);
+/// ```
+#[derive(Debug)]
+pub struct DecodeReader<R> {
+    inner: R,
+    /// Hex text that's been read from `inner` but not decoded yet is `buffer[start..end]`.
+    buffer: Scratch<[u8; BUFFER_LEN]>,
+    start: usize,
+    end: usize,
+    decoder: Decoder,
+}
+
+impl<R> DecodeReader<R> {
+    /// Create a new reader, decoding the hex text read from `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buffer: Scratch([0; BUFFER_LEN]),
+            start: 0,
+            end: 0,
+            decoder: Decoder::new(),
+        }
+    }
+
+    /// Unwrap this reader, returning the inner reader.
+    ///
+    /// Any hex text that has been read from the inner reader but not decoded yet is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Handle `read` bytes having been read into the buffer.
+    ///
+    /// Returns `Some` if that's the end of the stream.
+    fn refilled<E>(&mut self, read: usize) -> Option<Result<usize, ReadError<E>>> {
+        if read == 0 {
+            return Some(
+                self.decoder
+                    .clone()
+                    .finish()
+                    .map(|()| 0)
+                    .map_err(ReadError::Decode),
+            );
+        }
+
+        (self.start, self.end) = (0, read);
+
+        None
+    }
+
+    /// Decode as much of the buffered text as fits into `output`.
+    fn decode_buffered<E>(&mut self, output: &mut [u8]) -> Result<usize, ReadError<E>> {
+        let (consumed, written) = self
+            .decoder
+            .decode_chunk(&self.buffer[self.start..self.end], output)
+            .map_err(ReadError::Decode)?;
+
+        self.start += consumed;
+
+        Ok(written)
+    }
+}
+
+impl<R: ErrorType> ErrorType for DecodeReader<R> {
+    type Error = ReadError<R::Error>;
+}
+
+impl<R: Read> Read for DecodeReader<R> {
+    fn read(&mut self, output: &mut [u8]) -> Result<usize, Self::Error> {
+        if output.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            if self.start == self.end {
+                let read = self.inner.read(&mut self.buffer).map_err(ReadError::Io)?;
+
+                if let Some(result) = self.refilled(read) {
+                    return result;
+                }
+            }
+
+            // Nothing gets written if all that was left was half a byte.
+            match self.decode_buffered(output)? {
+                0 => continue,
+                written => return Ok(written),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "embedded-io-async")]
+impl<R: embedded_io_async::Read> embedded_io_async::Read for DecodeReader<R> {
+    async fn read(&mut self, output: &mut [u8]) -> Result<usize, Self::Error> {
+        if output.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            if self.start == self.end {
+                let read = self
+                    .inner
+                    .read(&mut self.buffer)
+                    .await
+                    .map_err(ReadError::Io)?;
+
+                if let Some(result) = self.refilled(read) {
+                    return result;
+                }
+            }
+
+            // Nothing gets written if all that was left was half a byte.
+            match self.decode_buffered(output)? {
+                0 => continue,
+                written => return Ok(written),
+            }
+        }
+    }
+}
+
+/// A writer that hex encodes everything written to it before passing it on to an inner writer.
+///
+/// Every write is passed straight through (there's no buffering to flush),
+/// if writing to the inner writer fails, the amount of input that made it through is unknown (like `write_all`).
+///
+/// # Examples
+/// ```
+/// use embedded_io::Write;
+///
+/// let mut output = [0; 6];
+/// let mut writer = hector::embedded::EncodeWriter::new(&mut output[..]);
+///
+/// writer.write_all(&[0xde, 0xca, 0xff]).unwrap();
+///
+/// assert_eq!(&output, b"decaff");
+/// ```
+#[derive(Debug)]
+pub struct EncodeWriter<W> {
+    inner: W,
+    encoder: Encoder,
+}
+
+impl<W> EncodeWriter<W> {
+    /// Create a new writer, writing lowercase hex to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            encoder: Encoder::new(),
+        }
+    }
+
+    /// Create a new writer, writing uppercase hex to `inner`.
+    pub fn new_upper(inner: W) -> Self {
+        Self {
+            inner,
+            encoder: Encoder::new_upper(),
+        }
+    }
+
+    /// Unwrap this writer, returning the inner writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: ErrorType> ErrorType for EncodeWriter<W> {
+    type Error = W::Error;
+}
+
+impl<W: Write> Write for EncodeWriter<W> {
+    fn write(&mut self, input: &[u8]) -> Result<usize, Self::Error> {
+        if input.is_empty() {
+            return Ok(0);
+        }
+
+        let mut buffer = Scratch([0; BUFFER_LEN]);
+        let (consumed, written) = self.encoder.encode_chunk(input, &mut buffer);
+        self.inner.write_all(&buffer[..written])?;
+
+        Ok(consumed)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        // The buffer is an even length, so this shouldn't happen, but just in case.
+        if self.encoder.has_pending() {
+            let mut buffer = Scratch([0; 1]);
+            let (_, written) = self.encoder.encode_chunk(&[], &mut buffer);
+            self.inner.write_all(&buffer[..written])?;
+        }
+
+        self.inner.flush()
+    }
+}
+
+#[cfg(feature = "embedded-io-async")]
+impl<W: embedded_io_async::Write> embedded_io_async::Write for EncodeWriter<W> {
+    async fn write(&mut self, input: &[u8]) -> Result<usize, Self::Error> {
+        if input.is_empty() {
+            return Ok(0);
+        }
+
+        let mut buffer = Scratch([0; BUFFER_LEN]);
+        let (consumed, written) = self.encoder.encode_chunk(input, &mut buffer);
+        self.inner.write_all(&buffer[..written]).await?;
+
+        Ok(consumed)
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        // The buffer is an even length, so this shouldn't happen, but just in case.
+        if self.encoder.has_pending() {
+            let mut buffer = Scratch([0; 1]);
+            let (_, written) = self.encoder.encode_chunk(&[], &mut buffer);
+            self.inner.write_all(&buffer[..written]).await?;
+        }
+
+        self.inner.flush().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_io::{Read, Write};
+
+    use super::{DecodeReader, EncodeWriter, ReadError, BUFFER_LEN};
+    use crate::DecodeError;
+
+    #[test]
+    fn round_trip() {
+        let bytes = crate::test_util::bytes(BUFFER_LEN * 3 + 1);
+
+        let mut encoded = std::vec![0; bytes.len() * 2];
+        let mut writer = EncodeWriter::new_upper(&mut encoded[..]);
+        writer.write_all(&bytes).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(encoded, crate::encode_upper(&bytes).into_bytes());
+
+        let mut decoded = std::vec![0; bytes.len()];
+        let mut reader = DecodeReader::new(&encoded[..]);
+        reader.read_exact(&mut decoded).unwrap();
+        assert_eq!(reader.read(&mut [0; 4]), Ok(0));
+        assert_eq!(decoded, bytes);
+    }
+
+    #[test]
+    fn decode_errors() {
+        let mut input = crate::encode([0; BUFFER_LEN]).into_bytes();
+        let offset = BUFFER_LEN + 3;
+        input[offset] = b'x';
+
+        let mut reader = DecodeReader::new(&input[..]);
+        let mut output = [0; BUFFER_LEN];
+
+        assert_eq!(reader.read_exact(&mut output[..offset / 2]), Ok(()));
+
+        let expected = ReadError::Decode(DecodeError::InvalidHex {
+            offset,
+            value: b'x',
+        });
+        assert_eq!(reader.read(&mut output), Err(expected));
+        assert_eq!(reader.read(&mut output), Err(expected));
+
+        let mut reader = DecodeReader::new(&b"decaf"[..]);
+        assert_eq!(reader.read(&mut output), Ok(2));
+        assert_eq!(
+            reader.read(&mut output),
+            Err(ReadError::Decode(DecodeError::OddLength))
+        );
+    }
+
+    #[cfg(feature = "embedded-io-async")]
+    #[test]
+    fn async_round_trip() {
+        use core::future::Future;
+        use core::task::{Context, Poll, Waker};
+
+        /// Slices never block, so polling once is enough.
+        fn block_on<F: Future>(future: F) -> F::Output {
+            let mut future = core::pin::pin!(future);
+            match future
+                .as_mut()
+                .poll(&mut Context::from_waker(Waker::noop()))
+            {
+                Poll::Ready(output) => output,
+                Poll::Pending => unreachable!(),
+            }
+        }
+
+        let mut encoded = [0; 6];
+        let mut writer = EncodeWriter::new(&mut encoded[..]);
+        block_on(embedded_io_async::Write::write_all(
+            &mut writer,
+            &[0xde, 0xca, 0xff],
+        ))
+        .unwrap();
+        assert_eq!(&encoded, b"decaff");
+
+        let mut decoded = [0; 3];
+        let mut reader = DecodeReader::new(&encoded[..]);
+        block_on(embedded_io_async::Read::read_exact(
+            &mut reader,
+            &mut decoded,
+        ))
+        .unwrap();
+        assert_eq!(decoded, [0xde, 0xca, 0xff]);
+    }
+}
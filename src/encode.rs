@@ -1,6 +1,10 @@
+#[cfg(not(feature = "safe"))]
+use core::mem::MaybeUninit;
+
 /// An error occured while encoding.
 ///
-/// Currently the only error that can occur while encoding is an output size mismatch when encoding to a slice.
+/// Currently the only error that can occur while encoding is an output size mismatch,
+/// when encoding to a slice (of the wrong length), or to a fixed capacity (that's too small).
 #[derive(Debug, Eq, PartialEq)]
 pub struct Error;
 
@@ -14,8 +18,43 @@ impl core::fmt::Display for Error {
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
+/// Allocating the output failed.
+///
+/// This is what the fallible allocation functions, such as [`try_encode`], return,
+/// and it's reported the same way as [`DecodeError::AllocationFailed`](crate::DecodeError::AllocationFailed).
+#[cfg(feature = "alloc")]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct AllocationError {
+    /// The length (in bytes) of the output that couldn't be allocated.
+    pub len: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for AllocationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "failed to allocate `{}` bytes for the output", self.len)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AllocationError {}
+
+#[cfg(feature = "alloc")]
+impl From<AllocationError> for crate::DecodeError {
+    fn from(err: AllocationError) -> Self {
+        crate::DecodeError::AllocationFailed { len: err.len }
+    }
+}
+
 mod fallback;
 
+pub(crate) use fallback::{byte_to_hex, encode_chunked, encode_delimited_with, FMT_BUFFER_LEN};
+
+mod options;
+
+pub use options::EncodeOptions;
+
 /// Encode `input` to a lowercase hex string.
 ///
 /// # Examples
@@ -48,13 +87,312 @@ pub fn encode_upper<T: AsRef<[u8]>>(input: T) -> alloc::string::String {
     fallback::encode::<true>(input.as_ref())
 }
 
+/// Encode `input` to a lowercase hex string, returning an error instead of aborting if allocating the output fails.
+///
+/// # Errors
+/// - [`AllocationError`] if the output couldn't be allocated.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::try_encode([0xde, 0xca, 0xff]).as_deref(), Ok("decaff"));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn try_encode<T: AsRef<[u8]>>(input: T) -> Result<alloc::string::String, AllocationError> {
+    fallback::try_encode::<false>(input.as_ref())
+}
+
+/// Encode `input` to an uppercase hex string, returning an error instead of aborting if allocating the output fails.
+///
+/// # Errors
+/// - [`AllocationError`] if the output couldn't be allocated.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::try_encode_upper([0xde, 0xca, 0xff]).as_deref(), Ok("DECAFF"));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn try_encode_upper<T: AsRef<[u8]>>(
+    input: T,
+) -> Result<alloc::string::String, AllocationError> {
+    fallback::try_encode::<true>(input.as_ref())
+}
+
+/// Encode `input` to a lowercase hex string, allocated at exactly the right size.
+///
+/// A `Box<str>` has no spare capacity to carry around, which makes it a good fit for results that are stored for a long time.
+///
+/// # Examples
+/// ```
+/// let hex: Box<str> = hector::encode_boxed([0xde, 0xca, 0xff]);
+///
+/// assert_eq!(&*hex, "decaff");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_boxed<T: AsRef<[u8]>>(input: T) -> alloc::boxed::Box<str> {
+    // `encode` allocates exactly the right size, so this doesn't reallocate.
+    fallback::encode::<false>(input.as_ref()).into_boxed_str()
+}
+
+/// Encode `input` to an uppercase hex string, allocated at exactly the right size.
+///
+/// A `Box<str>` has no spare capacity to carry around, which makes it a good fit for results that are stored for a long time.
+///
+/// # Examples
+/// ```
+/// assert_eq!(&*hector::encode_boxed_upper([0xde, 0xca, 0xff]), "DECAFF");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_boxed_upper<T: AsRef<[u8]>>(input: T) -> alloc::boxed::Box<str> {
+    // `encode_upper` allocates exactly the right size, so this doesn't reallocate.
+    fallback::encode::<true>(input.as_ref()).into_boxed_str()
+}
+
+/// Encode `input` to hex, as ascii bytes in a `Vec` allocated in `alloc`, at exactly the right size.
+#[cfg(feature = "allocator-api")]
+fn encode_in_impl<const UPPER: bool, A: core::alloc::Allocator>(
+    input: &[u8],
+    alloc: A,
+) -> alloc::vec::Vec<u8, A> {
+    let mut output = alloc::vec::Vec::with_capacity_in(input.len() * 2, alloc);
+
+    crate::output::for_each_piece(
+        |write| fallback::encode_chunked::<_, { fallback::FMT_BUFFER_LEN }, UPPER>(input, write),
+        |chunk| output.extend_from_slice(chunk.as_bytes()),
+    );
+
+    output
+}
+
+/// Encode `input` to lowercase hex, as ascii bytes allocated in `alloc` (like an arena).
+///
+/// `String` doesn't support custom allocators, so the output is a `Vec<u8, A>` (that's always valid UTF-8).
+/// This needs a nightly compiler (with the `allocator-api` feature).
+///
+/// # Examples
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::Global;
+///
+/// let hex = hector::encode_in([0xde, 0xca, 0xff], Global);
+///
+/// assert_eq!(hex, b"decaff");
+/// assert_eq!(hex.capacity(), 6);
+/// ```
+#[cfg(feature = "allocator-api")]
+#[must_use]
+pub fn encode_in<T: AsRef<[u8]>, A: core::alloc::Allocator>(
+    input: T,
+    alloc: A,
+) -> alloc::vec::Vec<u8, A> {
+    encode_in_impl::<false, A>(input.as_ref(), alloc)
+}
+
+/// Encode `input` to uppercase hex, as ascii bytes allocated in `alloc` (like an arena).
+///
+/// This needs a nightly compiler (with the `allocator-api` feature).
+///
+/// # Examples
+/// ```
+/// #![feature(allocator_api)]
+///
+/// assert_eq!(hector::encode_in_upper([0xde, 0xca, 0xff], std::alloc::Global), b"DECAFF");
+/// ```
+#[cfg(feature = "allocator-api")]
+#[must_use]
+pub fn encode_in_upper<T: AsRef<[u8]>, A: core::alloc::Allocator>(
+    input: T,
+    alloc: A,
+) -> alloc::vec::Vec<u8, A> {
+    encode_in_impl::<true, A>(input.as_ref(), alloc)
+}
+
+/// Encode `input` to a lowercase hex string, reusing its allocation if it has enough capacity.
+///
+/// If `input.capacity() >= input.len() * 2`, `input` is encoded in place (without allocating),
+/// otherwise this is the same as [`encode`].
+///
+/// # Examples
+/// ```
+/// let mut input = Vec::with_capacity(6);
+/// input.extend_from_slice(&[0xde, 0xca, 0xff]);
+///
+/// assert_eq!(hector::encode_vec(input), "decaff");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_vec(input: alloc::vec::Vec<u8>) -> alloc::string::String {
+    fallback::encode_vec::<false>(input)
+}
+
+/// Encode `input` to an uppercase hex string, reusing its allocation if it has enough capacity.
+///
+/// If `input.capacity() >= input.len() * 2`, `input` is encoded in place (without allocating),
+/// otherwise this is the same as [`encode_upper`].
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::encode_vec_upper(vec![0xde, 0xca, 0xff]), "DECAFF");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_vec_upper(input: alloc::vec::Vec<u8>) -> alloc::string::String {
+    fallback::encode_vec::<true>(input)
+}
+
+/// Append the lowercase hex encoding of `input` to `output`.
+///
+/// Unlike [`encode`], this reuses `output`'s allocation (growing it if needed),
+/// which avoids an allocation per call when building up a larger string.
+///
+/// # Examples
+/// ```
+/// let mut line = String::from("id=");
+///
+/// hector::encode_append([0xde, 0xca, 0xff], &mut line);
+/// line.push_str(" key=");
+/// hector::encode_append("Hello", &mut line);
+///
+/// assert_eq!(line, "id=decaff key=48656c6c6f");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode_append<T: AsRef<[u8]>>(input: T, output: &mut alloc::string::String) {
+    fallback::encode_append::<false>(input.as_ref(), output);
+}
+
+/// Append the uppercase hex encoding of `input` to `output`.
+///
+/// Unlike [`encode_upper`], this reuses `output`'s allocation (growing it if needed),
+/// which avoids an allocation per call when building up a larger string.
+///
+/// # Examples
+/// ```
+/// let mut line = String::from("id=");
+///
+/// hector::encode_append_upper([0xde, 0xca, 0xff], &mut line);
+///
+/// assert_eq!(line, "id=DECAFF");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode_append_upper<T: AsRef<[u8]>>(input: T, output: &mut alloc::string::String) {
+    fallback::encode_append::<true>(input.as_ref(), output);
+}
+
+/// Append the lowercase hex encoding of `input` to `output`, as ascii bytes.
+///
+/// Like [`encode_append`], but for byte buffers (for instance, a protocol frame under construction).
+///
+/// # Examples
+/// ```
+/// let mut frame = b"id: ".to_vec();
+///
+/// hector::encode_into_vec([0xde, 0xca, 0xff], &mut frame);
+/// frame.extend_from_slice(b"\r\n");
+///
+/// assert_eq!(frame, b"id: decaff\r\n");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode_into_vec<T: AsRef<[u8]>>(input: T, output: &mut alloc::vec::Vec<u8>) {
+    fallback::encode_append_vec::<false>(input.as_ref(), output);
+}
+
+/// Append the uppercase hex encoding of `input` to `output`, as ascii bytes.
+///
+/// Like [`encode_append_upper`], but for byte buffers (for instance, a protocol frame under construction).
+///
+/// # Examples
+/// ```
+/// let mut frame = b"id: ".to_vec();
+///
+/// hector::encode_into_vec_upper([0xde, 0xca, 0xff], &mut frame);
+///
+/// assert_eq!(frame, b"id: DECAFF");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode_into_vec_upper<T: AsRef<[u8]>>(input: T, output: &mut alloc::vec::Vec<u8>) {
+    fallback::encode_append_vec::<true>(input.as_ref(), output);
+}
+
+/// Encode `input` to a lowercase hex string, starting with `0x`.
+///
+/// This allocates once, rather than prefixing the output of [`encode`] after the fact.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::encode_prefixed([0xde, 0xca, 0xff]), "0xdecaff");
+/// assert_eq!(hector::encode_prefixed([]), "0x");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_prefixed<T: AsRef<[u8]>>(input: T) -> alloc::string::String {
+    fallback::encode_prefixed::<false>(input.as_ref())
+}
+
+/// Encode `input` to an uppercase hex string, starting with `0x`.
+///
+/// Only the hex is uppercase, the prefix is always a lowercase `0x`.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::encode_prefixed_upper([0xde, 0xca, 0xff]), "0xDECAFF");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_prefixed_upper<T: AsRef<[u8]>>(input: T) -> alloc::string::String {
+    fallback::encode_prefixed::<true>(input.as_ref())
+}
+
+/// Encode `input` to a lowercase hex string starting with `0x`, in `output`.
+///
+/// For convenience, this returns a [`&str`](str), backed by `output`.
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 8];
+///
+/// assert_eq!(hector::encode_prefixed_to_slice([0xde, 0xca, 0xff], &mut storage), Ok("0xdecaff"));
+/// ```
+///
+/// # Errors
+/// - [`EncodeError`] if the output isn't exactly `2 + input.len() * 2` bytes.
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn encode_prefixed_to_slice<T: AsRef<[u8]>>(
+    input: T,
+    output: &mut [u8],
+) -> Result<&str, Error> {
+    fallback::encode_prefixed_to_slice::<false>(input.as_ref(), output)
+}
+
+/// Encode `input` to an uppercase hex string starting with `0x`, in `output`.
+///
+/// For convenience, this returns a [`&str`](str), backed by `output`.
+/// Only the hex is uppercase, the prefix is always a lowercase `0x`.
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 8];
+///
+/// assert_eq!(hector::encode_prefixed_to_slice_upper([0xde, 0xca, 0xff], &mut storage), Ok("0xDECAFF"));
+/// ```
+///
+/// # Errors
+/// - [`EncodeError`] if the output isn't exactly `2 + input.len() * 2` bytes.
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn encode_prefixed_to_slice_upper<T: AsRef<[u8]>>(
+    input: T,
+    output: &mut [u8],
+) -> Result<&str, Error> {
+    fallback::encode_prefixed_to_slice::<true>(input.as_ref(), output)
+}
+
 /// Encode `input` to a lowercase hex string.
 ///
 /// For convenience, this returns a [`&str`](str), backed by `output`.
 ///
 /// # Examples
 /// ```
-//
 /// let mut storage = [0; 6];
 ///
 /// assert_eq!(hector::encode_to_slice([0xde, 0xca, 0xff], &mut storage), Ok("decaff"));
@@ -71,6 +409,7 @@ pub fn encode_upper<T: AsRef<[u8]>>(input: T) -> alloc::string::String {
 ///
 /// # Errors
 /// - [`EncodeError`] if the output is too big or too small.
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
 pub fn encode_to_slice<T: AsRef<[u8]>>(input: T, output: &mut [u8]) -> Result<&str, Error> {
     fallback::encode_to_slice::<false>(input.as_ref(), output)
 }
@@ -81,7 +420,6 @@ pub fn encode_to_slice<T: AsRef<[u8]>>(input: T, output: &mut [u8]) -> Result<&s
 ///
 /// # Examples
 /// ```
-//
 /// let mut storage = [0; 6];
 ///
 /// assert_eq!(hector::encode_to_slice_upper([0xde, 0xca, 0xff], &mut storage), Ok("DECAFF"));
@@ -98,68 +436,1167 @@ pub fn encode_to_slice<T: AsRef<[u8]>>(input: T, output: &mut [u8]) -> Result<&s
 ///
 /// # Errors
 /// - [`EncodeError`] if the output is too big or too small.
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
 pub fn encode_to_slice_upper<T: AsRef<[u8]>>(input: T, output: &mut [u8]) -> Result<&str, Error> {
     fallback::encode_to_slice::<true>(input.as_ref(), output)
 }
 
-/// Encode `input` to a hex string.
+/// Encode `inputs` to a lowercase hex string, as if they were concatenated into one input.
 ///
-/// For convenience, this returns a  [`&str`](str), backed by `output`.
+/// This avoids having to concatenate scattered buffers (for instance, the segments of a message) just to encode them.
 ///
 /// # Examples
 /// ```
-//
-/// let mut storage = [0; 6];
+/// let segments: [&[u8]; 3] = [&[0xde, 0xca], &[], &[0xff]];
 ///
-/// assert_eq!(hector::encode_to_array(&[0xde, 0xca, 0xff], &mut storage), "decaff");
-/// assert_eq!(&storage, b"decaff");
+/// assert_eq!(hector::encode_vectored(&segments), "decaff");
 /// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_vectored<T: AsRef<[u8]>>(inputs: &[T]) -> alloc::string::String {
+    fallback::encode_vectored::<T, false>(inputs)
+}
+
+/// Encode `inputs` to an uppercase hex string, as if they were concatenated into one input.
 ///
+/// This avoids having to concatenate scattered buffers (for instance, the segments of a message) just to encode them.
+///
+/// # Examples
 /// ```
-/// let mut storage = [0; 26];
+/// assert_eq!(hector::encode_vectored_upper(&["Hello", ", ", "world!"]), "48656C6C6F2C20776F726C6421");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_vectored_upper<T: AsRef<[u8]>>(inputs: &[T]) -> alloc::string::String {
+    fallback::encode_vectored::<T, true>(inputs)
+}
+
+/// Encode `inputs` to a lowercase hex string, as if they were concatenated into one input.
 ///
-/// assert_eq!(hector::encode_to_array(b"Hello, world!", &mut storage), "48656c6c6f2c20776f726c6421");
-/// assert_eq!(&storage, b"48656c6c6f2c20776f726c6421");
+/// For convenience, this returns a [`&str`](str), backed by `output`.
+///
+/// # Examples
 /// ```
+/// let segments: [&[u8]; 2] = [&[0xde, 0xca], &[0xff]];
+/// let mut storage = [0; 6];
 ///
-/// # Panics
-/// Due to limitations in const generics,
-/// this function currently panics if `N != 2 * M`,
-/// once `const_generic_exprs` is stable, `M` will be removed, and this will become a compiler error.
-pub fn encode_to_array<'a, const N: usize, const M: usize>(
-    input: &[u8; N],
-    output: &'a mut [u8; M],
-) -> &'a str {
-    fallback::encode_array::<N, M, false>(input, output)
+/// assert_eq!(hector::encode_vectored_to_slice(&segments, &mut storage), Ok("decaff"));
+/// ```
+///
+/// # Errors
+/// - [`EncodeError`] if the output isn't exactly twice the combined length of `inputs`.
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn encode_vectored_to_slice<'a, T: AsRef<[u8]>>(
+    inputs: &[T],
+    output: &'a mut [u8],
+) -> Result<&'a str, Error> {
+    fallback::encode_vectored_to_slice::<T, false>(inputs, output)
 }
 
-/// Encode `input` to an uppercase hex string.
+/// Encode `inputs` to an uppercase hex string, as if they were concatenated into one input.
 ///
-/// For convenience, this returns a `&str`, backed by `output`.
+/// For convenience, this returns a [`&str`](str), backed by `output`.
 ///
 /// # Examples
 /// ```
-//
+/// let segments: [&[u8]; 2] = [&[0xde, 0xca], &[0xff]];
 /// let mut storage = [0; 6];
 ///
-/// assert_eq!(hector::encode_to_array_upper(&[0xde, 0xca, 0xff], &mut storage), "DECAFF");
-/// assert_eq!(&storage, b"DECAFF");
+/// assert_eq!(hector::encode_vectored_to_slice_upper(&segments, &mut storage), Ok("DECAFF"));
 /// ```
 ///
+/// # Errors
+/// - [`EncodeError`] if the output isn't exactly twice the combined length of `inputs`.
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn encode_vectored_to_slice_upper<'a, T: AsRef<[u8]>>(
+    inputs: &[T],
+    output: &'a mut [u8],
+) -> Result<&'a str, Error> {
+    fallback::encode_vectored_to_slice::<T, true>(inputs, output)
+}
+
+/// Write the lowercase hex encoding of `input` to `output`.
+///
+/// This encodes a small chunk at a time on the stack, so it neither allocates nor needs an output buffer sized in advance,
+/// which makes it a good fit for [`Display`](core::fmt::Display) implementations.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
 /// ```
-/// let mut storage = [0; 26];
+/// struct Id([u8; 3]);
 ///
-/// assert_eq!(hector::encode_to_array_upper(b"Hello, world!", &mut storage), "48656C6C6F2C20776F726C6421");
-/// assert_eq!(&storage, b"48656C6C6F2C20776F726C6421");
+/// impl core::fmt::Display for Id {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         f.write_str("id:")?;
+///         hector::encode_to_fmt(self.0, f)
+///     }
+/// }
+///
+/// assert_eq!(Id([0xde, 0xca, 0xff]).to_string(), "id:decaff");
 /// ```
+pub fn encode_to_fmt<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> core::fmt::Result {
+    fallback::encode_chunked::<_, { fallback::FMT_BUFFER_LEN }, false>(input.as_ref(), |chunk| {
+        output.write_str(chunk)
+    })
+}
+
+/// Write the uppercase hex encoding of `input` to `output`.
 ///
-/// # Panics
-/// Due to limitations in const generics,
-/// this function currently panics if `N != 2 * M`,
-/// once `const_generic_exprs` is stable, `M` will be removed, and this will become a compiler error.
-pub fn encode_to_array_upper<'a, const N: usize, const M: usize>(
-    input: &[u8; N],
-    output: &'a mut [u8; M],
-) -> &'a str {
-    fallback::encode_array::<N, M, true>(input, output)
+/// This encodes a small chunk at a time on the stack, so it neither allocates nor needs an output buffer sized in advance,
+/// which makes it a good fit for [`Display`](core::fmt::Display) implementations.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// let mut output = String::from("id:");
+///
+/// hector::encode_to_fmt_upper([0xde, 0xca, 0xff], &mut output).unwrap();
+///
+/// assert_eq!(output, "id:DECAFF");
+/// ```
+pub fn encode_to_fmt_upper<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> core::fmt::Result {
+    fallback::encode_chunked::<_, { fallback::FMT_BUFFER_LEN }, true>(input.as_ref(), |chunk| {
+        output.write_str(chunk)
+    })
+}
+
+/// Encode `input` a chunk at a time, passing each chunk to `extend` (for the `encode_extend*` functions).
+fn encode_extend_impl<const UPPER: bool>(input: &[u8], extend: impl FnMut(&str)) {
+    crate::output::for_each_piece(
+        |write| fallback::encode_chunked::<_, { fallback::FMT_BUFFER_LEN }, UPPER>(input, write),
+        extend,
+    );
+}
+
+/// Encode `input` to lowercase hex, extending `output` with the ascii bytes.
+///
+/// This works with any byte collection (like a `VecDeque<u8>` used as a ring buffer), without an intermediate buffer.
+///
+/// # Examples
+/// ```
+/// use std::collections::VecDeque;
+///
+/// let mut ring = VecDeque::from(*b"id=");
+/// hector::encode_extend([0xde, 0xca, 0xff], &mut ring);
+///
+/// assert!(ring.iter().eq(b"id=decaff"));
+/// ```
+pub fn encode_extend<T: AsRef<[u8]>, E: Extend<u8> + ?Sized>(input: T, output: &mut E) {
+    encode_extend_impl::<false>(input.as_ref(), |chunk| output.extend(chunk.bytes()));
+}
+
+/// Encode `input` to uppercase hex, extending `output` with the ascii bytes.
+///
+/// # Examples
+/// ```
+/// let mut output = b"ID=".to_vec();
+/// hector::encode_extend_upper([0xde, 0xca, 0xff], &mut output);
+///
+/// assert_eq!(output, b"ID=DECAFF");
+/// ```
+pub fn encode_extend_upper<T: AsRef<[u8]>, E: Extend<u8> + ?Sized>(input: T, output: &mut E) {
+    encode_extend_impl::<true>(input.as_ref(), |chunk| output.extend(chunk.bytes()));
+}
+
+/// Encode `input` to lowercase hex, extending `output` with the [`char`]s.
+///
+/// # Examples
+/// ```
+/// let mut output = String::from("id=");
+/// hector::encode_extend_chars([0xde, 0xca, 0xff], &mut output);
+///
+/// assert_eq!(output, "id=decaff");
+/// ```
+pub fn encode_extend_chars<T: AsRef<[u8]>, E: Extend<char> + ?Sized>(input: T, output: &mut E) {
+    encode_extend_impl::<false>(input.as_ref(), |chunk| output.extend(chunk.chars()));
+}
+
+/// Encode `input` to uppercase hex, extending `output` with the [`char`]s.
+///
+/// # Examples
+/// ```
+/// let mut output = String::from("ID=");
+/// hector::encode_extend_chars_upper([0xde, 0xca, 0xff], &mut output);
+///
+/// assert_eq!(output, "ID=DECAFF");
+/// ```
+pub fn encode_extend_chars_upper<T: AsRef<[u8]>, E: Extend<char> + ?Sized>(
+    input: T,
+    output: &mut E,
+) {
+    encode_extend_impl::<true>(input.as_ref(), |chunk| output.extend(chunk.chars()));
+}
+
+/// Encode `input` to a lowercase hex string, with `separator` between each byte.
+///
+/// The output is allocated at exactly the right size up front.
+/// For more control (grouping bytes, prefixes, line wrapping), see [`EncodeOptions`].
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::encode_delimited([0xde, 0xca, 0xff], ':'), "de:ca:ff");
+/// assert_eq!(hector::encode_delimited([0xde, 0xca, 0xff], '·'), "de·ca·ff");
+/// assert_eq!(hector::encode_delimited([0xde], ':'), "de");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_delimited<T: AsRef<[u8]>>(input: T, separator: char) -> alloc::string::String {
+    fallback::encode_delimited::<false>(input.as_ref(), separator)
+}
+
+/// Encode `input` to an uppercase hex string, with `separator` between each byte.
+///
+/// The output is allocated at exactly the right size up front.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::encode_delimited_upper([0xde, 0xca, 0xff], ' '), "DE CA FF");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_delimited_upper<T: AsRef<[u8]>>(input: T, separator: char) -> alloc::string::String {
+    fallback::encode_delimited::<true>(input.as_ref(), separator)
+}
+
+/// Write the lowercase hex encoding of `input` to `output`, with `separator` between each byte.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// struct Fingerprint([u8; 4]);
+///
+/// impl core::fmt::Display for Fingerprint {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         hector::encode_delimited_to_fmt(self.0, ':', f)
+///     }
+/// }
+///
+/// assert_eq!(Fingerprint([0xde, 0xad, 0xbe, 0xef]).to_string(), "de:ad:be:ef");
+/// ```
+pub fn encode_delimited_to_fmt<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    separator: char,
+    output: &mut W,
+) -> core::fmt::Result {
+    fallback::encode_delimited_with::<_, false>(input.as_ref(), separator, 1, |chunk| {
+        output.write_str(chunk)
+    })
+}
+
+/// Write the uppercase hex encoding of `input` to `output`, with `separator` between each byte.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// let mut output = String::from("mac: ");
+///
+/// hector::encode_delimited_to_fmt_upper([0x00, 0x1b, 0x63], '-', &mut output).unwrap();
+///
+/// assert_eq!(output, "mac: 00-1B-63");
+/// ```
+pub fn encode_delimited_to_fmt_upper<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    separator: char,
+    output: &mut W,
+) -> core::fmt::Result {
+    fallback::encode_delimited_with::<_, true>(input.as_ref(), separator, 1, |chunk| {
+        output.write_str(chunk)
+    })
+}
+
+/// Encode `input` to a lowercase hex string, in reverse byte order.
+///
+/// This is how little-endian hashes are usually shown, like Bitcoin's transaction and block IDs,
+/// and it's done in a single pass, without reversing a copy of `input` first.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::encode_reverse([0xde, 0xca, 0xff]), "ffcade");
+///
+/// let hash = [0x3b, 0xa3, 0xed, 0xfd, 0x7a, 0x7b, 0x12, 0xb2, 0x7a, 0xc7];
+/// assert_eq!(hector::encode_reverse(hash), "c77ab2127b7afdeda33b");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_reverse<T: AsRef<[u8]>>(input: T) -> alloc::string::String {
+    fallback::encode_reverse::<false>(input.as_ref())
+}
+
+/// Encode `input` to an uppercase hex string, in reverse byte order.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::encode_reverse_upper([0xde, 0xca, 0xff]), "FFCADE");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_reverse_upper<T: AsRef<[u8]>>(input: T) -> alloc::string::String {
+    fallback::encode_reverse::<true>(input.as_ref())
+}
+
+/// Encode `input` to a lowercase hex string in reverse byte order, in `output`.
+///
+/// For convenience, this returns a [`&str`](str), backed by `output`.
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 6];
+///
+/// assert_eq!(hector::encode_reverse_to_slice([0xde, 0xca, 0xff], &mut storage), Ok("ffcade"));
+/// ```
+///
+/// # Errors
+/// - [`EncodeError`] if the output isn't exactly `input.len() * 2` bytes.
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn encode_reverse_to_slice<T: AsRef<[u8]>>(input: T, output: &mut [u8]) -> Result<&str, Error> {
+    fallback::encode_reverse_to_slice::<false>(input.as_ref(), output)
+}
+
+/// Encode `input` to an uppercase hex string in reverse byte order, in `output`.
+///
+/// For convenience, this returns a [`&str`](str), backed by `output`.
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 6];
+///
+/// assert_eq!(hector::encode_reverse_to_slice_upper([0xde, 0xca, 0xff], &mut storage), Ok("FFCADE"));
+/// ```
+///
+/// # Errors
+/// - [`EncodeError`] if the output isn't exactly `input.len() * 2` bytes.
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn encode_reverse_to_slice_upper<T: AsRef<[u8]>>(
+    input: T,
+    output: &mut [u8],
+) -> Result<&str, Error> {
+    fallback::encode_reverse_to_slice::<true>(input.as_ref(), output)
+}
+
+/// Write the lowercase hex encoding of `input` to `output`, in reverse byte order.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// struct Txid([u8; 4]);
+///
+/// impl core::fmt::Display for Txid {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         hector::encode_reverse_to_fmt(self.0, f)
+///     }
+/// }
+///
+/// assert_eq!(Txid([0x01, 0x02, 0x03, 0x04]).to_string(), "04030201");
+/// ```
+pub fn encode_reverse_to_fmt<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> core::fmt::Result {
+    fallback::encode_reverse_with::<_, false>(input.as_ref(), |chunk| output.write_str(chunk))
+}
+
+/// Write the uppercase hex encoding of `input` to `output`, in reverse byte order.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// let mut output = String::from("txid: ");
+///
+/// hector::encode_reverse_to_fmt_upper([0xde, 0xca, 0xff], &mut output).unwrap();
+///
+/// assert_eq!(output, "txid: FFCADE");
+/// ```
+pub fn encode_reverse_to_fmt_upper<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> core::fmt::Result {
+    fallback::encode_reverse_with::<_, true>(input.as_ref(), |chunk| output.write_str(chunk))
+}
+
+/// Encode `input` with the nibbles of each byte swapped, as in the semi-octet (TBCD) strings of GSM and SIM cards.
+///
+/// Each byte is written low nibble first, so `[0x21, 0x43, 0x65]` is `123456`.
+/// The output is allocated at exactly the right size up front.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::encode_swapped([0x21, 0x43, 0x65]), "123456");
+/// assert_eq!(hector::encode_swapped([0x21, 0xf3]), "123f");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_swapped<T: AsRef<[u8]>>(input: T) -> alloc::string::String {
+    fallback::encode_swapped::<false, false>(input.as_ref())
+}
+
+/// Encode `input` with the nibbles of each byte swapped, like [`encode_swapped`], in uppercase.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::encode_swapped_upper([0x21, 0xf3]), "123F");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_swapped_upper<T: AsRef<[u8]>>(input: T) -> alloc::string::String {
+    fallback::encode_swapped::<false, true>(input.as_ref())
+}
+
+/// Encode `input` with the nibbles of each byte swapped, leaving off a filler `f` at the end.
+///
+/// Semi-octet strings with an odd number of digits (like phone numbers in GSM TS 23.040)
+/// fill the last nibble with `f`, which this drops, so `[0x21, 0xf3]` is `123`.
+/// An `f` anywhere else is kept.
+///
+/// # Examples
+/// ```
+/// // The digits of the address `+31641600986`.
+/// assert_eq!(hector::encode_swapped_filler([0x13, 0x46, 0x61, 0x00, 0x89, 0xf6]), "31641600986");
+/// assert_eq!(hector::encode_swapped_filler([0x21, 0x43]), "1234");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_swapped_filler<T: AsRef<[u8]>>(input: T) -> alloc::string::String {
+    fallback::encode_swapped::<true, false>(input.as_ref())
+}
+
+/// Encode `input` with the nibbles of each byte swapped, leaving off a filler `F` at the end, like [`encode_swapped_filler`], in uppercase.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::encode_swapped_filler_upper([0xba, 0xfc]), "ABC");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_swapped_filler_upper<T: AsRef<[u8]>>(input: T) -> alloc::string::String {
+    fallback::encode_swapped::<true, true>(input.as_ref())
+}
+
+/// Write `input` to `output` with the nibbles of each byte swapped, see [`encode_swapped`].
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// let mut output = String::from("ICCID: ");
+///
+/// hector::encode_swapped_to_fmt([0x98, 0x10, 0x14], &mut output).unwrap();
+///
+/// assert_eq!(output, "ICCID: 890141");
+/// ```
+pub fn encode_swapped_to_fmt<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> core::fmt::Result {
+    fallback::encode_swapped_with::<_, false, false>(input.as_ref(), |chunk| {
+        output.write_str(chunk)
+    })
+}
+
+/// Write `input` to `output` with the nibbles of each byte swapped, like [`encode_swapped_to_fmt`], in uppercase.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// let mut output = String::from("IMSI: ");
+///
+/// hector::encode_swapped_to_fmt_upper([0x21, 0xf3], &mut output).unwrap();
+///
+/// assert_eq!(output, "IMSI: 123F");
+/// ```
+pub fn encode_swapped_to_fmt_upper<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> core::fmt::Result {
+    fallback::encode_swapped_with::<_, false, true>(input.as_ref(), |chunk| output.write_str(chunk))
+}
+
+/// Write `input` to `output` with the nibbles of each byte swapped, leaving off a filler `f` at the end, see [`encode_swapped_filler`].
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// let mut output = String::from("tel: +");
+///
+/// hector::encode_swapped_filler_to_fmt([0x13, 0x46, 0x61, 0x00, 0x89, 0xf6], &mut output).unwrap();
+///
+/// assert_eq!(output, "tel: +31641600986");
+/// ```
+pub fn encode_swapped_filler_to_fmt<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> core::fmt::Result {
+    fallback::encode_swapped_with::<_, true, false>(input.as_ref(), |chunk| output.write_str(chunk))
+}
+
+/// Write `input` to `output` with the nibbles of each byte swapped, like [`encode_swapped_filler_to_fmt`], in uppercase.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// let mut output = String::new();
+///
+/// hector::encode_swapped_filler_to_fmt_upper([0xba, 0xfc], &mut output).unwrap();
+///
+/// assert_eq!(output, "ABC");
+/// ```
+pub fn encode_swapped_filler_to_fmt_upper<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> core::fmt::Result {
+    fallback::encode_swapped_with::<_, true, true>(input.as_ref(), |chunk| output.write_str(chunk))
+}
+
+/// Encode the big-endian number in `input` to a lowercase hex string, without leading zeros.
+///
+/// Zero (including empty input) is `0`, so with `0x` in front,
+/// this is a quantity in Ethereum's JSON-RPC (see [`decode_trimmed`](crate::decode_trimmed) for parsing them).
+/// The output is allocated at exactly the right size up front.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::encode_trimmed([0x00, 0x04, 0x00]), "400");
+/// assert_eq!(hector::encode_trimmed([0x00, 0x00]), "0");
+///
+/// let balance: [u8; 32] = core::array::from_fn(|it| if it == 31 { 0x41 } else { 0 });
+/// assert_eq!(format!("0x{}", hector::encode_trimmed(balance)), "0x41");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_trimmed<T: AsRef<[u8]>>(input: T) -> alloc::string::String {
+    fallback::encode_trimmed::<false>(input.as_ref())
+}
+
+/// Encode the big-endian number in `input` to an uppercase hex string, without leading zeros.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::encode_trimmed_upper([0x00, 0x0a, 0xbc]), "ABC");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_trimmed_upper<T: AsRef<[u8]>>(input: T) -> alloc::string::String {
+    fallback::encode_trimmed::<true>(input.as_ref())
+}
+
+/// Write the big-endian number in `input` to `output` as lowercase hex, without leading zeros, see [`encode_trimmed`].
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// struct Quantity([u8; 8]);
+///
+/// impl core::fmt::Display for Quantity {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         f.write_str("0x")?;
+///         hector::encode_trimmed_to_fmt(self.0, f)
+///     }
+/// }
+///
+/// assert_eq!(Quantity(1024_u64.to_be_bytes()).to_string(), "0x400");
+/// assert_eq!(Quantity([0; 8]).to_string(), "0x0");
+/// ```
+pub fn encode_trimmed_to_fmt<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> core::fmt::Result {
+    fallback::encode_trimmed_with::<_, false>(input.as_ref(), |chunk| output.write_str(chunk))
+}
+
+/// Encode `input` as `\xNN` escapes (like in Rust and Python byte strings), `[0xde, 0xca, 0xff]` is `\xde\xca\xff`.
+///
+/// The output is allocated at exactly the right size up front.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::encode_escaped([0xde, 0xca, 0xff]), r"\xde\xca\xff");
+/// assert_eq!(hector::encode_escaped("hi"), r"\x68\x69");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_escaped<T: AsRef<[u8]>>(input: T) -> alloc::string::String {
+    fallback::encode_escaped::<false>(input.as_ref())
+}
+
+/// Encode `input` as `\xNN` escapes, except for printable ascii, which is passed through as is.
+///
+/// `\`, `"`, and `'` are still escaped, so the output can go in any string (or byte string) literal.
+/// The output is allocated at exactly the right size up front.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::encode_escaped_ascii(b"GET /\r\n\xff"), r"GET /\x0d\x0a\xff");
+/// assert_eq!(hector::encode_escaped_ascii(br#"say "hi\""#), r"say \x22hi\x5c\x22");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_escaped_ascii<T: AsRef<[u8]>>(input: T) -> alloc::string::String {
+    fallback::encode_escaped::<true>(input.as_ref())
+}
+
+/// Write `input` to `output` as `\xNN` escapes.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// let mut output = String::from("let shellcode = b\"");
+///
+/// hector::encode_escaped_to_fmt([0x31, 0xc0, 0xc3], &mut output).unwrap();
+/// output.push_str("\";");
+///
+/// assert_eq!(output, r#"let shellcode = b"\x31\xc0\xc3";"#);
+/// ```
+pub fn encode_escaped_to_fmt<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> core::fmt::Result {
+    fallback::encode_escaped_with::<_, false>(input.as_ref(), |chunk| output.write_str(chunk))
+}
+
+/// Write `input` to `output` as `\xNN` escapes, except for printable ascii, which is passed through as is.
+///
+/// `\`, `"`, and `'` are still escaped, like [`encode_escaped_ascii`].
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// struct Packet<'a>(&'a [u8]);
+///
+/// impl core::fmt::Debug for Packet<'_> {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         f.write_str("b\"")?;
+///         hector::encode_escaped_ascii_to_fmt(self.0, f)?;
+///         f.write_str("\"")
+///     }
+/// }
+///
+/// assert_eq!(format!("{:?}", Packet(b"OK\x00")), r#"b"OK\x00""#);
+/// ```
+pub fn encode_escaped_ascii_to_fmt<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> core::fmt::Result {
+    fallback::encode_escaped_with::<_, true>(input.as_ref(), |chunk| output.write_str(chunk))
+}
+
+/// Write the lowercase hex encoding of `input` to `output`.
+///
+/// This encodes a chunk at a time on the stack, so even very large inputs can be streamed out without
+/// materializing the whole hex string.
+///
+/// Since each chunk is written separately, `output` should be buffered if writes to it are expensive.
+///
+/// # Errors
+/// - Any [`io::Error`](std::io::Error) from writing to `output`.
+///
+/// # Examples
+/// ```
+/// let mut output = Vec::new();
+///
+/// hector::encode_to_writer([0xde, 0xca, 0xff], &mut output).unwrap();
+///
+/// assert_eq!(output, b"decaff");
+/// ```
+#[cfg(feature = "std")]
+pub fn encode_to_writer<T: AsRef<[u8]>, W: std::io::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> std::io::Result<()> {
+    fallback::encode_chunked::<_, { fallback::IO_BUFFER_LEN }, false>(input.as_ref(), |chunk| {
+        output.write_all(chunk.as_bytes())
+    })
+}
+
+/// Write the uppercase hex encoding of `input` to `output`.
+///
+/// This encodes a chunk at a time on the stack, so even very large inputs can be streamed out without
+/// materializing the whole hex string.
+///
+/// Since each chunk is written separately, `output` should be buffered if writes to it are expensive.
+///
+/// # Errors
+/// - Any [`io::Error`](std::io::Error) from writing to `output`.
+///
+/// # Examples
+/// ```
+/// let mut output = Vec::new();
+///
+/// hector::encode_to_writer_upper([0xde, 0xca, 0xff], &mut output).unwrap();
+///
+/// assert_eq!(output, b"DECAFF");
+/// ```
+#[cfg(feature = "std")]
+pub fn encode_to_writer_upper<T: AsRef<[u8]>, W: std::io::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> std::io::Result<()> {
+    fallback::encode_chunked::<_, { fallback::IO_BUFFER_LEN }, true>(input.as_ref(), |chunk| {
+        output.write_all(chunk.as_bytes())
+    })
+}
+
+/// Encode `input` to a lowercase hex string, with a fixed capacity of `CAP` on the stack (without `alloc`).
+///
+/// # Errors
+/// - [`EncodeError`] if `input.len() * 2` is more than `CAP`.
+///
+/// # Examples
+/// ```
+/// let hex: arrayvec::ArrayString<8> = hector::encode_arrayvec([0xde, 0xca, 0xff])?;
+/// assert_eq!(hex.as_str(), "decaff");
+///
+/// assert!(hector::encode_arrayvec::<4>([0xde, 0xca, 0xff]).is_err());
+/// # Ok::<(), hector::EncodeError>(())
+/// ```
+#[cfg(feature = "arrayvec")]
+pub fn encode_arrayvec<const CAP: usize>(
+    input: impl AsRef<[u8]>,
+) -> Result<arrayvec::ArrayString<CAP>, Error> {
+    encode_arrayvec_impl::<CAP, false>(input.as_ref())
+}
+
+/// Encode `input` to an uppercase hex string, with a fixed capacity of `CAP` on the stack (without `alloc`).
+///
+/// # Errors
+/// - [`EncodeError`] if `input.len() * 2` is more than `CAP`.
+///
+/// # Examples
+/// ```
+/// let hex: arrayvec::ArrayString<6> = hector::encode_arrayvec_upper([0xde, 0xca, 0xff])?;
+/// assert_eq!(hex.as_str(), "DECAFF");
+/// # Ok::<(), hector::EncodeError>(())
+/// ```
+#[cfg(feature = "arrayvec")]
+pub fn encode_arrayvec_upper<const CAP: usize>(
+    input: impl AsRef<[u8]>,
+) -> Result<arrayvec::ArrayString<CAP>, Error> {
+    encode_arrayvec_impl::<CAP, true>(input.as_ref())
+}
+
+#[cfg(feature = "arrayvec")]
+fn encode_arrayvec_impl<const CAP: usize, const UPPER: bool>(
+    input: &[u8],
+) -> Result<arrayvec::ArrayString<CAP>, Error> {
+    let mut output = arrayvec::ArrayString::new();
+    crate::sink::encode_into_impl::<UPPER, _>(input, &mut output)?;
+
+    Ok(output)
+}
+
+/// Encode `input` to a lowercase hex string, with a fixed capacity of `N` (without `alloc`).
+///
+/// # Errors
+/// - [`EncodeError`] if `input.len() * 2` is more than `N`.
+///
+/// # Examples
+/// ```
+/// let hex: heapless::String<8> = hector::encode_heapless([0xde, 0xca, 0xff])?;
+/// assert_eq!(hex, "decaff");
+///
+/// assert!(hector::encode_heapless::<4>([0xde, 0xca, 0xff]).is_err());
+/// # Ok::<(), hector::EncodeError>(())
+/// ```
+#[cfg(feature = "heapless")]
+pub fn encode_heapless<const N: usize>(
+    input: impl AsRef<[u8]>,
+) -> Result<heapless::String<N>, Error> {
+    encode_heapless_impl::<N, false>(input.as_ref())
+}
+
+/// Encode `input` to an uppercase hex string, with a fixed capacity of `N` (without `alloc`).
+///
+/// # Errors
+/// - [`EncodeError`] if `input.len() * 2` is more than `N`.
+///
+/// # Examples
+/// ```
+/// let hex: heapless::String<6> = hector::encode_heapless_upper([0xde, 0xca, 0xff])?;
+/// assert_eq!(hex, "DECAFF");
+/// # Ok::<(), hector::EncodeError>(())
+/// ```
+#[cfg(feature = "heapless")]
+pub fn encode_heapless_upper<const N: usize>(
+    input: impl AsRef<[u8]>,
+) -> Result<heapless::String<N>, Error> {
+    encode_heapless_impl::<N, true>(input.as_ref())
+}
+
+#[cfg(feature = "heapless")]
+fn encode_heapless_impl<const N: usize, const UPPER: bool>(
+    input: &[u8],
+) -> Result<heapless::String<N>, Error> {
+    let mut output = heapless::String::new();
+    crate::sink::encode_into_impl::<UPPER, _>(input, &mut output)?;
+
+    Ok(output)
+}
+
+/// Encode `input` to a lowercase hex string, in possibly uninitialized memory.
+///
+/// This is useful for encoding directly into memory that hasn't been initialized yet,
+/// such as the spare capacity of a `Vec`, without having to zero it first.
+///
+/// For convenience, this returns a [`&str`](str), backed by `output`.
+///
+/// # Examples
+/// ```
+/// use core::mem::MaybeUninit;
+///
+/// let mut storage = [MaybeUninit::uninit(); 6];
+///
+/// assert_eq!(hector::encode_to_uninit_slice([0xde, 0xca, 0xff], &mut storage), Ok("decaff"));
+/// ```
+///
+/// ```
+/// let mut output = Vec::with_capacity(26);
+///
+/// let encoded = hector::encode_to_uninit_slice("Hello, world!", &mut output.spare_capacity_mut()[..26])?;
+/// assert_eq!(encoded, "48656c6c6f2c20776f726c6421");
+///
+/// // Safety: `encode_to_uninit_slice` succeeded, so it initialized all 26 bytes.
+/// unsafe { output.set_len(26) };
+/// assert_eq!(output, b"48656c6c6f2c20776f726c6421");
+/// # Ok::<(), hector::EncodeError>(())
+/// ```
+///
+/// # Errors
+/// - [`EncodeError`] if the output is too big or too small.
+#[cfg(not(feature = "safe"))]
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn encode_to_uninit_slice<T: AsRef<[u8]>>(
+    input: T,
+    output: &mut [MaybeUninit<u8>],
+) -> Result<&str, Error> {
+    fallback::encode_to_uninit_slice::<false>(input.as_ref(), output)
+}
+
+/// Encode `input` to an uppercase hex string, in possibly uninitialized memory.
+///
+/// This is useful for encoding directly into memory that hasn't been initialized yet,
+/// such as the spare capacity of a `Vec`, without having to zero it first.
+///
+/// For convenience, this returns a [`&str`](str), backed by `output`.
+///
+/// # Examples
+/// ```
+/// use core::mem::MaybeUninit;
+///
+/// let mut storage = [MaybeUninit::uninit(); 6];
+///
+/// assert_eq!(hector::encode_to_uninit_slice_upper([0xde, 0xca, 0xff], &mut storage), Ok("DECAFF"));
+/// ```
+///
+/// # Errors
+/// - [`EncodeError`] if the output is too big or too small.
+#[cfg(not(feature = "safe"))]
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn encode_to_uninit_slice_upper<T: AsRef<[u8]>>(
+    input: T,
+    output: &mut [MaybeUninit<u8>],
+) -> Result<&str, Error> {
+    fallback::encode_to_uninit_slice::<true>(input.as_ref(), output)
+}
+
+/// Encode `input` to a hex string.
+///
+/// For convenience, this returns a  [`&str`](str), backed by `output`.
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 6];
+///
+/// assert_eq!(hector::encode_to_array(&[0xde, 0xca, 0xff], &mut storage), "decaff");
+/// assert_eq!(&storage, b"decaff");
+/// ```
+///
+/// ```
+/// let mut storage = [0; 26];
+///
+/// assert_eq!(hector::encode_to_array(b"Hello, world!", &mut storage), "48656c6c6f2c20776f726c6421");
+/// assert_eq!(&storage, b"48656c6c6f2c20776f726c6421");
+/// ```
+///
+/// This is a `const fn`, so it works in `const` and `static` initializers too:
+/// ```
+/// const HEX: [u8; 6] = {
+///     let mut storage = [0; 6];
+///     hector::encode_to_array(&[0xc0, 0xff, 0xee], &mut storage);
+///     storage
+/// };
+///
+/// assert_eq!(&HEX, b"c0ffee");
+/// ```
+///
+/// # Compile errors
+/// Due to limitations in const generics, `M` has to be passed separately,
+/// and using this with `M != 2 * N` fails to compile.
+/// Once `const_generic_exprs` is stable, `M` will be removed.
+///
+/// ```compile_fail
+/// let mut storage = [0; 5];
+/// hector::encode_to_array(&[0xde, 0xca, 0xff], &mut storage);
+/// ```
+pub const fn encode_to_array<'a, const N: usize, const M: usize>(
+    input: &[u8; N],
+    output: &'a mut [u8; M],
+) -> &'a str {
+    fallback::encode_array::<N, M, false>(input, output)
+}
+
+/// Encode `input` to an uppercase hex string.
+///
+/// For convenience, this returns a `&str`, backed by `output`.
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 6];
+///
+/// assert_eq!(hector::encode_to_array_upper(&[0xde, 0xca, 0xff], &mut storage), "DECAFF");
+/// assert_eq!(&storage, b"DECAFF");
+/// ```
+///
+/// ```
+/// let mut storage = [0; 26];
+///
+/// assert_eq!(hector::encode_to_array_upper(b"Hello, world!", &mut storage), "48656C6C6F2C20776F726C6421");
+/// assert_eq!(&storage, b"48656C6C6F2C20776F726C6421");
+/// ```
+///
+/// # Compile errors
+/// Due to limitations in const generics, `M` has to be passed separately,
+/// and using this with `M != 2 * N` fails to compile.
+/// Once `const_generic_exprs` is stable, `M` will be removed.
+///
+/// ```compile_fail
+/// let mut storage = [0; 5];
+/// hector::encode_to_array_upper(&[0xde, 0xca, 0xff], &mut storage);
+/// ```
+pub const fn encode_to_array_upper<'a, const N: usize, const M: usize>(
+    input: &[u8; N],
+    output: &'a mut [u8; M],
+) -> &'a str {
+    fallback::encode_array::<N, M, true>(input, output)
+}
+
+/// Encode `input` to a lowercase hex string, returning the output array by value.
+///
+/// Unlike [`encode_to_array`], there's no need to declare a buffer up front,
+/// but since an array isn't a string, this returns the hex as ascii bytes.
+///
+/// # Examples
+/// ```
+/// assert_eq!(&hector::encode_array_owned(&[0xde, 0xca, 0xff]), b"decaff");
+/// ```
+///
+/// ```
+/// struct Id {
+///     hex: [u8; 8],
+/// }
+///
+/// let id = Id { hex: hector::encode_array_owned(&[0xc0, 0xff, 0xee, 0x00]) };
+/// assert_eq!(&id.hex, b"c0ffee00");
+/// ```
+///
+/// This is a `const fn`, so it works in `const` and `static` initializers too:
+/// ```
+/// const BUILD_ID: [u8; 8] = hector::encode_array_owned(&[0xde, 0xad, 0xbe, 0xef]);
+/// assert_eq!(&BUILD_ID, b"deadbeef");
+/// ```
+///
+/// # Compile errors
+/// Due to limitations in const generics, `M` has to be passed separately,
+/// and using this with `M != 2 * N` fails to compile.
+/// Once `const_generic_exprs` is stable, `M` will be removed.
+///
+/// ```compile_fail
+/// let hex: [u8; 5] = hector::encode_array_owned(&[0xde, 0xca, 0xff]);
+/// ```
+#[must_use]
+pub const fn encode_array_owned<const N: usize, const M: usize>(input: &[u8; N]) -> [u8; M] {
+    fallback::encode_array_owned::<N, M, false>(input)
+}
+
+/// Encode `input` to an uppercase hex string, returning the output array by value.
+///
+/// Unlike [`encode_to_array_upper`], there's no need to declare a buffer up front,
+/// but since an array isn't a string, this returns the hex as ascii bytes.
+///
+/// # Examples
+/// ```
+/// assert_eq!(&hector::encode_array_owned_upper(&[0xde, 0xca, 0xff]), b"DECAFF");
+/// ```
+///
+/// # Compile errors
+/// Due to limitations in const generics, `M` has to be passed separately,
+/// and using this with `M != 2 * N` fails to compile.
+/// Once `const_generic_exprs` is stable, `M` will be removed.
+///
+/// ```compile_fail
+/// let hex: [u8; 5] = hector::encode_array_owned_upper(&[0xde, 0xca, 0xff]);
+/// ```
+#[must_use]
+pub const fn encode_array_owned_upper<const N: usize, const M: usize>(input: &[u8; N]) -> [u8; M] {
+    fallback::encode_array_owned::<N, M, true>(input)
+}
+
+/// Encode `input` to a lowercase hex string, returning the output array by value.
+///
+/// This is [`encode_array_owned`] without the `M` parameter,
+/// which needs `generic_const_exprs`, so it's only available on nightly (with the `nightly` feature).
+/// Crates calling this need to enable `generic_const_exprs` too.
+///
+/// # Examples
+/// ```
+/// #![allow(incomplete_features)]
+/// #![feature(generic_const_exprs)]
+///
+/// assert_eq!(&hector::encode_array(&[0xde, 0xca, 0xff]), b"decaff");
+///
+/// const BUILD_ID: [u8; 8] = hector::encode_array(&[0xde, 0xad, 0xbe, 0xef]);
+/// assert_eq!(&BUILD_ID, b"deadbeef");
+/// ```
+#[cfg(feature = "nightly")]
+#[must_use]
+pub const fn encode_array<const N: usize>(input: &[u8; N]) -> [u8; N * 2]
+where
+    [(); N * 2]:,
+{
+    fallback::encode_array_owned::<N, { N * 2 }, false>(input)
+}
+
+/// Encode `input` to an uppercase hex string, returning the output array by value.
+///
+/// This is [`encode_array_owned_upper`] without the `M` parameter,
+/// which needs `generic_const_exprs`, so it's only available on nightly (with the `nightly` feature).
+/// Crates calling this need to enable `generic_const_exprs` too.
+///
+/// # Examples
+/// ```
+/// #![allow(incomplete_features)]
+/// #![feature(generic_const_exprs)]
+///
+/// assert_eq!(&hector::encode_array_upper(&[0xde, 0xca, 0xff]), b"DECAFF");
+/// ```
+#[cfg(feature = "nightly")]
+#[must_use]
+pub const fn encode_array_upper<const N: usize>(input: &[u8; N]) -> [u8; N * 2]
+where
+    [(); N * 2]:,
+{
+    fallback::encode_array_owned::<N, { N * 2 }, true>(input)
+}
+
+/// Returns `true` if `hex` is the hex encoding of `bytes`, in either case.
+///
+/// This encodes on the fly, so it doesn't need to allocate or decode `hex` first.
+///
+/// # Examples
+/// ```
+/// let digest = [0xde, 0xca, 0xff];
+///
+/// assert!(hector::eq_hex(digest, "decaff"));
+/// assert!(hector::eq_hex(digest, "DeCaFF"));
+///
+/// assert!(!hector::eq_hex(digest, "decafe"));
+/// assert!(!hector::eq_hex(digest, "deca"));
+/// ```
+#[must_use]
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn eq_hex<A: AsRef<[u8]>, B: AsRef<[u8]>>(bytes: A, hex: B) -> bool {
+    fallback::eq_hex(bytes.as_ref(), hex.as_ref())
+}
+
+/// Hex encoding as methods, for anything that's [`AsRef<[u8]>`](AsRef) (`[u8]`, `[u8; N]`, `Vec<u8>`, ...).
+///
+/// # Examples
+/// ```
+/// use hector::ToHex;
+///
+/// let digest = [0xde, 0xca, 0xff];
+///
+/// assert_eq!(digest.to_hex(), "decaff");
+/// assert_eq!(digest.to_hex_upper(), "DECAFF");
+/// assert_eq!(vec![0xc0, 0xff, 0xee].to_hex(), "c0ffee");
+/// ```
+pub trait ToHex {
+    /// Encode `self` to a lowercase hex string, see [`encode`](crate::encode).
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    fn to_hex(&self) -> alloc::string::String;
+
+    /// Encode `self` to an uppercase hex string, see [`encode_upper`](crate::encode_upper).
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    fn to_hex_upper(&self) -> alloc::string::String;
+
+    /// Encode `self` to a lowercase hex string in `output`, see [`encode_to_slice`].
+    ///
+    /// # Errors
+    /// - [`EncodeError`](crate::EncodeError) if the output is too big or too small.
+    ///
+    /// # Examples
+    /// ```
+    /// use hector::ToHex;
+    ///
+    /// let mut storage = [0; 6];
+    ///
+    /// assert_eq!([0xde, 0xca, 0xff].to_hex_slice(&mut storage), Ok("decaff"));
+    /// ```
+    fn to_hex_slice<'a>(&self, output: &'a mut [u8]) -> Result<&'a str, Error>;
+
+    /// Encode `self` to an uppercase hex string in `output`, see [`encode_to_slice_upper`].
+    ///
+    /// # Errors
+    /// - [`EncodeError`](crate::EncodeError) if the output is too big or too small.
+    fn to_hex_slice_upper<'a>(&self, output: &'a mut [u8]) -> Result<&'a str, Error>;
+}
+
+impl<T: AsRef<[u8]> + ?Sized> ToHex for T {
+    #[cfg(feature = "alloc")]
+    fn to_hex(&self) -> alloc::string::String {
+        fallback::encode::<false>(self.as_ref())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_hex_upper(&self) -> alloc::string::String {
+        fallback::encode::<true>(self.as_ref())
+    }
+
+    fn to_hex_slice<'a>(&self, output: &'a mut [u8]) -> Result<&'a str, Error> {
+        fallback::encode_to_slice::<false>(self.as_ref(), output)
+    }
+
+    fn to_hex_slice_upper<'a>(&self, output: &'a mut [u8]) -> Result<&'a str, Error> {
+        fallback::encode_to_slice::<true>(self.as_ref(), output)
+    }
 }
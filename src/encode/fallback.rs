@@ -1,6 +1,9 @@
+#[cfg(not(feature = "safe"))]
 use core::mem::MaybeUninit;
 
 use super::Error;
+use crate::output::{self, OutputByte};
+use crate::scratch::Scratch;
 
 #[cfg(feature = "alloc")]
 use alloc::{string::String, vec::Vec};
@@ -39,7 +42,7 @@ const fn nibble_to_hex<const UPPER: bool>(nibble: u8) -> HexChar {
 ///
 /// This method is the common primitive of all the hex encoding functions in this module.
 #[inline(always)]
-const fn byte_to_hex<const UPPER: bool>(byte: u8) -> [HexChar; 2] {
+pub(crate) const fn byte_to_hex<const UPPER: bool>(byte: u8) -> [HexChar; 2] {
     [
         nibble_to_hex::<UPPER>(byte >> 4),
         nibble_to_hex::<UPPER>(byte & 0xf),
@@ -54,27 +57,359 @@ pub(super) fn encode<const UPPER: bool>(input: &[u8]) -> String {
     // 2. `flat_map` + `collect`. Pays for an unneeded assert to ensure we don't overflow a `usize`
     //    (even though `collect` does a more restrictive check just a bit later).
     // 3. `vec![0; {len}]` + `encode_to_slice`. This would require emitting a `rust_allocate_zeroed` instead of a `rust_allocate`.
-    // 4. (this solution) `Vec::with_capacity` + `Vec::spare_capacity_mut`.
-    //    This requires extra unsafe code...
+    // 4. (this solution) `Vec::with_capacity` + `Vec::spare_capacity_mut` (via `output::append`).
+    //    This requires extra unsafe code... (and with the `safe` feature, it turns into 3 anyway).
     let out_len = input.len() * 2;
 
     let mut output = Vec::with_capacity(out_len);
-    unsafe {
-        // vec only guarantees we get "at least" `out_len` capacity, we might have a bit extra.
-        encode_impl::<UPPER>(input, &mut output.spare_capacity_mut()[..out_len]);
-        // safety: `encode_inner` guarantees that `output.len()` elements are written.
-        output.set_len(out_len);
+    output::append(&mut output, out_len, |spare| {
+        encode_impl::<_, UPPER>(input, spare)
+    });
+
+    // For all values of input bytes both output bytes will be valid ascii-hex (as asserted by tests for `byte_to_hex`).
+    output::hex_string(output)
+}
+
+#[cfg(feature = "alloc")]
+pub(super) fn try_encode<const UPPER: bool>(
+    input: &[u8],
+) -> Result<String, super::AllocationError> {
+    let len = input.len() * 2;
+
+    let mut output = Vec::new();
+    output
+        .try_reserve_exact(len)
+        .map_err(|_| super::AllocationError { len })?;
+
+    // This won't need to allocate anymore.
+    encode_append_vec::<UPPER>(input, &mut output);
+
+    Ok(output::hex_string(output))
+}
+
+#[cfg(feature = "alloc")]
+pub(super) fn encode_vec<const UPPER: bool>(mut buffer: Vec<u8>) -> String {
+    let len = buffer.len();
+
+    if buffer.capacity() < len * 2 {
+        return encode::<UPPER>(&buffer);
+    }
+
+    // This doesn't allocate, since the capacity is already there.
+    buffer.resize(len * 2, 0);
+
+    // Every byte is written to indexes at or after the one it's read from (`index * 2 >= index`),
+    // and everything after `index` has already been read, so going back to front never overwrites a byte that's still needed.
+    for index in (0..len).rev() {
+        [buffer[index * 2], buffer[index * 2 + 1]] = byte_to_hex::<UPPER>(buffer[index]);
     }
 
+    output::hex_string(buffer)
+}
+
+/// Append the hex encoding of `input` to `output`.
+#[cfg(feature = "alloc")]
+pub(super) fn encode_append_vec<const UPPER: bool>(input: &[u8], output: &mut Vec<u8>) {
+    // See `encode` for why this uses `spare_capacity_mut`.
+    let out_len = input.len() * 2;
+
+    output::append(output, out_len, |spare| {
+        encode_impl::<_, UPPER>(input, spare)
+    });
+}
+
+#[cfg(feature = "alloc")]
+pub(super) fn encode_append<const UPPER: bool>(input: &[u8], output: &mut String) {
+    #[cfg(not(feature = "safe"))]
     // Safety: for all values of input bytes both output bytes will be valid ascii-hex (as asserted by tests for `byte_to_hex`).
-    // Ascii hex characters are valid UTF-8 (because ascii is valid UTF-8).
-    // Therefore, this is a valid conversion.
-    unsafe { String::from_utf8_unchecked(output) }
+    // Ascii hex characters are valid UTF-8 (because ascii is valid UTF-8),
+    // and appending valid UTF-8 to a `String` keeps it valid UTF-8.
+    encode_append_vec::<UPPER>(input, unsafe { output.as_mut_vec() });
+
+    // There's no safe way to get at the bytes of a `String`, so this goes through a buffer instead.
+    #[cfg(feature = "safe")]
+    {
+        output.reserve(input.len() * 2);
+
+        output::for_each_piece(
+            |write| encode_chunked::<_, FMT_BUFFER_LEN, UPPER>(input, write),
+            |chunk| output.push_str(chunk),
+        );
+    }
 }
 
-// note: There *is* a way to deduplicate this with the slice/array impls, but honestly, it just isn't worth it with the current stdlib.
-// coincidentally, this function existing means we *could* expose a write once method.
-// For instance if someone needs to do an in-place init of an array/slice.
+#[cfg(feature = "alloc")]
+pub(super) fn encode_prefixed<const UPPER: bool>(input: &[u8]) -> String {
+    // Allocating the whole thing up front, so `encode_append` doesn't need to grow it.
+    let mut output = String::with_capacity(2 + input.len() * 2);
+    output.push_str("0x");
+
+    encode_append::<UPPER>(input, &mut output);
+
+    output
+}
+
+pub(super) fn encode_prefixed_to_slice<'a, const UPPER: bool>(
+    input: &[u8],
+    output: &'a mut [u8],
+) -> Result<&'a str, Error> {
+    // Checked up front, so that `output` isn't touched on error.
+    if output.len() != 2 + input.len() * 2 {
+        return Err(Error);
+    }
+
+    let Some((prefix, hex)) = output.split_at_mut_checked(2) else {
+        return Err(Error);
+    };
+
+    prefix.copy_from_slice(b"0x");
+    encode_impl::<u8, UPPER>(input, hex);
+
+    Ok(output::hex_str_mut(output))
+}
+
+/// The exact length of `input_len` bytes of hex, with `separator` between each byte.
+#[cfg(feature = "alloc")]
+fn delimited_len(input_len: usize, separator: char) -> usize {
+    input_len * 2 + input_len.saturating_sub(1) * separator.len_utf8()
+}
+
+/// Encode `input` with `separator` between each `group` bytes, passing each piece to `write`.
+pub(crate) fn encode_delimited_with<E, const UPPER: bool>(
+    input: &[u8],
+    separator: char,
+    group: usize,
+    mut write: impl FnMut(&str) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut encoded = [0; 4];
+    let separator = &*separator.encode_utf8(&mut encoded);
+
+    // Hex (and ascii separators) is staged in `buffer[..len]`, so it's written a chunk at a time rather than per byte.
+    let mut buffer = [0; FMT_BUFFER_LEN];
+    let mut len = 0;
+
+    let mut encode = || {
+        for (index, &byte) in input.iter().enumerate() {
+            if index != 0 && index.is_multiple_of(group) {
+                if let &[ascii] = separator.as_bytes() {
+                    buffer[len] = ascii;
+                    len += 1;
+                } else {
+                    // Only ascii can go through `hex_str`, so other separators are written on their own.
+                    if len != 0 {
+                        write(output::hex_str(&buffer[..len]))?;
+                        len = 0;
+                    }
+
+                    write(separator)?;
+                }
+            }
+
+            buffer[len..][..2].copy_from_slice(&byte_to_hex::<UPPER>(byte));
+            len += 2;
+
+            // Leave room for the next byte and its separator.
+            if buffer.len() - len < 3 {
+                write(output::hex_str(&buffer[..len]))?;
+                len = 0;
+            }
+        }
+
+        if len != 0 {
+            write(output::hex_str(&buffer[..len]))?;
+        }
+
+        Ok(())
+    };
+
+    let result = encode();
+
+    #[cfg(feature = "zeroize")]
+    crate::scratch::wipe(&mut buffer[..]);
+
+    result
+}
+
+#[cfg(feature = "alloc")]
+pub(super) fn encode_delimited<const UPPER: bool>(input: &[u8], separator: char) -> String {
+    output::collect_string(delimited_len(input.len(), separator), |write| {
+        encode_delimited_with::<_, UPPER>(input, separator, 1, write)
+    })
+}
+
+/// Encode `input` in reverse byte order into `output`, which has to be exactly `input.len() * 2` bytes.
+fn encode_reverse_impl<const UPPER: bool>(input: &[u8], output: &mut [u8]) {
+    for (output, &byte) in output.chunks_exact_mut(2).zip(input.iter().rev()) {
+        output.copy_from_slice(&byte_to_hex::<UPPER>(byte));
+    }
+}
+
+/// Encode `input` in reverse byte order (`[0x01, 0x02]` is `0201`), passing each chunk to `write`.
+pub(super) fn encode_reverse_with<E, const UPPER: bool>(
+    input: &[u8],
+    mut write: impl FnMut(&str) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut buffer = Scratch([0; FMT_BUFFER_LEN]);
+
+    // The last chunk of `input` is the first chunk of the output.
+    for chunk in input.rchunks(FMT_BUFFER_LEN / 2) {
+        let hex = &mut buffer[..chunk.len() * 2];
+
+        encode_reverse_impl::<UPPER>(chunk, hex);
+        write(output::hex_str(hex))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "alloc")]
+pub(super) fn encode_reverse<const UPPER: bool>(input: &[u8]) -> String {
+    output::collect_string(input.len() * 2, |write| {
+        encode_reverse_with::<_, UPPER>(input, write)
+    })
+}
+
+pub(super) fn encode_reverse_to_slice<'a, const UPPER: bool>(
+    input: &[u8],
+    output: &'a mut [u8],
+) -> Result<&'a str, Error> {
+    if output.len() != input.len() * 2 {
+        return Err(Error);
+    }
+
+    encode_reverse_impl::<UPPER>(input, output);
+
+    Ok(output::hex_str_mut(output))
+}
+
+/// Split `input` at its first nonzero byte, `None` if every byte is zero.
+fn strip_leading_zeros(input: &[u8]) -> Option<(u8, &[u8])> {
+    let start = input.iter().position(|&byte| byte != 0)?;
+    let (&first, rest) = input[start..].split_first()?;
+
+    Some((first, rest))
+}
+
+/// Encode `input` as a number, without leading zero nibbles (`[0x00, 0x0a, 0xbc]` is `abc`), passing each chunk to `write`.
+///
+/// Zero (including empty input) is `0`.
+pub(super) fn encode_trimmed_with<E, const UPPER: bool>(
+    input: &[u8],
+    mut write: impl FnMut(&str) -> Result<(), E>,
+) -> Result<(), E> {
+    let Some((first, rest)) = strip_leading_zeros(input) else {
+        return write("0");
+    };
+
+    // The first nonzero byte might only need its low nibble.
+    let first = byte_to_hex::<UPPER>(first);
+    let first = if first[0] == b'0' {
+        &first[1..]
+    } else {
+        &first[..]
+    };
+    write(output::hex_str(first))?;
+
+    encode_chunked::<_, FMT_BUFFER_LEN, UPPER>(rest, write)
+}
+
+#[cfg(feature = "alloc")]
+pub(super) fn encode_trimmed<const UPPER: bool>(input: &[u8]) -> String {
+    let len = match strip_leading_zeros(input) {
+        Some((first, rest)) => rest.len() * 2 + if first < 0x10 { 1 } else { 2 },
+        None => 1,
+    };
+    output::collect_string(len, |write| encode_trimmed_with::<_, UPPER>(input, write))
+}
+
+/// Whether a swapped encoding of `input` ends in a filler `f` that's left off, with `FILLER`.
+fn ends_in_filler<const FILLER: bool>(input: &[u8]) -> bool {
+    FILLER && input.last().is_some_and(|&byte| byte >> 4 == 0xf)
+}
+
+/// Encode `input` with the nibbles of each byte swapped (`[0x21, 0x43]` is `1234`), passing each chunk to `write`.
+///
+/// With `FILLER`, a filler `f` in the last (high) nibble is left off, so `[0x21, 0xf3]` is `123`.
+pub(crate) fn encode_swapped_with<E, const FILLER: bool, const UPPER: bool>(
+    input: &[u8],
+    mut write: impl FnMut(&str) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut buffer = Scratch([0; FMT_BUFFER_LEN]);
+    let trim = ends_in_filler::<FILLER>(input);
+
+    let mut chunks = input.chunks(FMT_BUFFER_LEN / 2).peekable();
+    while let Some(chunk) = chunks.next() {
+        for (output, &byte) in buffer.chunks_exact_mut(2).zip(chunk) {
+            let [high, low] = byte_to_hex::<UPPER>(byte);
+            output.copy_from_slice(&[low, high]);
+        }
+
+        let len = chunk.len() * 2 - usize::from(trim && chunks.peek().is_none());
+        write(output::hex_str(&buffer[..len]))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "alloc")]
+pub(super) fn encode_swapped<const FILLER: bool, const UPPER: bool>(input: &[u8]) -> String {
+    let len = input.len() * 2 - usize::from(ends_in_filler::<FILLER>(input));
+    output::collect_string(len, |write| {
+        encode_swapped_with::<_, FILLER, UPPER>(input, write)
+    })
+}
+
+/// Whether `byte` goes in escaped output as is, when printable ascii is passed through.
+///
+/// That's printable ascii, other than `\` and the quotes (so the output is valid in any string literal).
+fn passes_through(byte: u8) -> bool {
+    (byte.is_ascii_graphic() || byte == b' ') && !matches!(byte, b'\\' | b'"' | b'\'')
+}
+
+#[cfg(feature = "alloc")]
+fn escaped_len<const ASCII: bool>(input: &[u8]) -> usize {
+    input
+        .iter()
+        .map(|&byte| if ASCII && passes_through(byte) { 1 } else { 4 })
+        .sum()
+}
+
+/// Encode `input` as `\xNN` escapes, passing each piece to `write`.
+///
+/// With `ASCII`, printable ascii is passed through, each run of it as one piece.
+pub(crate) fn encode_escaped_with<E, const ASCII: bool>(
+    input: &[u8],
+    mut write: impl FnMut(&str) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut rest = input;
+
+    while let Some((&byte, tail)) = rest.split_first() {
+        if ASCII && passes_through(byte) {
+            let len = rest.iter().take_while(|&&it| passes_through(it)).count();
+            let (plain, tail) = rest.split_at(len);
+
+            write(output::hex_str(plain))?;
+            rest = tail;
+        } else {
+            let [high, low] = byte_to_hex::<false>(byte);
+
+            write(output::hex_str(&[b'\\', b'x', high, low]))?;
+            rest = tail;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "alloc")]
+pub(super) fn encode_escaped<const ASCII: bool>(input: &[u8]) -> String {
+    output::collect_string(escaped_len::<ASCII>(input), |write| {
+        encode_escaped_with::<_, ASCII>(input, write)
+    })
+}
+
+// note: There *is* a way to deduplicate this with the array impl, but honestly, it just isn't worth it with the current stdlib.
+// coincidentally, this function existing is what makes `encode_to_uninit_slice` possible.
 ///
 /// # Safety
 /// `output` *must* have exactly the right length for `input`.
@@ -83,15 +418,128 @@ pub(super) fn encode<const UPPER: bool>(input: &[u8]) -> String {
 ///
 /// This function will *never* write uninitialized values.
 #[inline(always)]
-fn encode_impl<const UPPER: bool>(input: &[u8], output: &mut [MaybeUninit<u8>]) {
+fn encode_impl<O: OutputByte, const UPPER: bool>(input: &[u8], output: &mut [O]) {
+    // Small inputs (hashes, IDs, etc) are very common, and the general loop has a fair bit of setup for them,
+    // so they get their own fully unrolled versions.
+    match input.len() {
+        2 => encode_fixed::<O, 2, UPPER>(input, output),
+        3 => encode_fixed::<O, 3, UPPER>(input, output),
+        4 => encode_fixed::<O, 4, UPPER>(input, output),
+        5 => encode_fixed::<O, 5, UPPER>(input, output),
+        6 => encode_fixed::<O, 6, UPPER>(input, output),
+        7 => encode_fixed::<O, 7, UPPER>(input, output),
+        8 => encode_fixed::<O, 8, UPPER>(input, output),
+        9 => encode_fixed::<O, 9, UPPER>(input, output),
+        10 => encode_fixed::<O, 10, UPPER>(input, output),
+        11 => encode_fixed::<O, 11, UPPER>(input, output),
+        12 => encode_fixed::<O, 12, UPPER>(input, output),
+        13 => encode_fixed::<O, 13, UPPER>(input, output),
+        14 => encode_fixed::<O, 14, UPPER>(input, output),
+        15 => encode_fixed::<O, 15, UPPER>(input, output),
+        16 => encode_fixed::<O, 16, UPPER>(input, output),
+        len if len >= LARGE_INPUT_LEN => encode_blocked::<O, UPPER>(input, output),
+        _ => encode_loop::<O, UPPER>(input, output),
+    }
+}
+
+/// [`encode_impl`], for exactly `N` bytes of input.
+///
+/// Since `N` is a constant, the compiler can fully unroll this.
+#[inline(always)]
+fn encode_fixed<O: OutputByte, const N: usize, const UPPER: bool>(input: &[u8], output: &mut [O]) {
+    let (Ok(input), Some(output)) = (<&[u8; N]>::try_from(input), output.get_mut(..N * 2)) else {
+        return encode_loop::<O, UPPER>(input, output);
+    };
+
+    for (index, &byte) in input.iter().enumerate() {
+        let [high, low] = byte_to_hex::<UPPER>(byte);
+        output[index * 2] = O::new(high);
+        output[index * 2 + 1] = O::new(low);
+    }
+}
+
+/// Inputs at least this long get processed by [`encode_blocked`].
+///
+/// Below this, the input and output mostly fit in cache, and a single pass is just as fast.
+/// Above it, prefetching can start to win, compare with the `to-string-large` benchmarks.
+const LARGE_INPUT_LEN: usize = 4 * 1024 * 1024;
+
+/// How far ahead of the input it's encoding [`encode_blocked`] prefetches,
+/// small enough that the prefetched block and the output for the current one comfortably fit in L2.
+const BLOCK_LEN: usize = 64 * 1024;
+
+/// The amount of input [`encode_blocked`] encodes in one go (between prefetches).
+const STRIDE_LEN: usize = 4 * 1024;
+
+/// The (assumed) size of a cache line.
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "sse",
+    not(feature = "safe"),
+    not(feature = "force-fallback")
+))]
+const CACHE_LINE_LEN: usize = 64;
+
+/// Hint to the CPU that `input` is going to be read soon.
+///
+/// This is a no-op with `force-fallback` (which only uses scalar code) and `safe` (which rules out the intrinsic).
+#[inline(always)]
+fn prefetch(input: &[u8]) {
+    #[cfg(all(
+        target_arch = "x86_64",
+        target_feature = "sse",
+        not(feature = "safe"),
+        not(feature = "force-fallback")
+    ))]
+    for line in input.chunks(CACHE_LINE_LEN) {
+        use core::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+
+        // Safety: `sse` is statically enabled, and `line` is a valid slice (not that prefetching can fault anyway).
+        unsafe { _mm_prefetch::<_MM_HINT_T0>(line.as_ptr().cast()) };
+    }
+
+    #[cfg(not(all(
+        target_arch = "x86_64",
+        target_feature = "sse",
+        not(feature = "safe"),
+        not(feature = "force-fallback")
+    )))]
+    let _ = input;
+}
+
+/// [`encode_impl`], for very large inputs.
+///
+/// A single pass over a large input ends up bound by memory bandwidth,
+/// so this prefetches a block ahead of what it's currently encoding.
+#[inline(never)]
+fn encode_blocked<O: OutputByte, const UPPER: bool>(input: &[u8], output: &mut [O]) {
+    let mut input_strides = input.chunks_exact(STRIDE_LEN);
+    let mut output_strides = output.chunks_exact_mut(STRIDE_LEN * 2);
+
+    // Every stride of the current block lines up with a stride of the next one.
+    let mut ahead = input.chunks(STRIDE_LEN).skip(BLOCK_LEN / STRIDE_LEN);
+
+    for (input, output) in (&mut input_strides).zip(&mut output_strides) {
+        if let Some(ahead) = ahead.next() {
+            prefetch(ahead);
+        }
+
+        encode_loop::<O, UPPER>(input, output);
+    }
+
+    encode_loop::<O, UPPER>(input_strides.remainder(), output_strides.into_remainder());
+}
+
+/// [`encode_impl`], for any length of input.
+#[inline(always)]
+fn encode_loop<O: OutputByte, const UPPER: bool>(input: &[u8], output: &mut [O]) {
     // array chunks would be _neat_, but relying on LLVM here is _fine_ (just make sure it code-gens well).
     for (output, input) in output.chunks_exact_mut(2).zip(input.iter().copied()) {
         let byte = byte_to_hex::<UPPER>(input);
-        [output[0], output[1]] = [MaybeUninit::new(byte[0]), MaybeUninit::new(byte[1])];
+        [output[0], output[1]] = [O::new(byte[0]), O::new(byte[1])];
     }
 }
 
-// todo: const fn when `&mut` in const fn is stable... And everything else.
 pub(super) fn encode_to_slice<'a, const UPPER: bool>(
     input: &[u8],
     output: &'a mut [u8],
@@ -100,44 +548,230 @@ pub(super) fn encode_to_slice<'a, const UPPER: bool>(
         return Err(Error);
     }
 
-    // array chunks would be _neat_, but relying on LLVM here is _fine_ (just make sure it code-gens well).
-    for (output, input) in output.chunks_exact_mut(2).zip(input.iter().copied()) {
-        [output[0], output[1]] = byte_to_hex::<UPPER>(input);
+    encode_impl::<u8, UPPER>(input, output);
+
+    // For all values of input bytes both output bytes will be valid ascii-hex (as asserted by tests for `byte_to_hex`).
+    Ok(output::hex_str_mut(output))
+}
+
+// There's no safe way to go from initialized `MaybeUninit`s back to bytes, so this doesn't exist with the `safe` feature.
+#[cfg(not(feature = "safe"))]
+pub(super) fn encode_to_uninit_slice<'a, const UPPER: bool>(
+    input: &[u8],
+    output: &'a mut [MaybeUninit<u8>],
+) -> Result<&'a str, Error> {
+    if output.len() != input.len() * 2 {
+        return Err(Error);
     }
 
-    // Safety: for all values of input bytes both output bytes will be valid ascii-hex (as asserted by tests for `byte_to_hex`).
-    // Ascii hex characters are valid UTF-8 (because ascii is valid UTF-8).
-    // Therefore, this is a valid conversion.
-    Ok(unsafe { core::str::from_utf8_unchecked_mut(output) })
+    encode_impl::<MaybeUninit<u8>, UPPER>(input, output);
+
+    // Safety: `encode_impl` initialized all of `output`, and `MaybeUninit<u8>` has the same layout as `u8`.
+    let output = unsafe { &mut *(output as *mut [MaybeUninit<u8>] as *mut [u8]) };
+
+    // For all values of input bytes both output bytes will be valid ascii-hex (as asserted by tests for `byte_to_hex`).
+    Ok(output::hex_str_mut(output))
+}
+
+/// The combined length of `inputs`.
+fn vectored_len<T: AsRef<[u8]>>(inputs: &[T]) -> usize {
+    inputs.iter().map(|input| input.as_ref().len()).sum()
+}
+
+/// [`encode_impl`], for each of `inputs` one after the other.
+///
+/// Returns `true` if `inputs` exactly filled `output`,
+/// which only goes wrong if an [`AsRef`] impl doesn't return the same slice every time (making the length check a lie).
+/// If this returns `false`, `output` might not be fully initialized.
+fn encode_vectored_impl<T: AsRef<[u8]>, O: OutputByte, const UPPER: bool>(
+    inputs: &[T],
+    mut output: &mut [O],
+) -> bool {
+    for input in inputs {
+        let input = input.as_ref();
+        let Some((current, rest)) = output.split_at_mut_checked(input.len() * 2) else {
+            return false;
+        };
+
+        encode_impl::<O, UPPER>(input, current);
+        output = rest;
+    }
+
+    output.is_empty()
+}
+
+#[cfg(feature = "alloc")]
+pub(super) fn encode_vectored<T: AsRef<[u8]>, const UPPER: bool>(inputs: &[T]) -> String {
+    let mut output = Vec::with_capacity(vectored_len(inputs) * 2);
+
+    // Each input is encoded from the one slice its `AsRef` impl returns here,
+    // so an impl that doesn't return the same slice every time only costs a reallocation.
+    for input in inputs {
+        encode_append_vec::<UPPER>(input.as_ref(), &mut output);
+    }
+
+    output::hex_string(output)
+}
+
+pub(super) fn encode_vectored_to_slice<'a, T: AsRef<[u8]>, const UPPER: bool>(
+    inputs: &[T],
+    output: &'a mut [u8],
+) -> Result<&'a str, Error> {
+    if output.len() != vectored_len(inputs) * 2 {
+        return Err(Error);
+    }
+
+    if !encode_vectored_impl::<T, u8, UPPER>(inputs, output) {
+        return Err(Error);
+    }
+
+    Ok(output::hex_str_mut(output))
+}
+
+/// The size of the buffer [`encode_chunked`] uses for formatters, small enough to comfortably live on the stack.
+pub(crate) const FMT_BUFFER_LEN: usize = 512;
+
+/// The size of the buffer [`encode_chunked`] uses for io writers,
+/// bigger than for formatters since each write might be a syscall.
+#[cfg(feature = "std")]
+pub(super) const IO_BUFFER_LEN: usize = 8 * 1024;
+
+/// Encode `input` a chunk at a time (into an `N` byte buffer on the stack), passing each encoded chunk to `write`,
+/// stopping at the first error.
+///
+/// This is the common part of encoding to a sink (formatter, writer, ...) without allocating.
+#[inline]
+pub(crate) fn encode_chunked<E, const N: usize, const UPPER: bool>(
+    input: &[u8],
+    mut write: impl FnMut(&str) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut buffer = [output::UNWRITTEN; N];
+
+    let result = input.chunks(N / 2).try_for_each(|input| {
+        let output = &mut buffer[..input.len() * 2];
+
+        #[cfg(not(feature = "safe"))]
+        let output = encode_to_uninit_slice::<UPPER>(input, output);
+        #[cfg(feature = "safe")]
+        let output = encode_to_slice::<UPPER>(input, output);
+
+        // `output` is exactly the right length, so this can't fail.
+        let Ok(output) = output else { unreachable!() };
+
+        write(output)
+    });
+
+    #[cfg(feature = "zeroize")]
+    crate::scratch::wipe(&mut buffer[..]);
+
+    result
+}
+
+pub(super) fn eq_hex(bytes: &[u8], hex: &[u8]) -> bool {
+    if hex.len() != bytes.len() * 2 {
+        return false;
+    }
+
+    // `&` rather than `&&` so that there's no early exit (which lets this vectorize).
+    bytes
+        .iter()
+        .zip(hex.chunks_exact(2))
+        .fold(true, |equal, (&byte, pair)| {
+            let [lower_high, lower_low] = byte_to_hex::<false>(byte);
+            let [upper_high, upper_low] = byte_to_hex::<true>(byte);
+
+            // Comparing against both cases (rather than lowercasing `pair`) so no other characters compare equal.
+            equal
+                & ((pair[0] == lower_high) | (pair[0] == upper_high))
+                & ((pair[1] == lower_low) | (pair[1] == upper_low))
+        })
 }
 
-// pre 1.0: `N * 2` needs to work, so, const-generic exprs.
+/// Checks the lengths for [`encode_array`], at compile time.
 ///
+/// This is an associated const (rather than an inline `const` block) because `generic_const_exprs` rejects the latter.
+struct ArrayLen<const N: usize, const M: usize>;
+
+impl<const N: usize, const M: usize> ArrayLen<N, M> {
+    /// Evaluated once for each `N` and `M` that [`encode_array`] is used with, failing to compile if `M != 2 * N`.
+    const CHECK: () = assert!(
+        N * 2 == M,
+        "the output array must be exactly twice as long as the input"
+    );
+}
+
+/// Encode `input` into `output`, returning the hex as a `&str`.
 ///
-/// # Panics
-/// Due to limitations in const generics,
-/// this function currently panics if `N != 2 * M`,
-/// in the future this will turn into a compiler error.
-pub(super) fn encode_array<'a, const N: usize, const M: usize, const UPPER: bool>(
+/// # Compile errors
+/// Due to limitations in const generics, `M` has to be passed separately,
+/// using this with `M != 2 * N` fails to compile (when it's instantiated).
+pub(super) const fn encode_array<'a, const N: usize, const M: usize, const UPPER: bool>(
     input: &[u8; N],
     output: &'a mut [u8; M],
 ) -> &'a str {
-    assert!(N * 2 == M);
+    let () = ArrayLen::<N, M>::CHECK;
 
-    // Array chunks would be _neat_, but relying on LLVM here is _fine_ (just make sure it code-gens well).
-    for (output, input) in output.chunks_exact_mut(2).zip(input.iter().copied()) {
-        [output[0], output[1]] = byte_to_hex::<UPPER>(input);
+    // Iterators aren't usable in const fn, so this is a plain loop.
+    let mut index = 0;
+    while index < N {
+        [output[index * 2], output[index * 2 + 1]] = byte_to_hex::<UPPER>(input[index]);
+        index += 1;
     }
 
-    // Safety: for all values of input bytes both output bytes will be valid ascii-hex (as asserted by tests for `byte_to_hex`).
-    // Ascii hex characters are valid UTF-8 (because ascii is valid UTF-8).
-    // Therefore, this is a valid conversion.
-    unsafe { core::str::from_utf8_unchecked_mut(output) }
+    // For all values of input bytes both output bytes will be valid ascii-hex (as asserted by tests for `byte_to_hex`).
+    output::hex_str_mut(output)
+}
+
+/// Encode `input` into a new array.
+///
+/// # Compile errors
+/// Due to limitations in const generics, `M` has to be passed separately,
+/// using this with `M != 2 * N` fails to compile (when it's instantiated).
+pub(super) const fn encode_array_owned<const N: usize, const M: usize, const UPPER: bool>(
+    input: &[u8; N],
+) -> [u8; M] {
+    let mut output = [0; M];
+
+    encode_array::<N, M, UPPER>(input, &mut output);
+
+    output
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{byte_to_hex, encode, encode_to_slice, nibble_to_hex};
+    use super::{
+        byte_to_hex, encode, encode_append, encode_chunked, encode_delimited, encode_escaped,
+        encode_prefixed, encode_prefixed_to_slice, encode_reverse, encode_reverse_to_slice,
+        encode_swapped, encode_to_slice, encode_trimmed, encode_vec, encode_vectored,
+        encode_vectored_to_slice, eq_hex, nibble_to_hex, FMT_BUFFER_LEN,
+    };
+
+    #[test]
+    fn encode_small_lengths() {
+        let input = crate::test_util::bytes(20);
+
+        for len in 0..=input.len() {
+            let input = &input[..len];
+            let expected: std::string::String =
+                input.iter().map(|it| std::format!("{it:02x}")).collect();
+
+            assert_eq!(encode::<false>(input), expected);
+
+            let mut buf = [0; 40];
+            let actual = encode_to_slice::<true>(input, &mut buf[..len * 2]).unwrap();
+            assert_eq!(actual, expected.to_ascii_uppercase());
+        }
+    }
+
+    #[test]
+    fn encode_is_exact_size() {
+        let input: [u8; 40] = core::array::from_fn(|it| it as u8);
+
+        for len in 0..=input.len() {
+            let output = encode::<false>(&input[..len]);
+            assert_eq!(output.capacity(), output.len(), "len: {len}");
+        }
+    }
 
     #[test]
     fn nibble_always_valid() {
@@ -175,9 +809,318 @@ mod tests {
         for v in u16::MIN..=u16::MAX {
             let expected = encode::<false>(&v.to_be_bytes());
             let mut buf = [0; 4];
-            let actual = &*encode_to_slice::<false>(&v.to_be_bytes(), &mut buf).unwrap();
+            let actual = encode_to_slice::<false>(&v.to_be_bytes(), &mut buf).unwrap();
 
             assert_eq!(expected, actual);
         }
     }
+
+    #[test]
+    fn encode_large_matches_small() {
+        let input = crate::test_util::bytes(super::LARGE_INPUT_LEN + super::BLOCK_LEN / 2 + 3);
+
+        let mut expected = std::vec![0; input.len() * 2];
+        for (output, byte) in expected.chunks_exact_mut(2).zip(&input) {
+            [output[0], output[1]] = byte_to_hex::<false>(*byte);
+        }
+
+        assert_eq!(encode::<false>(&input).as_bytes(), expected);
+    }
+
+    #[test]
+    fn encode_append_matches_encode() {
+        let mut output = std::string::String::new();
+        let mut expected = std::string::String::new();
+
+        for v in 0..=u8::MAX {
+            let input = [v].repeat(v as usize % 19);
+
+            encode_append::<false>(&input, &mut output);
+            expected.push_str(&encode::<false>(&input));
+
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn encode_delimited_matches_join() {
+        for separator in [':', ' ', 'é', '€', '🦀'] {
+            // Past `FMT_BUFFER_LEN`, so that the output is written in more than one chunk.
+            for len in (0..20).chain([FMT_BUFFER_LEN / 3, FMT_BUFFER_LEN, FMT_BUFFER_LEN + 1]) {
+                let input = crate::test_util::bytes(len);
+
+                let expected = input
+                    .iter()
+                    .map(|byte| std::format!("{byte:02x}"))
+                    .collect::<std::vec::Vec<_>>()
+                    .join(separator.encode_utf8(&mut [0; 4]));
+
+                let output = encode_delimited::<false>(&input, separator);
+                assert_eq!(output, expected);
+                assert_eq!(output.capacity(), output.len());
+            }
+        }
+    }
+
+    #[test]
+    fn encode_escaped_matches_ascii_escape() {
+        let input: std::vec::Vec<u8> = (0..=u8::MAX).collect();
+
+        let expected: std::string::String = input
+            .iter()
+            .map(|byte| std::format!("\\x{byte:02x}"))
+            .collect();
+        let output = encode_escaped::<false>(&input);
+        assert_eq!(output, expected);
+        assert_eq!(output.capacity(), output.len());
+
+        // Everything that's passed through is what `escape_ascii` would pass through too.
+        let output = encode_escaped::<true>(&input);
+        let expected: std::string::String = input
+            .iter()
+            .map(|&byte| match byte {
+                b'\\' | b'"' | b'\'' => std::format!("\\x{byte:02x}"),
+                _ if byte.escape_ascii().len() == 1 => char::from(byte).into(),
+                _ => std::format!("\\x{byte:02x}"),
+            })
+            .collect();
+        assert_eq!(output, expected);
+        assert_eq!(output.capacity(), output.len());
+    }
+
+    #[test]
+    fn encode_reverse_matches_encode() {
+        let input = crate::test_util::bytes(FMT_BUFFER_LEN * 2 + 3);
+
+        for len in [
+            0,
+            1,
+            FMT_BUFFER_LEN / 2,
+            FMT_BUFFER_LEN / 2 + 1,
+            input.len(),
+        ] {
+            let input = &input[..len];
+            let reversed: std::vec::Vec<u8> = input.iter().rev().copied().collect();
+
+            let output = encode_reverse::<true>(input);
+            assert_eq!(output, encode::<true>(&reversed));
+            assert_eq!(output.capacity(), output.len());
+
+            let mut buffer = std::vec![0; len * 2];
+            assert_eq!(
+                encode_reverse_to_slice::<false>(input, &mut buffer).as_deref(),
+                Ok(&*encode::<false>(&reversed))
+            );
+        }
+    }
+
+    #[test]
+    fn encode_trimmed_matches_encode() {
+        let input: std::vec::Vec<u8> = crate::test_util::bytes(FMT_BUFFER_LEN * 2 + 3)
+            .into_iter()
+            .map(|byte| byte | 1)
+            .collect();
+
+        for zeros in [0, 1, 2, FMT_BUFFER_LEN] {
+            for first in [0x01, 0x0f, 0x10, 0xff] {
+                for len in [0, 1, FMT_BUFFER_LEN / 2, input.len()] {
+                    let mut padded = std::vec![0; zeros];
+                    padded.push(first);
+                    padded.extend_from_slice(&input[..len]);
+
+                    let output = encode_trimmed::<false>(&padded);
+                    let expected = encode::<false>(&padded[zeros..]);
+                    assert_eq!(output, expected.strip_prefix('0').unwrap_or(&expected));
+                    assert_eq!(output.capacity(), output.len());
+                }
+            }
+
+            assert_eq!(encode_trimmed::<false>(&std::vec![0; zeros]), "0");
+        }
+    }
+
+    #[test]
+    fn encode_swapped_matches_encode() {
+        let input = crate::test_util::bytes(FMT_BUFFER_LEN * 2 + 3);
+
+        for len in [
+            0,
+            1,
+            2,
+            FMT_BUFFER_LEN / 2,
+            FMT_BUFFER_LEN / 2 + 1,
+            input.len(),
+        ] {
+            let input = &input[..len];
+            let swapped: std::vec::Vec<u8> = input.iter().map(|byte| byte.rotate_left(4)).collect();
+
+            let output = encode_swapped::<false, false>(input);
+            assert_eq!(output, encode::<false>(&swapped));
+            assert_eq!(output.capacity(), output.len());
+
+            let output = encode_swapped::<false, true>(input);
+            assert_eq!(output, encode::<true>(&swapped));
+            assert_eq!(output.capacity(), output.len());
+
+            let mut filled = input.to_vec();
+            filled.extend([0xf7]);
+
+            let output = encode_swapped::<true, false>(&filled);
+            assert_eq!(output, std::format!("{}7", encode::<false>(&swapped)));
+            assert_eq!(output.capacity(), output.len());
+
+            let output = encode_swapped::<true, true>(&filled);
+            assert_eq!(output, std::format!("{}7", encode::<true>(&swapped)));
+            assert_eq!(output.capacity(), output.len());
+        }
+    }
+
+    #[test]
+    fn encode_prefixed_matches_encode() {
+        for len in 0..40 {
+            let input = crate::test_util::bytes(len);
+
+            let expected = std::format!("0x{}", encode::<true>(&input));
+
+            let output = encode_prefixed::<true>(&input);
+            assert_eq!(output, expected);
+            assert_eq!(output.capacity(), output.len());
+
+            let mut storage = std::vec![0; expected.len()];
+            assert_eq!(
+                encode_prefixed_to_slice::<true>(&input, &mut storage),
+                Ok(expected.as_str())
+            );
+
+            // Too small and too big both fail, without writing anything.
+            for len in [expected.len() - 1, expected.len() + 1] {
+                let mut storage = std::vec![0; len];
+                assert!(encode_prefixed_to_slice::<true>(&input, &mut storage).is_err());
+                assert!(storage.iter().all(|&it| it == 0));
+            }
+        }
+    }
+
+    #[test]
+    fn encode_vec_matches_encode() {
+        for len in 0..40 {
+            let input = crate::test_util::bytes(len);
+
+            let mut in_place = std::vec::Vec::with_capacity(len * 2);
+            in_place.extend_from_slice(&input);
+            let ptr = in_place.as_ptr();
+
+            let output = encode_vec::<false>(in_place);
+            assert_eq!(output, encode::<false>(&input));
+            if len > 0 {
+                assert_eq!(output.as_ptr(), ptr, "should have reused the allocation");
+            }
+
+            assert_eq!(encode_vec::<true>(input.clone()), encode::<true>(&input));
+        }
+    }
+
+    #[test]
+    fn encode_vectored_matches_encode() {
+        let input = crate::test_util::bytes(40);
+
+        // Split the input up at a few different points, including empty segments.
+        for split in 0..=input.len() {
+            let (head, tail) = input.split_at(split);
+            let segments = [head, &[], tail];
+
+            assert_eq!(
+                encode_vectored::<_, false>(&segments),
+                encode::<false>(&input)
+            );
+
+            let mut output = std::vec![0; input.len() * 2];
+            assert_eq!(
+                encode_vectored_to_slice::<_, true>(&segments, &mut output),
+                Ok(encode::<true>(&input).as_str())
+            );
+            assert!(encode_vectored_to_slice::<_, true>(&segments, &mut output[1..]).is_err());
+        }
+    }
+
+    #[test]
+    fn encode_vectored_inconsistent_as_ref() {
+        /// Gets longer every time it's looked at.
+        struct Growing(core::cell::Cell<usize>);
+
+        impl AsRef<[u8]> for Growing {
+            fn as_ref(&self) -> &[u8] {
+                self.0.set(self.0.get() + 1);
+                &[0xaa; 4][..self.0.get()]
+            }
+        }
+
+        let inputs = [Growing(core::cell::Cell::new(0))];
+        let mut output = [0; 2];
+
+        assert!(encode_vectored_to_slice::<_, false>(&inputs, &mut output).is_err());
+
+        // Measured as 1 byte, then encoded as 2.
+        let inputs = [Growing(core::cell::Cell::new(0))];
+        assert_eq!(encode_vectored::<_, false>(&inputs), "aaaa");
+    }
+
+    #[test]
+    fn encode_chunked_matches_encode() {
+        const CHUNK_LEN: usize = FMT_BUFFER_LEN / 2;
+
+        for len in [
+            0,
+            1,
+            CHUNK_LEN - 1,
+            CHUNK_LEN,
+            CHUNK_LEN + 1,
+            CHUNK_LEN * 3 + 7,
+        ] {
+            let input = crate::test_util::bytes(len);
+
+            let mut output = std::string::String::new();
+            let result = encode_chunked::<(), FMT_BUFFER_LEN, false>(&input, |chunk| {
+                output.push_str(chunk);
+                Ok(())
+            });
+
+            assert_eq!(result, Ok(()));
+            assert_eq!(output, encode::<false>(&input));
+        }
+    }
+
+    #[test]
+    fn encode_chunked_stops_at_error() {
+        let input = [0; FMT_BUFFER_LEN * 2];
+        let mut calls = 0;
+
+        let result = encode_chunked::<_, FMT_BUFFER_LEN, false>(&input, |_| {
+            calls += 1;
+            Err(())
+        });
+
+        assert_eq!(result, Err(()));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn eq_hex_matches_encode() {
+        for byte in u8::MIN..=u8::MAX {
+            let lower = encode::<false>(&[byte]);
+            let upper = encode::<true>(&[byte]);
+
+            for high in u8::MIN..=u8::MAX {
+                let pair = [high, lower.as_bytes()[1]];
+                let expected = high == lower.as_bytes()[0] || high == upper.as_bytes()[0];
+
+                assert_eq!(eq_hex(&[byte], &pair), expected, "{byte:#x}, {high:#x}");
+            }
+
+            assert!(eq_hex(
+                &[byte, byte],
+                std::format!("{lower}{upper}").as_bytes()
+            ));
+        }
+    }
 }
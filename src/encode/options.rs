@@ -0,0 +1,464 @@
+use core::num::NonZeroUsize;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use super::Error;
+use crate::output;
+
+/// How many bytes of input [`EncodeOptions::encode_to_fmt`] (and [`EncodeOptions::encode_to_writer`]) encode at a time.
+const FMT_CHUNK_LEN: usize = 64;
+
+/// Encoding to formatted hex (MAC addresses, dumps, config files, ...), without having to post-process the string.
+///
+/// By default, this encodes exactly like [`encode`](crate::encode).
+///
+/// # Examples
+/// ```
+/// use hector::EncodeOptions;
+///
+/// let mac = EncodeOptions::new().uppercase(true).separator(b':');
+/// assert_eq!(mac.encode([0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6]), "00:1B:63:84:45:E6");
+///
+/// let words = EncodeOptions::new().prefix(true).separator(b' ').group_size(2);
+/// assert_eq!(words.encode([0xde, 0xad, 0xbe, 0xef, 0x01]), "0xdead beef 01");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct EncodeOptions {
+    upper: bool,
+    prefix: bool,
+    separator: Option<u8>,
+    group: NonZeroUsize,
+    wrap: Option<NonZeroUsize>,
+}
+
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EncodeOptions {
+    /// Options that encode exactly like [`encode`](crate::encode).
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            upper: false,
+            prefix: false,
+            separator: None,
+            group: NonZeroUsize::MIN,
+            wrap: None,
+        }
+    }
+
+    /// Whether to use uppercase hex characters.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::EncodeOptions::new().uppercase(true);
+    ///
+    /// assert_eq!(options.encode([0xde, 0xca, 0xff]), "DECAFF");
+    /// ```
+    #[must_use]
+    pub const fn uppercase(mut self, upper: bool) -> Self {
+        self.upper = upper;
+        self
+    }
+
+    /// Whether to start the output with `0x`.
+    ///
+    /// The prefix is written even when the input is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::EncodeOptions::new().prefix(true);
+    ///
+    /// assert_eq!(options.encode([0xde, 0xca, 0xff]), "0xdecaff");
+    /// assert_eq!(options.encode([]), "0x");
+    /// ```
+    #[must_use]
+    pub const fn prefix(mut self, prefix: bool) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Write `separator` between every group of bytes.
+    ///
+    /// # Panics
+    /// If `separator` isn't ascii (the output has to be a valid string).
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::EncodeOptions::new().separator(b'-');
+    ///
+    /// assert_eq!(options.encode([0xde, 0xca, 0xff]), "de-ca-ff");
+    /// ```
+    #[must_use]
+    pub const fn separator(mut self, separator: u8) -> Self {
+        assert!(separator.is_ascii(), "the separator must be ascii");
+
+        self.separator = Some(separator);
+        self
+    }
+
+    /// How many bytes go between each [`separator`](Self::separator), `1` by default.
+    ///
+    /// # Panics
+    /// If `size` is `0`.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::EncodeOptions::new().separator(b' ').group_size(4);
+    ///
+    /// assert_eq!(options.encode(b"Hello, world"), "48656c6c 6f2c2077 6f726c64");
+    /// ```
+    #[must_use]
+    pub const fn group_size(mut self, size: usize) -> Self {
+        self.group = match NonZeroUsize::new(size) {
+            Some(size) => size,
+            None => panic!("the group size must be at least 1"),
+        };
+
+        self
+    }
+
+    /// Start a new line after every `width` bytes.
+    ///
+    /// The newline replaces the separator at the end of each line, there's no newline at the end of the output.
+    ///
+    /// # Panics
+    /// If `width` is `0`.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::EncodeOptions::new().separator(b' ').wrap(4);
+    ///
+    /// assert_eq!(options.encode(b"Hello, world"), "48 65 6c 6c\n6f 2c 20 77\n6f 72 6c 64");
+    /// ```
+    ///
+    /// Wrapping works the same with every sink, so large blobs can be streamed into fixture files:
+    /// ```
+    /// let options = hector::EncodeOptions::new().wrap(32);
+    /// let blob = [0xab; 80];
+    ///
+    /// let mut fixture = Vec::new();
+    /// options.encode_to_writer(blob, &mut fixture)?;
+    ///
+    /// let lines: Vec<&[u8]> = fixture.split(|&it| it == b'\n').collect();
+    /// assert_eq!(lines.iter().map(|line| line.len()).collect::<Vec<_>>(), [64, 64, 32]);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[must_use]
+    pub const fn wrap(mut self, width: usize) -> Self {
+        self.wrap = match NonZeroUsize::new(width) {
+            Some(width) => Some(width),
+            None => panic!("the wrap width must be at least 1"),
+        };
+
+        self
+    }
+
+    /// The exact length of the output for `input_len` bytes of input.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::EncodeOptions::new().prefix(true).separator(b':');
+    ///
+    /// assert_eq!(options.encoded_len(3), "0xde:ca:ff".len());
+    /// assert_eq!(options.encoded_len(0), "0x".len());
+    /// ```
+    #[must_use]
+    pub fn encoded_len(&self, input_len: usize) -> usize {
+        self.prefix_len() + input_len * 2 + self.delimiters_before(input_len)
+    }
+
+    /// Encode `input` with these options.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::EncodeOptions::new().separator(b',');
+    ///
+    /// assert_eq!(options.encode([0xde, 0xca, 0xff]), "de,ca,ff");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn encode<T: AsRef<[u8]>>(&self, input: T) -> String {
+        let input = input.as_ref();
+
+        let mut output = alloc::vec![0; self.encoded_len(input.len())];
+        self.write(input, &mut output);
+
+        output::hex_string(output)
+    }
+
+    /// Encode `input` into `output` with these options.
+    ///
+    /// For convenience, this returns a [`&str`](str), backed by `output`.
+    ///
+    /// # Errors
+    /// - [`EncodeError`](crate::EncodeError) if `output.len() != self.encoded_len(input.len())`.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::EncodeOptions::new().separator(b':');
+    /// let mut storage = [0; 8];
+    ///
+    /// assert_eq!(options.encode_to_slice([0xde, 0xca, 0xff], &mut storage), Ok("de:ca:ff"));
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn encode_to_slice<'a, T: AsRef<[u8]>>(
+        &self,
+        input: T,
+        output: &'a mut [u8],
+    ) -> Result<&'a str, Error> {
+        let input = input.as_ref();
+
+        if output.len() != self.encoded_len(input.len()) {
+            return Err(Error);
+        }
+
+        self.write(input, output);
+
+        Ok(output::hex_str_mut(output))
+    }
+
+    /// Encode `input` into a [`fmt::Write`](core::fmt::Write) with these options, without allocating.
+    ///
+    /// # Errors
+    /// - Any error that `output` returns.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::EncodeOptions::new().uppercase(true).separator(b' ');
+    /// let mut output = String::from("key: ");
+    ///
+    /// options.encode_to_fmt([0xde, 0xca, 0xff], &mut output)?;
+    /// assert_eq!(output, "key: DE CA FF");
+    /// # Ok::<(), core::fmt::Error>(())
+    /// ```
+    pub fn encode_to_fmt<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+        &self,
+        input: T,
+        output: &mut W,
+    ) -> core::fmt::Result {
+        self.encode_with(input.as_ref(), |chunk| output.write_str(chunk))
+    }
+
+    /// Encode `input` into an [`io::Write`](std::io::Write) with these options, without allocating.
+    ///
+    /// Since each chunk is written separately, `output` should be buffered if writes to it are expensive.
+    ///
+    /// # Errors
+    /// - Any [`io::Error`](std::io::Error) from writing to `output`.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::EncodeOptions::new().wrap(4);
+    /// let mut output = Vec::new();
+    ///
+    /// options.encode_to_writer(b"Hello, world", &mut output)?;
+    /// assert_eq!(output, b"48656c6c\n6f2c2077\n6f726c64");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn encode_to_writer<T: AsRef<[u8]>, W: std::io::Write + ?Sized>(
+        &self,
+        input: T,
+        output: &mut W,
+    ) -> std::io::Result<()> {
+        self.encode_with(input.as_ref(), |chunk| output.write_all(chunk.as_bytes()))
+    }
+
+    /// Encode `input` a chunk at a time on the stack, passing each piece to `write`.
+    fn encode_with<E>(
+        &self,
+        input: &[u8],
+        mut write: impl FnMut(&str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        // Each byte is at most 2 hex characters and a delimiter.
+        let mut buffer = [0; FMT_CHUNK_LEN * 3];
+
+        if self.prefix {
+            write("0x")?;
+        }
+
+        let result = input
+            .chunks(FMT_CHUNK_LEN)
+            .enumerate()
+            .try_for_each(|(index, chunk)| {
+                let start = index * FMT_CHUNK_LEN;
+                let len = chunk.len() * 2 + self.delimiters_before(start + chunk.len())
+                    - self.delimiters_before(start);
+
+                let buffer = &mut buffer[..len];
+                self.write_bytes(chunk, start, buffer);
+
+                write(output::hex_str(buffer))
+            });
+
+        #[cfg(feature = "zeroize")]
+        crate::scratch::wipe(&mut buffer[..]);
+
+        result
+    }
+
+    fn prefix_len(&self) -> usize {
+        if self.prefix {
+            2
+        } else {
+            0
+        }
+    }
+
+    /// The delimiter (separator or newline) that goes before the byte at `index`, if any.
+    fn delimiter(&self, index: usize) -> Option<u8> {
+        if index == 0 {
+            return None;
+        }
+
+        match (self.wrap, self.separator) {
+            (Some(width), _) if index % width == 0 => Some(b'\n'),
+            (_, Some(separator)) if index % self.group == 0 => Some(separator),
+            _ => None,
+        }
+    }
+
+    /// The number of delimiters that go before the bytes at `1..end`.
+    fn delimiters_before(&self, end: usize) -> usize {
+        // Every index in `1..end` that's a multiple of `n`.
+        let multiples = |n: NonZeroUsize| end.saturating_sub(1) / n;
+
+        let newlines = self.wrap.map_or(0, multiples);
+
+        let separators = match self.separator {
+            None => 0,
+            // Newlines take the place of separators, so the indexes that are multiples of both don't count.
+            Some(_) => {
+                let both = self
+                    .wrap
+                    .and_then(|width| lcm(self.group, width))
+                    .map_or(0, multiples);
+
+                multiples(self.group) - both
+            }
+        };
+
+        newlines + separators
+    }
+
+    /// Write the whole encoding of `input` to `output`, which must be exactly [`encoded_len`](Self::encoded_len).
+    fn write(&self, input: &[u8], output: &mut [u8]) {
+        let (prefix, output) = output.split_at_mut(self.prefix_len().min(output.len()));
+        prefix.copy_from_slice(&b"0x"[..prefix.len()]);
+
+        self.write_bytes(input, 0, output);
+    }
+
+    /// Write the encoding of `input`, which starts at byte `start` of the whole input,
+    /// to `output`, which must be exactly the right length.
+    fn write_bytes(&self, input: &[u8], start: usize, output: &mut [u8]) {
+        let mut output = output.iter_mut();
+        let mut push = |char| {
+            if let Some(output) = output.next() {
+                *output = char;
+            }
+        };
+
+        for (index, &byte) in (start..).zip(input) {
+            if let Some(delimiter) = self.delimiter(index) {
+                push(delimiter);
+            }
+
+            let [high, low] = if self.upper {
+                super::byte_to_hex::<true>(byte)
+            } else {
+                super::byte_to_hex::<false>(byte)
+            };
+
+            push(high);
+            push(low);
+        }
+    }
+}
+
+/// The least common multiple of `a` and `b`, or `None` if it doesn't fit in a `usize`.
+fn lcm(a: NonZeroUsize, b: NonZeroUsize) -> Option<NonZeroUsize> {
+    let (mut x, mut y) = (a.get(), b.get());
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+
+    // `x` is the gcd, which is non-zero and divides `a`.
+    (a.get() / x)
+        .checked_mul(b.get())
+        .and_then(NonZeroUsize::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EncodeOptions;
+
+    #[test]
+    fn default_matches_encode() {
+        for input in [&[][..], &[0xde, 0xca, 0xff], &[0; 200]] {
+            assert_eq!(EncodeOptions::new().encode(input), crate::encode(input));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn encoded_len_is_exact() {
+        let input = crate::test_util::bytes(150);
+
+        for prefix in [false, true] {
+            for separator in [None, Some(b':')] {
+                for group in 1..=6 {
+                    for wrap in [None, Some(1), Some(3), Some(4), Some(16), Some(67)] {
+                        let mut options = EncodeOptions::new().prefix(prefix).group_size(group);
+
+                        if let Some(separator) = separator {
+                            options = options.separator(separator);
+                        }
+
+                        if let Some(wrap) = wrap {
+                            options = options.wrap(wrap);
+                        }
+
+                        for len in [0, 1, 2, 3, 11, 12, 64, 65, 129, 150] {
+                            let input = &input[..len];
+                            let output = options.encode(input);
+                            assert_eq!(options.encoded_len(len), output.len(), "{options:?}");
+
+                            let mut fmt = std::string::String::new();
+                            assert_eq!(options.encode_to_fmt(input, &mut fmt), Ok(()));
+                            assert_eq!(fmt, output, "{options:?}");
+
+                            let mut writer = std::vec::Vec::new();
+                            assert!(options.encode_to_writer(input, &mut writer).is_ok());
+                            assert_eq!(writer, output.as_bytes(), "{options:?}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wrap_without_separator() {
+        let options = EncodeOptions::new().wrap(2).uppercase(true);
+
+        assert_eq!(options.encode([0xde, 0xca, 0xff]), "DECA\nFF");
+    }
+
+    #[test]
+    fn encode_to_slice_checks_len() {
+        let options = EncodeOptions::new().separator(b':');
+
+        assert!(options.encode_to_slice([0xde, 0xca], &mut [0; 4]).is_err());
+        assert!(options.encode_to_slice([0xde, 0xca], &mut [0; 6]).is_err());
+        assert_eq!(
+            options.encode_to_slice([0xde, 0xca], &mut [0; 5]),
+            Ok("de:ca")
+        );
+    }
+}
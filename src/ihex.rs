@@ -0,0 +1,447 @@
+//! Intel HEX, the text format most firmware images are shipped (and flashed) in.
+//!
+//! Each line is a [`Record`]: a `:`, then the byte count, 16-bit address, [record type](RecordType), data,
+//! and checksum, all in hex.
+//! Addresses past 64 KiB are reached with extended address records, which set the base that later addresses
+//! are relative to:
+//! ```text
+//! :020000040800F2
+//! :0C00000048656C6C6F2C20776F726C648C
+//! :00000001FF
+//! ```
+//!
+//! [`encode`] writes a whole image (at any 32-bit address), and [`records`] reads one a record at a time,
+//! checking each record's checksum as it goes.
+//!
+//! # Examples
+//! ```
+//! let text = hector::ihex::encode(0x0800_0000, b"Hello, world");
+//! assert_eq!(text, ":020000040800F2\n:0C00000048656C6C6F2C20776F726C648C\n:00000001FF\n");
+//!
+//! let segments = hector::ihex::decode(&text)?;
+//! assert_eq!(segments[0].address, 0x0800_0000);
+//! assert_eq!(segments[0].data, b"Hello, world");
+//! # Ok::<(), hector::ihex::ParseError>(())
+//! ```
+
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::scratch::Scratch;
+
+mod parse;
+
+#[cfg(feature = "alloc")]
+pub use parse::{decode, Segment};
+pub use parse::{records, ParseError, ParseErrorKind, Records};
+
+/// The most data a record can hold, since its byte count is a single byte.
+pub const MAX_DATA_LEN: usize = 255;
+
+/// How much data [`encode`] puts in each data record, the same as most other tools.
+const DATA_RECORD_LEN: usize = 16;
+
+/// The length of the longest record, `:`, then the byte count, address, type, data, and checksum.
+const MAX_RECORD_LEN: usize = 1 + (1 + 2 + 1 + MAX_DATA_LEN + 1) * 2;
+
+/// What a [`Record`] means.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RecordType {
+    /// Data, at the record's address (relative to the last extended address).
+    Data = 0x00,
+
+    /// The end of the file, after which anything else is ignored.
+    EndOfFile = 0x01,
+
+    /// A segment (in 16 byte paragraphs) that later addresses are relative to, for 20-bit addresses.
+    ExtendedSegmentAddress = 0x02,
+
+    /// The initial `CS:IP`, for 8086 style processors.
+    StartSegmentAddress = 0x03,
+
+    /// The upper 16 bits of later addresses, for 32-bit addresses.
+    ExtendedLinearAddress = 0x04,
+
+    /// The 32-bit address execution starts at.
+    StartLinearAddress = 0x05,
+}
+
+impl RecordType {
+    /// The record type with the code `value`, if there is one.
+    ///
+    /// # Examples
+    /// ```
+    /// use hector::ihex::RecordType;
+    ///
+    /// assert_eq!(RecordType::from_u8(0x04), Some(RecordType::ExtendedLinearAddress));
+    /// assert_eq!(RecordType::from_u8(0x06), None);
+    /// ```
+    #[must_use]
+    pub const fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0x00 => Some(Self::Data),
+            0x01 => Some(Self::EndOfFile),
+            0x02 => Some(Self::ExtendedSegmentAddress),
+            0x03 => Some(Self::StartSegmentAddress),
+            0x04 => Some(Self::ExtendedLinearAddress),
+            0x05 => Some(Self::StartLinearAddress),
+            _ => None,
+        }
+    }
+
+    /// The code of this record type, as it's written in a record.
+    #[must_use]
+    pub const fn as_u8(self) -> u8 {
+        self as u8
+    }
+
+    /// The length of the data that records of this type have, if they're fixed length.
+    const fn data_len(self) -> Option<usize> {
+        match self {
+            Self::Data => None,
+            Self::EndOfFile => Some(0),
+            Self::ExtendedSegmentAddress | Self::ExtendedLinearAddress => Some(2),
+            Self::StartSegmentAddress | Self::StartLinearAddress => Some(4),
+        }
+    }
+}
+
+/// A single Intel HEX record (one line of a file), which formats as its text form (in uppercase, without a newline).
+///
+/// # Examples
+/// ```
+/// use hector::ihex::{Record, RecordType};
+///
+/// let record = Record::new(RecordType::Data, 0x0100, b"hi");
+/// assert_eq!(record.to_string(), ":0201000068692C");
+/// assert_eq!(record.checksum(), 0x2c);
+///
+/// assert_eq!(":0201000068692C".parse(), Ok(record));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Record {
+    kind: RecordType,
+    address: u16,
+    /// The data is `data[..len]`, the rest is always zero (so the derives only compare the data).
+    data: [u8; MAX_DATA_LEN],
+    len: u8,
+}
+
+impl Record {
+    /// Create a record of type `kind`, at `address`, holding `data`.
+    ///
+    /// # Panics
+    /// If `data` is longer than [`MAX_DATA_LEN`].
+    #[must_use]
+    pub fn new(kind: RecordType, address: u16, data: &[u8]) -> Self {
+        assert!(
+            data.len() <= MAX_DATA_LEN,
+            "a record can only hold {MAX_DATA_LEN} bytes"
+        );
+
+        let mut storage = [0; MAX_DATA_LEN];
+        storage[..data.len()].copy_from_slice(data);
+
+        Self {
+            kind,
+            address,
+            data: storage,
+            len: data.len() as u8,
+        }
+    }
+
+    /// The end of file record (`:00000001FF`).
+    #[must_use]
+    pub fn end_of_file() -> Self {
+        Self::new(RecordType::EndOfFile, 0, &[])
+    }
+
+    /// An extended linear address record, setting the upper 16 bits of the addresses after it to `upper`.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(hector::ihex::Record::extended_linear_address(0x0800).to_string(), ":020000040800F2");
+    /// ```
+    #[must_use]
+    pub fn extended_linear_address(upper: u16) -> Self {
+        Self::new(RecordType::ExtendedLinearAddress, 0, &upper.to_be_bytes())
+    }
+
+    /// A start linear address record, for execution to start at `address`.
+    #[must_use]
+    pub fn start_linear_address(address: u32) -> Self {
+        Self::new(RecordType::StartLinearAddress, 0, &address.to_be_bytes())
+    }
+
+    /// The type of this record.
+    #[must_use]
+    pub fn kind(&self) -> RecordType {
+        self.kind
+    }
+
+    /// The address of this record (the lower 16 bits, see [`Records::base_address`] for the rest).
+    #[must_use]
+    pub fn address(&self) -> u16 {
+        self.address
+    }
+
+    /// The data that this record holds.
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        &self.data[..usize::from(self.len)]
+    }
+
+    /// The checksum of this record, the two's complement of the sum of every other byte in it.
+    #[must_use]
+    pub fn checksum(&self) -> u8 {
+        let [high, low] = self.address.to_be_bytes();
+
+        let sum = [self.len, high, low, self.kind.as_u8()]
+            .iter()
+            .chain(self.data())
+            .fold(0_u8, |sum, &byte| sum.wrapping_add(byte));
+
+        sum.wrapping_neg()
+    }
+
+    /// Parse a single record from `line` (without a line ending).
+    ///
+    /// # Errors
+    /// - [`ParseError`] if `line` isn't a valid record (including if its checksum doesn't match),
+    ///   the line is always 1.
+    ///
+    /// # Examples
+    /// ```
+    /// use hector::ihex::{ParseErrorKind, Record};
+    ///
+    /// let record = Record::parse(":00000001FF")?;
+    /// assert_eq!(record, Record::end_of_file());
+    ///
+    /// let error = Record::parse(":00000001FE").unwrap_err();
+    /// assert_eq!(error.kind, ParseErrorKind::ChecksumMismatch { expected: 0xff, found: 0xfe });
+    /// # Ok::<(), hector::ihex::ParseError>(())
+    /// ```
+    pub fn parse<T: AsRef<[u8]>>(line: T) -> Result<Self, ParseError> {
+        parse::parse_record(line.as_ref()).map_err(|(column, kind)| ParseError {
+            line: 1,
+            column,
+            kind,
+        })
+    }
+
+    /// The base address that this record sets for the records after it, if it's an extended address record.
+    fn base_address(&self) -> Option<u32> {
+        let upper = match *self.data() {
+            [high, low] => u32::from(u16::from_be_bytes([high, low])),
+            _ => return None,
+        };
+
+        match self.kind {
+            RecordType::ExtendedSegmentAddress => Some(upper << 4),
+            RecordType::ExtendedLinearAddress => Some(upper << 16),
+            _ => None,
+        }
+    }
+
+    /// Write the text form of this record into `buffer`, returning it.
+    fn encode_into<'a>(&self, buffer: &'a mut [u8; MAX_RECORD_LEN]) -> &'a str {
+        let [high, low] = self.address.to_be_bytes();
+        let header = [self.len, high, low, self.kind.as_u8()];
+
+        let checksum = [self.checksum()];
+        let bytes = header.iter().chain(self.data()).chain(&checksum);
+
+        buffer[0] = b':';
+        let mut len = 1;
+        for &byte in bytes {
+            buffer[len..][..2].copy_from_slice(&crate::encode::byte_to_hex::<true>(byte));
+            len += 2;
+        }
+
+        crate::output::hex_str(&buffer[..len])
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.encode_into(&mut [0; MAX_RECORD_LEN]))
+    }
+}
+
+impl fmt::Debug for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Record")
+            .field("kind", &self.kind)
+            .field("address", &self.address)
+            .field("data", &self.data())
+            .finish()
+    }
+}
+
+impl core::str::FromStr for Record {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        Self::parse(line)
+    }
+}
+
+/// The records that [`encode`] writes for `data` at `address`, ending with the end of file record.
+fn image_records(mut address: u32, data: &[u8]) -> impl Iterator<Item = Record> + '_ {
+    assert!(
+        u64::from(address) + data.len() as u64 <= 1 << 32,
+        "the image has to fit in 32-bit addresses"
+    );
+
+    let mut rest = data;
+    // The upper 16 bits start out as 0, so they only need to be set if they aren't.
+    let mut upper = 0;
+
+    let records = core::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+
+        let address_upper = (address >> 16) as u16;
+        if address_upper != upper {
+            upper = address_upper;
+            return Some(Record::extended_linear_address(upper));
+        }
+
+        // Records can't cross into the next 64 KiB, since their addresses would wrap around.
+        let room = 0x1_0000 - (address & 0xffff) as usize;
+        let len = rest.len().min(DATA_RECORD_LEN).min(room);
+        let (chunk, tail) = rest.split_at(len);
+
+        let record = Record::new(RecordType::Data, address as u16, chunk);
+        address = address.wrapping_add(len as u32);
+        rest = tail;
+
+        Some(record)
+    });
+
+    records.chain(core::iter::once(Record::end_of_file()))
+}
+
+/// Write the records for `data` at `address` to `write`, each followed by a newline.
+fn encode_with<E>(
+    address: u32,
+    data: &[u8],
+    mut write: impl FnMut(&str) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut buffer = Scratch([0; MAX_RECORD_LEN]);
+
+    for record in image_records(address, data) {
+        write(record.encode_into(&mut buffer.0))?;
+        write("\n")?;
+    }
+
+    Ok(())
+}
+
+/// Encode `data` as an Intel HEX image starting at `address`, with 16 bytes in each data record.
+///
+/// Extended linear address records are added wherever the upper 16 bits of the address change
+/// (including at the start, for addresses past 64 KiB), and the image ends with an end of file record.
+/// Every line ends with `\n`, and the output is allocated at exactly the right size up front.
+///
+/// # Panics
+/// If `data` doesn't fit in 32-bit addresses after `address`.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::ihex::encode(0x0100, b"hi"), ":0201000068692C\n:00000001FF\n");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode(address: u32, data: &[u8]) -> String {
+    let len = image_records(address, data)
+        .map(|record| 1 + (4 + record.data().len() + 1) * 2 + 1)
+        .sum();
+
+    crate::output::collect_string(len, |write| encode_with(address, data, write))
+}
+
+/// Write `data` to `output` as an Intel HEX image starting at `address`, see [`encode`].
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Panics
+/// If `data` doesn't fit in 32-bit addresses after `address`.
+///
+/// # Examples
+/// ```
+/// let mut output = String::new();
+///
+/// hector::ihex::encode_to_fmt(0xfffe, b"abc", &mut output).unwrap();
+/// assert_eq!(output, ":02FFFE0061623E\n:020000040001F9\n:01000000639C\n:00000001FF\n");
+/// ```
+pub fn encode_to_fmt<W: fmt::Write + ?Sized>(
+    address: u32,
+    data: &[u8],
+    output: &mut W,
+) -> fmt::Result {
+    encode_with(address, data, |line| output.write_str(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode, Record, RecordType, MAX_DATA_LEN};
+    use std::string::ToString;
+
+    #[test]
+    fn record_round_trips() {
+        let data: std::vec::Vec<u8> = (0..=MAX_DATA_LEN as u8).collect();
+
+        for len in [0, 1, 16, MAX_DATA_LEN] {
+            let record = Record::new(RecordType::Data, 0xbeef, &data[..len]);
+            let text = record.to_string();
+
+            assert_eq!(text.len(), 11 + len * 2);
+            assert_eq!(Record::parse(&text), Ok(record));
+            assert_eq!(Record::parse(text.to_ascii_lowercase()), Ok(record));
+        }
+    }
+
+    #[test]
+    fn known_records() {
+        // From the examples in Intel's spec.
+        assert_eq!(
+            Record::new(RecordType::ExtendedSegmentAddress, 0, &[0x12, 0x00]).to_string(),
+            ":020000021200EA"
+        );
+        assert_eq!(
+            Record::start_linear_address(0x0000_00cd).to_string(),
+            ":04000005000000CD2A"
+        );
+        assert_eq!(Record::end_of_file().to_string(), ":00000001FF");
+    }
+
+    #[test]
+    fn encode_splits_at_64k() {
+        let data = [0xaa; 40];
+        let text = encode(0x1_fff0, &data);
+
+        let lines: std::vec::Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                ":020000040001F9",
+                ":10FFF000AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA61",
+                ":020000040002F8",
+                ":10000000AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA50",
+                ":08001000AAAAAAAAAAAAAAAA98",
+                ":00000001FF",
+            ]
+        );
+        assert_eq!(text.capacity(), text.len());
+    }
+
+    #[test]
+    fn encode_empty() {
+        assert_eq!(encode(0x1234_5678, &[]), ":00000001FF\n");
+    }
+}
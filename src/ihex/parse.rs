@@ -0,0 +1,396 @@
+use core::fmt;
+use core::iter::FusedIterator;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use super::{Record, RecordType, MAX_DATA_LEN};
+use crate::scratch::Scratch;
+use crate::DecodeError;
+
+/// What went wrong while parsing an Intel HEX record.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The line didn't start with `:`.
+    MissingStartCode,
+
+    /// The character at the error's column was not a valid hex character.
+    InvalidHex {
+        /// The character in question.
+        value: u8,
+    },
+
+    /// The line was too short to be a record, or didn't have as much data as its byte count said.
+    InvalidLength,
+
+    /// The record's checksum didn't match its contents.
+    ChecksumMismatch {
+        /// The checksum that the record should have had.
+        expected: u8,
+
+        /// The checksum that the record had.
+        found: u8,
+    },
+
+    /// The record's type wasn't one of the [`RecordType`]s.
+    UnknownRecordType {
+        /// The type code in question.
+        value: u8,
+    },
+
+    /// The record had the wrong amount of data for its type (like an end of file record with data).
+    InvalidRecord,
+
+    /// The input ended without an end of file record.
+    MissingEndOfFile,
+}
+
+/// An error from parsing Intel HEX, and where in the input it was.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line (starting from 1) that the error occurred on.
+    pub line: usize,
+
+    /// The column (in bytes, starting from 1) that the error occurred at.
+    pub column: usize,
+
+    /// What went wrong.
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+
+        match self.kind {
+            ParseErrorKind::MissingStartCode => f.write_str("record doesn't start with `:`"),
+            ParseErrorKind::InvalidHex { value } => write!(
+                f,
+                "character `{char_value}` ({value:#04x}) is not a valid hex character",
+                char_value = value as char
+            ),
+            ParseErrorKind::InvalidLength => {
+                f.write_str("record's length doesn't match its byte count")
+            }
+            ParseErrorKind::ChecksumMismatch { expected, found } => {
+                write!(
+                    f,
+                    "expected checksum `{expected:#04x}`, found `{found:#04x}`"
+                )
+            }
+            ParseErrorKind::UnknownRecordType { value } => {
+                write!(f, "unknown record type `{value:#04x}`")
+            }
+            ParseErrorKind::InvalidRecord => {
+                f.write_str("record has the wrong length for its type")
+            }
+            ParseErrorKind::MissingEndOfFile => f.write_str("missing end of file record"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Parse a single record from `line`, on error, returns the column (starting from 1) and what went wrong.
+pub(super) fn parse_record(line: &[u8]) -> Result<Record, (usize, ParseErrorKind)> {
+    let Some((b':', hex)) = line.split_first() else {
+        return Err((1, ParseErrorKind::MissingStartCode));
+    };
+
+    // The hex starts at column 2, after the `:`.
+    let invalid_hex = |error| match error {
+        DecodeError::InvalidHex { offset, value } => {
+            (offset + 2, ParseErrorKind::InvalidHex { value })
+        }
+        _ => (2, ParseErrorKind::InvalidLength),
+    };
+
+    let len = match hex {
+        [high, low, ..] => {
+            usize::from(crate::decode::decode_pair([*high, *low], 0).map_err(invalid_hex)?)
+        }
+        _ => return Err((2, ParseErrorKind::InvalidLength)),
+    };
+
+    // The byte count, address, type, data, and checksum.
+    let mut bytes = Scratch([0; 1 + 2 + 1 + MAX_DATA_LEN + 1]);
+    let bytes = &mut bytes[..len + 5];
+    if hex.len() != bytes.len() * 2 {
+        return Err((2, ParseErrorKind::InvalidLength));
+    }
+
+    crate::decode_to_slice(hex, bytes).map_err(invalid_hex)?;
+
+    let [_, high, low, kind, ref data @ .., found] = *bytes else {
+        unreachable!("records are always at least 5 bytes");
+    };
+
+    let sum = bytes.iter().fold(0_u8, |sum, &byte| sum.wrapping_add(byte));
+    if sum != 0 {
+        return Err((
+            line.len() - 1,
+            ParseErrorKind::ChecksumMismatch {
+                expected: found.wrapping_sub(sum),
+                found,
+            },
+        ));
+    }
+
+    let Some(kind) = RecordType::from_u8(kind) else {
+        return Err((8, ParseErrorKind::UnknownRecordType { value: kind }));
+    };
+
+    if kind.data_len().is_some_and(|expected| expected != len) {
+        return Err((2, ParseErrorKind::InvalidRecord));
+    }
+
+    Ok(Record::new(kind, u16::from_be_bytes([high, low]), data))
+}
+
+/// Iterate over the records in `input`, a record per line, see [`Records`].
+///
+/// # Examples
+/// ```
+/// use hector::ihex::RecordType;
+///
+/// let text = ":020000040800F2\n:0201000068692C\n:00000001FF\n";
+///
+/// let mut records = hector::ihex::records(text);
+/// assert_eq!(records.next().unwrap()?.kind(), RecordType::ExtendedLinearAddress);
+///
+/// let record = records.next().unwrap()?;
+/// assert_eq!(records.base_address() + u32::from(record.address()), 0x0800_0100);
+/// assert_eq!(record.data(), b"hi");
+///
+/// assert_eq!(records.next().unwrap()?.kind(), RecordType::EndOfFile);
+/// assert_eq!(records.next(), None);
+/// # Ok::<(), hector::ihex::ParseError>(())
+/// ```
+#[must_use]
+pub fn records<T: AsRef<[u8]> + ?Sized>(input: &T) -> Records<'_> {
+    Records {
+        rest: Some(input.as_ref()),
+        line: 0,
+        base_address: 0,
+    }
+}
+
+/// An iterator over the records in Intel HEX text, checking each as it goes.
+///
+/// Lines can end with `\n` or `\r\n`, and blank lines are skipped.
+/// Iteration stops after the end of file record (ignoring anything after it), or the first error,
+/// and input that ends without an end of file record is an error ([`ParseErrorKind::MissingEndOfFile`]).
+///
+/// Created with [`records`].
+#[derive(Debug, Clone)]
+pub struct Records<'a> {
+    /// The input after the last line read, `None` once iteration is over.
+    rest: Option<&'a [u8]>,
+    /// The number of the last line read.
+    line: usize,
+    base_address: u32,
+}
+
+impl Records<'_> {
+    /// The address that the addresses of data records are relative to,
+    /// as set by the last extended address record so far (or 0, if there hasn't been one).
+    #[must_use]
+    pub fn base_address(&self) -> u32 {
+        self.base_address
+    }
+}
+
+impl Iterator for Records<'_> {
+    type Item = Result<Record, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut rest = self.rest?;
+
+        let line = loop {
+            self.line += 1;
+
+            if rest.is_empty() {
+                self.rest = None;
+
+                return Some(Err(ParseError {
+                    line: self.line,
+                    column: 1,
+                    kind: ParseErrorKind::MissingEndOfFile,
+                }));
+            }
+
+            let (line, tail) = match rest.iter().position(|&it| it == b'\n') {
+                Some(end) => (&rest[..end], &rest[end + 1..]),
+                None => (rest, &[][..]),
+            };
+
+            rest = tail;
+
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if !line.is_empty() {
+                break line;
+            }
+        };
+
+        let record = match parse_record(line) {
+            Ok(record) => record,
+            Err((column, kind)) => {
+                self.rest = None;
+
+                return Some(Err(ParseError {
+                    line: self.line,
+                    column,
+                    kind,
+                }));
+            }
+        };
+
+        self.rest = (record.kind() != RecordType::EndOfFile).then_some(rest);
+
+        if let Some(base_address) = record.base_address() {
+            self.base_address = base_address;
+        }
+
+        Some(Ok(record))
+    }
+}
+
+impl FusedIterator for Records<'_> {}
+
+/// A contiguous run of data from an Intel HEX image.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Segment {
+    /// The address of the first byte of `data`.
+    pub address: u32,
+
+    /// The data.
+    pub data: Vec<u8>,
+}
+
+/// Decode the Intel HEX image in `input` into the segments of data it holds, in the order they appear.
+///
+/// Data records that follow on directly from the one before them are merged into one segment,
+/// and the start address records are ignored.
+///
+/// # Errors
+/// - [`ParseError`] if a record is malformed, or `input` ends without an end of file record.
+///
+/// # Examples
+/// ```
+/// use hector::ihex::Segment;
+///
+/// let text = "\
+///     :0200000068692D\n\
+///     :0100020021DC\n\
+///     :020000021000EC\n\
+///     :0100000000FF\n\
+///     :00000001FF\n";
+///
+/// assert_eq!(
+///     hector::ihex::decode(text)?,
+///     [
+///         Segment { address: 0, data: b"hi!".to_vec() },
+///         Segment { address: 0x1_0000, data: vec![0] },
+///     ]
+/// );
+/// # Ok::<(), hector::ihex::ParseError>(())
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode<T: AsRef<[u8]> + ?Sized>(input: &T) -> Result<Vec<Segment>, ParseError> {
+    let mut segments: Vec<Segment> = Vec::new();
+    let mut records = records(input);
+
+    while let Some(record) = records.next() {
+        let record = record?;
+        if record.kind() != RecordType::Data {
+            continue;
+        }
+
+        let address = records
+            .base_address()
+            .wrapping_add(u32::from(record.address()));
+
+        match segments.last_mut() {
+            Some(segment) if segment.address.wrapping_add(segment.data.len() as u32) == address => {
+                segment.data.extend_from_slice(record.data());
+            }
+            _ => segments.push(Segment {
+                address,
+                data: record.data().to_vec(),
+            }),
+        }
+    }
+
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, records, ParseError, ParseErrorKind};
+    use crate::ihex::encode;
+
+    #[test]
+    fn decode_round_trips() {
+        let data = crate::test_util::bytes(1000);
+
+        for address in [0, 0x10, 0xfff0, 0x0800_0000, 0xffff_ffff - 1000] {
+            let segments = decode(&encode(address, &data)).unwrap();
+
+            assert_eq!(segments.len(), 1);
+            assert_eq!(segments[0].address, address);
+            assert_eq!(segments[0].data, data);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let error = |line, column, kind| Some(Err(ParseError { line, column, kind }));
+
+        assert_eq!(
+            records("0201000068692C").next(),
+            error(1, 1, ParseErrorKind::MissingStartCode)
+        );
+        assert_eq!(
+            records("\r\n:02010000686g2C").next(),
+            error(2, 13, ParseErrorKind::InvalidHex { value: b'g' })
+        );
+        assert_eq!(
+            records(":0201000068692").next(),
+            error(1, 2, ParseErrorKind::InvalidLength)
+        );
+        assert_eq!(
+            records(":020100006869FF").next(),
+            error(
+                1,
+                14,
+                ParseErrorKind::ChecksumMismatch {
+                    expected: 0x2c,
+                    found: 0xff
+                }
+            )
+        );
+        assert_eq!(
+            records(":00000006FA").next(),
+            error(1, 8, ParseErrorKind::UnknownRecordType { value: 6 })
+        );
+        assert_eq!(
+            records(":0100000100FE").next(),
+            error(1, 2, ParseErrorKind::InvalidRecord)
+        );
+
+        let mut iter = records(":0201000068692C\n");
+        assert!(iter.next().unwrap().is_ok());
+        assert_eq!(iter.next(), error(2, 1, ParseErrorKind::MissingEndOfFile));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn stops_at_end_of_file() {
+        let mut iter = records(":00000001FF\nnot a record");
+
+        assert!(iter.next().unwrap().is_ok());
+        assert_eq!(iter.next(), None);
+    }
+}
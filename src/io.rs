@@ -0,0 +1,586 @@
+//! Adapters for hex encoding and decoding [`std::io`] streams.
+
+use std::boxed::Box;
+use std::io::{self, Read, Write};
+use std::vec;
+
+use crate::scratch::Scratch;
+use crate::DecodeError;
+
+/// The size of the internal buffers of the adapters in this module.
+pub(crate) const BUFFER_LEN: usize = 8 * 1024;
+
+/// The writers here only give up their inner writer in `into_inner`, which consumes them.
+pub(crate) const TAKEN: &str = "the inner writer is only taken by `into_inner`";
+
+/// Convert a [`DecodeError`] into an [`io::Error`], running out of input halfway through a byte is an unexpected EOF.
+pub(crate) fn decode_error_to_io(err: DecodeError) -> io::Error {
+    let kind = match err {
+        DecodeError::OddLength => io::ErrorKind::UnexpectedEof,
+        _ => io::ErrorKind::InvalidData,
+    };
+
+    io::Error::new(kind, err)
+}
+
+/// A reader that decodes the hex text read from an inner reader.
+///
+/// This does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// Decoding errors are returned from [`read`](Read::read) as an [`io::Error`] wrapping a [`DecodeError`],
+/// with offsets counted from the start of the stream.
+/// - [`InvalidData`](io::ErrorKind::InvalidData) for [`DecodeError::InvalidHex`].
+/// - [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) for [`DecodeError::OddLength`],
+///   if the stream ends in the middle of a byte.
+///
+/// Any bytes before an invalid character are returned first, and the error is returned again on every read after it.
+///
+/// # Examples
+/// ```
+/// use std::io::Read;
+///
+/// let mut reader = hector::io::HexDecodeReader::new(&b"decaff"[..]);
+/// let mut output = Vec::new();
+///
+/// reader.read_to_end(&mut output).unwrap();
+///
+/// assert_eq!(output, [0xde, 0xca, 0xff]);
+/// ```
+///
+/// ```
+/// use std::io::Read;
+/// use hector::DecodeError;
+///
+/// let mut reader = hector::io::HexDecodeReader::new(&b"decafg"[..]);
+/// let mut output = Vec::new();
+///
+/// let err = reader.read_to_end(&mut output).unwrap_err();
+///
+/// assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+/// assert_eq!(
+///     err.into_inner().unwrap().downcast_ref::<DecodeError>(),
+///     Some(&DecodeError::InvalidHex { offset: 5, value: b'g' })
+/// );
+/// assert_eq!(output, [0xde, 0xca]);
+/// ```
+pub struct HexDecodeReader<R> {
+    inner: R,
+    /// Hex text that's been read from `inner` but not decoded yet is `buffer[start..end]`.
+    buffer: Scratch<Box<[u8]>>,
+    start: usize,
+    end: usize,
+    /// The offset of `buffer[start]` in the stream.
+    offset: usize,
+}
+
+impl<R: Read> HexDecodeReader<R> {
+    /// Create a new reader, decoding the hex text read from `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buffer: Scratch(vec![0; BUFFER_LEN].into_boxed_slice()),
+            start: 0,
+            end: 0,
+            offset: 0,
+        }
+    }
+}
+
+impl<R> HexDecodeReader<R> {
+    /// Get a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner reader.
+    ///
+    /// Reading from it directly will skip past hex text that this reader hasn't seen yet.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwrap this reader, returning the inner reader.
+    ///
+    /// Any hex text that has been read from the inner reader but not decoded yet is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for HexDecodeReader<R> {
+    fn read(&mut self, output: &mut [u8]) -> io::Result<usize> {
+        if output.is_empty() {
+            return Ok(0);
+        }
+
+        // Make sure there's at least one whole byte's worth of hex buffered.
+        while self.end - self.start < 2 {
+            // A pair can straddle refills, so keep the leftover character around.
+            self.buffer.copy_within(self.start..self.end, 0);
+            self.end -= self.start;
+            self.start = 0;
+
+            let read = self.inner.read(&mut self.buffer[self.end..])?;
+
+            if read == 0 {
+                return match self.end {
+                    0 => Ok(0),
+                    _ => Err(decode_error_to_io(DecodeError::OddLength)),
+                };
+            }
+
+            self.end += read;
+        }
+
+        let len = ((self.end - self.start) / 2).min(output.len());
+        let input = &self.buffer[self.start..][..len * 2];
+
+        let len = match crate::decode_to_slice(input, &mut output[..len]) {
+            Ok(_) => len,
+            Err(DecodeError::InvalidHex { offset, .. }) if offset >= 2 => {
+                // Hand out the bytes before the error first, the error shows up on the next read.
+                let len = offset / 2;
+                crate::decode_to_slice(&input[..len * 2], &mut output[..len])
+                    .map_err(decode_error_to_io)?;
+                len
+            }
+            Err(err) => return Err(decode_error_to_io(err.offset_by(self.offset))),
+        };
+
+        self.start += len * 2;
+        self.offset += len * 2;
+
+        Ok(len)
+    }
+}
+
+impl<R: core::fmt::Debug> core::fmt::Debug for HexDecodeReader<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HexDecodeReader")
+            .field("inner", self.get_ref())
+            .field("buffered", &(self.end - self.start))
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+/// A writer that hex encodes everything written to it before passing it on to an inner writer.
+///
+/// The encoded text is staged in an internal buffer, and only written to the inner writer when the buffer fills up,
+/// on [`flush`](Write::flush), or when this is dropped (where errors are ignored, like [`BufWriter`](io::BufWriter)).
+///
+/// # Examples
+/// ```
+/// use std::io::Write;
+///
+/// let mut writer = hector::io::HexEncodeWriter::new(Vec::new());
+///
+/// writer.write_all(&[0xde, 0xca]).unwrap();
+/// writer.write_all(b"\xff").unwrap();
+///
+/// assert_eq!(writer.into_inner().unwrap(), b"decaff");
+/// ```
+pub struct HexEncodeWriter<W: Write> {
+    /// Only `None` once [`into_inner`](Self::into_inner) has taken it, when there's nothing left to write.
+    inner: Option<W>,
+    /// Encoded text that hasn't been written to `inner` yet is `buffer[..len]`.
+    buffer: Scratch<Box<[u8]>>,
+    len: usize,
+    upper: bool,
+}
+
+impl<W: Write> HexEncodeWriter<W> {
+    /// Create a new writer, writing lowercase hex to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self::with_case(inner, false)
+    }
+
+    /// Create a new writer, writing uppercase hex to `inner`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::io::Write;
+    ///
+    /// let mut writer = hector::io::HexEncodeWriter::new_upper(Vec::new());
+    ///
+    /// writer.write_all(&[0xde, 0xca, 0xff]).unwrap();
+    ///
+    /// assert_eq!(writer.into_inner().unwrap(), b"DECAFF");
+    /// ```
+    pub fn new_upper(inner: W) -> Self {
+        Self::with_case(inner, true)
+    }
+
+    fn with_case(inner: W, upper: bool) -> Self {
+        Self {
+            inner: Some(inner),
+            buffer: Scratch(vec![0; BUFFER_LEN].into_boxed_slice()),
+            len: 0,
+            upper,
+        }
+    }
+
+    /// Get a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect(TAKEN)
+    }
+
+    /// Get a mutable reference to the inner writer.
+    ///
+    /// Writing to it directly will put the data before any encoded text that's still buffered.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.as_mut().expect(TAKEN)
+    }
+
+    /// Write all the buffered text to the inner writer, and then unwrap this writer, returning the inner writer.
+    ///
+    /// # Errors
+    /// If writing the buffered text fails, the error is returned along with this writer (so nothing is lost).
+    pub fn into_inner(mut self) -> Result<W, (io::Error, Self)> {
+        if let Err(err) = self.flush_buffer() {
+            return Err((err, self));
+        }
+
+        Ok(self.inner.take().expect(TAKEN))
+    }
+
+    /// Write as much of the buffered text to the inner writer as possible,
+    /// an error leaves whatever wasn't written in the buffer.
+    fn flush_buffer(&mut self) -> io::Result<()> {
+        let Some(inner) = &mut self.inner else {
+            return Ok(());
+        };
+
+        let mut written = 0;
+
+        let result = loop {
+            if written == self.len {
+                break Ok(());
+            }
+
+            match inner.write(&self.buffer[written..self.len]) {
+                Ok(0) => break Err(io::ErrorKind::WriteZero.into()),
+                Ok(len) => written += len,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => break Err(err),
+            }
+        };
+
+        self.buffer.copy_within(written..self.len, 0);
+        self.len -= written;
+
+        result
+    }
+}
+
+impl<W: Write> Write for HexEncodeWriter<W> {
+    fn write(&mut self, input: &[u8]) -> io::Result<usize> {
+        if input.is_empty() {
+            return Ok(0);
+        }
+
+        if self.buffer.len() - self.len < 2 {
+            self.flush_buffer()?;
+        }
+
+        let len = input.len().min((self.buffer.len() - self.len) / 2);
+        let (input, output) = (&input[..len], &mut self.buffer[self.len..][..len * 2]);
+
+        // `output` is exactly the right length, so this can't fail.
+        let result = match self.upper {
+            false => crate::encode_to_slice(input, output),
+            true => crate::encode_to_slice_upper(input, output),
+        };
+        debug_assert!(result.is_ok());
+
+        self.len += len * 2;
+
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_buffer()?;
+        self.get_mut().flush()
+    }
+}
+
+impl<W: Write> Drop for HexEncodeWriter<W> {
+    fn drop(&mut self) {
+        // There's nowhere to report an error to, call `flush` first to see them.
+        drop(self.flush_buffer());
+    }
+}
+
+impl<W: Write + core::fmt::Debug> core::fmt::Debug for HexEncodeWriter<W> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HexEncodeWriter")
+            .field("inner", self.get_ref())
+            .field("buffered", &self.len)
+            .field("upper", &self.upper)
+            .finish()
+    }
+}
+
+/// Read everything from `reader`, and write its lowercase hex encoding to `writer` (like [`io::copy`]).
+///
+/// Returns the number of bytes read from `reader` (half the amount of text written).
+///
+/// # Errors
+/// - Any [`io::Error`] from reading `reader` or writing `writer`.
+///
+/// # Examples
+/// ```
+/// let mut output = Vec::new();
+///
+/// assert_eq!(hector::io::encode_copy(&mut &[0xde, 0xca, 0xff][..], &mut output).unwrap(), 3);
+/// assert_eq!(output, b"decaff");
+/// ```
+pub fn encode_copy<R: Read + ?Sized, W: Write + ?Sized>(
+    reader: &mut R,
+    writer: &mut W,
+) -> io::Result<u64> {
+    copy_to_encoder(reader, HexEncodeWriter::new(writer))
+}
+
+/// Read everything from `reader`, and write its uppercase hex encoding to `writer` (like [`io::copy`]).
+///
+/// Returns the number of bytes read from `reader` (half the amount of text written).
+///
+/// # Errors
+/// - Any [`io::Error`] from reading `reader` or writing `writer`.
+///
+/// # Examples
+/// ```
+/// let mut output = Vec::new();
+///
+/// assert_eq!(hector::io::encode_copy_upper(&mut &[0xde, 0xca, 0xff][..], &mut output).unwrap(), 3);
+/// assert_eq!(output, b"DECAFF");
+/// ```
+pub fn encode_copy_upper<R: Read + ?Sized, W: Write + ?Sized>(
+    reader: &mut R,
+    writer: &mut W,
+) -> io::Result<u64> {
+    copy_to_encoder(reader, HexEncodeWriter::new_upper(writer))
+}
+
+fn copy_to_encoder<R: Read + ?Sized, W: Write>(
+    reader: &mut R,
+    mut encoder: HexEncodeWriter<W>,
+) -> io::Result<u64> {
+    let len = io::copy(reader, &mut encoder)?;
+
+    // Don't leave anything buffered (where errors would get ignored on drop).
+    encoder.into_inner().map_err(|(err, _)| err)?;
+
+    Ok(len)
+}
+
+/// Read hex text from `reader` until EOF, and write the decoded bytes to `writer` (like [`io::copy`]).
+///
+/// This does _not_ enforce a specific casing convention.
+///
+/// Returns the number of bytes written to `writer` (half the amount of text read).
+///
+/// # Errors
+/// - Any [`io::Error`] from reading `reader` or writing `writer`.
+/// - Decoding errors, like [`HexDecodeReader`].
+///
+/// # Examples
+/// ```
+/// let mut output = Vec::new();
+///
+/// assert_eq!(hector::io::decode_copy(&mut &b"decaff"[..], &mut output).unwrap(), 3);
+/// assert_eq!(output, [0xde, 0xca, 0xff]);
+/// ```
+pub fn decode_copy<R: Read + ?Sized, W: Write + ?Sized>(
+    reader: &mut R,
+    writer: &mut W,
+) -> io::Result<u64> {
+    io::copy(&mut HexDecodeReader::new(reader), writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Read, Write};
+    use std::vec::Vec;
+
+    use super::{decode_copy, encode_copy, HexDecodeReader, HexEncodeWriter, BUFFER_LEN};
+    use crate::DecodeError;
+
+    /// A reader that hands out at most `max` bytes at a time, to make pairs straddle reads.
+    struct Trickle<'a> {
+        input: &'a [u8],
+        max: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, output: &mut [u8]) -> io::Result<usize> {
+            let len = self.input.len().min(output.len()).min(self.max);
+            output[..len].copy_from_slice(&self.input[..len]);
+            self.input = &self.input[len..];
+            Ok(len)
+        }
+    }
+
+    fn decode_err(err: &io::Error) -> Option<DecodeError> {
+        err.get_ref()?.downcast_ref().copied()
+    }
+
+    #[test]
+    fn decode_reader_matches_decode() {
+        let bytes = crate::test_util::bytes(BUFFER_LEN * 2);
+        let input = crate::encode(&bytes);
+
+        for max in [1, 3, 7, BUFFER_LEN - 1, BUFFER_LEN * 4] {
+            let mut reader = HexDecodeReader::new(Trickle {
+                input: input.as_bytes(),
+                max,
+            });
+
+            let mut output = Vec::new();
+            reader.read_to_end(&mut output).unwrap();
+
+            assert_eq!(output, bytes, "max: {max}");
+        }
+    }
+
+    #[test]
+    fn decode_reader_absolute_offset() {
+        let mut input = crate::encode([0; BUFFER_LEN]).into_bytes();
+        let offset = BUFFER_LEN + 3;
+        input[offset] = b'x';
+
+        let mut reader = HexDecodeReader::new(Trickle {
+            input: &input,
+            max: 5,
+        });
+
+        let mut output = Vec::new();
+        let err = reader.read_to_end(&mut output).unwrap_err();
+
+        assert_eq!(
+            decode_err(&err),
+            Some(DecodeError::InvalidHex {
+                offset,
+                value: b'x'
+            })
+        );
+        assert_eq!(output.len(), offset / 2);
+
+        // The error sticks around.
+        let err = reader.read(&mut [0; 4]).unwrap_err();
+        assert_eq!(
+            decode_err(&err),
+            Some(DecodeError::InvalidHex {
+                offset,
+                value: b'x'
+            })
+        );
+    }
+
+    #[test]
+    fn decode_reader_odd_length() {
+        let mut reader = HexDecodeReader::new(&b"decaf"[..]);
+
+        let mut output = Vec::new();
+        let err = reader.read_to_end(&mut output).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(decode_err(&err), Some(DecodeError::OddLength));
+        assert_eq!(output, [0xde, 0xca]);
+    }
+
+    /// A writer that accepts at most `max` bytes at a time, and fails every other write.
+    struct Flaky {
+        output: Vec<u8>,
+        max: usize,
+        fail: bool,
+    }
+
+    impl Write for Flaky {
+        fn write(&mut self, input: &[u8]) -> io::Result<usize> {
+            self.fail = !self.fail;
+            if self.fail {
+                return Err(io::ErrorKind::Other.into());
+            }
+
+            let len = input.len().min(self.max);
+            self.output.extend_from_slice(&input[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn encode_writer_matches_encode() {
+        let bytes = crate::test_util::bytes(BUFFER_LEN * 2 + 5);
+
+        let mut writer = HexEncodeWriter::new(Flaky {
+            output: Vec::new(),
+            max: 777,
+            fail: false,
+        });
+
+        // Retry around the failures, nothing should get lost or duplicated.
+        let mut input = &bytes[..];
+        while !input.is_empty() {
+            if let Ok(len) = writer.write(input) {
+                input = &input[len..];
+            }
+        }
+
+        let writer = loop {
+            match writer.into_inner() {
+                Ok(it) => break it,
+                Err((_, it)) => writer = it,
+            }
+        };
+
+        assert_eq!(writer.output, crate::encode(&bytes).into_bytes());
+    }
+
+    #[test]
+    fn encode_writer_flushes_on_drop() {
+        let mut output = Vec::new();
+
+        {
+            let mut writer = HexEncodeWriter::new_upper(&mut output);
+            writer.write_all(&[0xde, 0xca, 0xff]).unwrap();
+        }
+
+        assert_eq!(output, b"DECAFF");
+    }
+
+    #[test]
+    fn copy_round_trip() {
+        let bytes = crate::test_util::bytes(BUFFER_LEN * 3 + 1);
+
+        let mut encoded = Vec::new();
+        let len = encode_copy(
+            &mut Trickle {
+                input: &bytes,
+                max: 1000,
+            },
+            &mut encoded,
+        )
+        .unwrap();
+        assert_eq!(len, bytes.len() as u64);
+        assert_eq!(encoded, crate::encode(&bytes).into_bytes());
+
+        let mut decoded = Vec::new();
+        let len = decode_copy(
+            &mut Trickle {
+                input: &encoded,
+                max: 999,
+            },
+            &mut decoded,
+        )
+        .unwrap();
+        assert_eq!(len, bytes.len() as u64);
+        assert_eq!(decoded, bytes);
+    }
+}
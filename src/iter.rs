@@ -0,0 +1,327 @@
+//! Lazy iterator adapters, for encoding and decoding without allocating (or needing an output buffer).
+
+use core::iter::FusedIterator;
+
+use crate::decode::decode_pair;
+use crate::encode::byte_to_hex;
+use crate::DecodeError;
+
+/// Lazily encode `input` to lowercase hex, as ascii bytes.
+///
+/// # Examples
+/// ```
+/// assert!(hector::iter::encode(&[0xde, 0xca, 0xff]).eq(*b"decaff"));
+///
+/// // It works backwards too.
+/// assert!(hector::iter::encode(&[0xde, 0xca, 0xff]).rev().eq(*b"ffaced"));
+/// ```
+///
+/// ```
+/// let mut output = b"id: ".to_vec();
+/// output.extend(hector::iter::encode(b"Hello"));
+///
+/// assert_eq!(output, b"id: 48656c6c6f");
+/// ```
+#[must_use]
+pub fn encode(input: &[u8]) -> Encode<'_> {
+    Encode::new(input, false)
+}
+
+/// Lazily encode `input` to uppercase hex, as ascii bytes.
+///
+/// # Examples
+/// ```
+/// assert!(hector::iter::encode_upper(&[0xde, 0xca, 0xff]).eq(*b"DECAFF"));
+/// ```
+#[must_use]
+pub fn encode_upper(input: &[u8]) -> Encode<'_> {
+    Encode::new(input, true)
+}
+
+/// Lazily encode `input` to lowercase hex, as [`char`]s.
+///
+/// # Examples
+/// ```
+/// let output: String = hector::iter::encode_chars(&[0xde, 0xca, 0xff]).collect();
+///
+/// assert_eq!(output, "decaff");
+/// ```
+#[must_use]
+pub fn encode_chars(input: &[u8]) -> EncodeChars<'_> {
+    EncodeChars(encode(input))
+}
+
+/// Lazily encode `input` to uppercase hex, as [`char`]s.
+///
+/// # Examples
+/// ```
+/// let output: String = hector::iter::encode_chars_upper(&[0xde, 0xca, 0xff]).collect();
+///
+/// assert_eq!(output, "DECAFF");
+/// ```
+#[must_use]
+pub fn encode_chars_upper(input: &[u8]) -> EncodeChars<'_> {
+    EncodeChars(encode_upper(input))
+}
+
+/// An iterator over the hex encoding of a byte slice, as ascii bytes.
+///
+/// Created by [`encode`] and [`encode_upper`].
+#[derive(Debug, Clone)]
+pub struct Encode<'a> {
+    input: core::slice::Iter<'a, u8>,
+    /// The low character of a byte that was started from the front.
+    front: Option<u8>,
+    /// The high character of a byte that was started from the back.
+    back: Option<u8>,
+    upper: bool,
+}
+
+impl<'a> Encode<'a> {
+    fn new(input: &'a [u8], upper: bool) -> Self {
+        Self {
+            input: input.iter(),
+            front: None,
+            back: None,
+            upper,
+        }
+    }
+
+    #[inline]
+    fn byte_to_hex(&self, byte: u8) -> [u8; 2] {
+        if self.upper {
+            byte_to_hex::<true>(byte)
+        } else {
+            byte_to_hex::<false>(byte)
+        }
+    }
+}
+
+impl Iterator for Encode<'_> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        if let Some(low) = self.front.take() {
+            return Some(low);
+        }
+
+        match self.input.next() {
+            Some(&byte) => {
+                let [high, low] = self.byte_to_hex(byte);
+                self.front = Some(low);
+                Some(high)
+            }
+            // The back might've started on the last byte.
+            None => self.back.take(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Encode<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<u8> {
+        if let Some(high) = self.back.take() {
+            return Some(high);
+        }
+
+        match self.input.next_back() {
+            Some(&byte) => {
+                let [high, low] = self.byte_to_hex(byte);
+                self.back = Some(high);
+                Some(low)
+            }
+            // The front might've started on the first byte.
+            None => self.front.take(),
+        }
+    }
+}
+
+impl ExactSizeIterator for Encode<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.input.len() * 2 + usize::from(self.front.is_some()) + usize::from(self.back.is_some())
+    }
+}
+
+impl FusedIterator for Encode<'_> {}
+
+/// An iterator over the hex encoding of a byte slice, as [`char`]s.
+///
+/// Created by [`encode_chars`] and [`encode_chars_upper`].
+#[derive(Debug, Clone)]
+pub struct EncodeChars<'a>(Encode<'a>);
+
+impl Iterator for EncodeChars<'_> {
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<char> {
+        self.0.next().map(char::from)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for EncodeChars<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<char> {
+        self.0.next_back().map(char::from)
+    }
+}
+
+impl ExactSizeIterator for EncodeChars<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl FusedIterator for EncodeChars<'_> {}
+
+/// Lazily decode the hex encoded `input`.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// Collecting this into a `Result<Vec<u8>, _>` gives the same result as [`decode`](crate::decode).
+///
+/// # Errors
+/// Each item is an error if it couldn't be decoded, after the first error, the iterator ends.
+/// - [`DecodeError::OddLength`] if `input.len()` is not even (this is the first item).
+/// - [`DecodeError::InvalidHex`] if either character of a byte isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// let mut iter = hector::iter::decode(b"decaff");
+///
+/// assert_eq!(iter.next(), Some(Ok(0xde)));
+/// assert_eq!(iter.next(), Some(Ok(0xca)));
+/// assert_eq!(iter.next(), Some(Ok(0xff)));
+/// assert_eq!(iter.next(), None);
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// let mut iter = hector::iter::decode(b"c0ffgeee");
+///
+/// assert_eq!(iter.next(), Some(Ok(0xc0)));
+/// assert_eq!(iter.next(), Some(Ok(0xff)));
+/// assert_eq!(iter.next(), Some(Err(DecodeError::InvalidHex { offset: 4, value: b'g' })));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[must_use]
+pub fn decode(input: &[u8]) -> Decode<'_> {
+    Decode {
+        input,
+        offset: 0,
+        done: false,
+    }
+}
+
+/// An iterator over the bytes decoded from a hex encoded slice.
+///
+/// Created by [`decode`].
+#[derive(Debug, Clone)]
+pub struct Decode<'a> {
+    /// The part of the input that hasn't been decoded yet.
+    input: &'a [u8],
+    /// The offset of `input` into the original input.
+    offset: usize,
+    /// Whether an error has been returned yet.
+    done: bool,
+}
+
+impl Iterator for Decode<'_> {
+    type Item = Result<u8, DecodeError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if !self.input.len().is_multiple_of(2) {
+            self.done = true;
+            return Some(Err(DecodeError::OddLength));
+        }
+
+        let (&[high, low], rest) = self.input.split_first_chunk::<2>()?;
+
+        let result = decode_pair([high, low], self.offset);
+
+        self.done = result.is_err();
+        self.input = rest;
+        self.offset += 2;
+
+        Some(result)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else if !self.input.len().is_multiple_of(2) {
+            (1, Some(1))
+        } else {
+            // Any item could be an error (which ends the iterator).
+            let len = self.input.len() / 2;
+            (usize::from(len > 0), Some(len))
+        }
+    }
+}
+
+impl FusedIterator for Decode<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode};
+
+    #[test]
+    fn mixed_directions() {
+        const INPUT: &[u8] = &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+        const EXPECTED: &[u8] = b"0123456789abcdef";
+
+        // Every pattern of taking from the front or the back.
+        for pattern in 0..(1_u32 << EXPECTED.len()) {
+            let mut iter = encode(INPUT);
+            let mut front = std::vec::Vec::new();
+            let mut back = std::vec::Vec::new();
+
+            for step in 0..EXPECTED.len() {
+                assert_eq!(iter.len(), EXPECTED.len() - step);
+
+                if pattern & (1 << step) == 0 {
+                    front.push(iter.next().unwrap());
+                } else {
+                    back.push(iter.next_back().unwrap());
+                }
+            }
+
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.next_back(), None);
+
+            front.extend(back.iter().rev());
+            assert_eq!(front, EXPECTED, "pattern: {pattern:#b}");
+        }
+    }
+
+    #[test]
+    fn decode_matches_decode() {
+        let inputs: [&[u8]; 6] = [b"", b"abc", b"decaff", b"C0Ffee", b"c0ffeg", b"g0ffee"];
+
+        for input in inputs {
+            let collected: Result<std::vec::Vec<u8>, _> = decode(input).collect();
+            assert_eq!(collected, crate::decode(input));
+        }
+    }
+}
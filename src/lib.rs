@@ -5,6 +5,10 @@
 #![warn(let_underscore_drop, noop_method_call)]
 #![warn(clippy::must_use_candidate)]
 #![deny(unreachable_pub)]
+#![cfg_attr(feature = "safe", forbid(unsafe_code))]
+#![cfg_attr(feature = "nightly", feature(generic_const_exprs))]
+#![cfg_attr(feature = "nightly", allow(incomplete_features))]
+#![cfg_attr(feature = "allocator-api", feature(allocator_api))]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
@@ -13,17 +17,270 @@ extern crate alloc;
 #[cfg(any(feature = "std", test))]
 extern crate std;
 
+#[cfg(feature = "bytes")]
+pub mod buf;
+mod case;
+#[cfg(feature = "tokio-util")]
+pub mod codec;
+pub mod ct;
 mod decode;
+mod display;
+pub mod dump;
+pub mod eip55;
+#[cfg(feature = "embedded-io")]
+pub mod embedded;
 mod encode;
+#[cfg(feature = "ihex")]
+pub mod ihex;
+#[cfg(feature = "std")]
+pub mod io;
+pub mod iter;
+pub mod mac;
+mod macros;
+mod output;
+#[cfg(feature = "bytemuck")]
+mod pod;
+mod scratch;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "serde_with")]
+pub mod serde_with;
+mod sink;
+pub mod sql;
+pub mod stream;
+#[cfg(test)]
+mod test_util;
+mod types;
+mod uint;
+pub mod uuid;
 
+pub use case::{
+    detect_case, hex_cmp_ignore_case, hex_eq_ignore_case, make_lowercase, make_lowercase_str,
+    make_uppercase, make_uppercase_str, Case,
+};
 pub use decode::Error as DecodeError;
+pub use decode::FromHex;
+pub use decode::{DecodeOptions, OddLength};
+pub use display::{display, display_truncated, HexSlice, HexTruncate};
+pub use dump::dump;
+pub use encode::EncodeOptions;
 pub use encode::Error as EncodeError;
+pub use encode::ToHex;
+pub use sink::{decode_into, encode_into, encode_into_upper, ByteSink, HexSink, ReserveError};
+pub use types::HexArray;
+pub use uint::{
+    decode_uint, encode_uint, encode_words_to_fmt, Endian, Uint, UintBuffer, UintOptions,
+};
 
-pub use decode::decode_to_slice;
-pub use encode::{encode_to_array, encode_to_array_upper, encode_to_slice, encode_to_slice_upper};
+#[doc(hidden)]
+pub use macros::decode_literal as __decode_literal;
+
+pub use decode::{
+    decode_array, decode_delimited_to_slice, decode_extend, decode_in_place, decode_into_array,
+    decode_lossy_to_slice, decode_prefixed_to_slice, decode_reverse_to_slice, decode_right_aligned,
+    decode_swapped_to_slice, decode_to_array, decode_to_slice, decode_to_slice_mut, find_invalid,
+    is_hex, starts_with_hex_prefix, take_hex_to_slice, validate,
+};
+pub use encode::{
+    encode_array_owned, encode_array_owned_upper, encode_delimited_to_fmt,
+    encode_delimited_to_fmt_upper, encode_escaped_ascii_to_fmt, encode_escaped_to_fmt,
+    encode_extend, encode_extend_chars, encode_extend_chars_upper, encode_extend_upper,
+    encode_prefixed_to_slice, encode_prefixed_to_slice_upper, encode_reverse_to_fmt,
+    encode_reverse_to_fmt_upper, encode_reverse_to_slice, encode_reverse_to_slice_upper,
+    encode_swapped_filler_to_fmt, encode_swapped_filler_to_fmt_upper, encode_swapped_to_fmt,
+    encode_swapped_to_fmt_upper, encode_to_array, encode_to_array_upper, encode_to_fmt,
+    encode_to_fmt_upper, encode_to_slice, encode_to_slice_upper, encode_trimmed_to_fmt,
+    encode_vectored_to_slice, encode_vectored_to_slice_upper, eq_hex,
+};
+
+// These can't be written without `unsafe`, so they don't exist with the `safe` feature.
+#[cfg(not(feature = "safe"))]
+pub use decode::{decode_to_slice_unchecked, decode_to_uninit_slice};
+#[cfg(not(feature = "safe"))]
+pub use encode::{encode_to_uninit_slice, encode_to_uninit_slice_upper};
+#[cfg(not(feature = "safe"))]
+pub use types::HexStr;
+
+#[cfg(feature = "alloc")]
+pub use encode::AllocationError;
+#[cfg(feature = "alloc")]
+pub use encode::{
+    encode, encode_append, encode_append_upper, encode_boxed, encode_boxed_upper, encode_delimited,
+    encode_delimited_upper, encode_escaped, encode_escaped_ascii, encode_into_vec,
+    encode_into_vec_upper, encode_prefixed, encode_prefixed_upper, encode_reverse,
+    encode_reverse_upper, encode_swapped, encode_swapped_filler, encode_swapped_filler_upper,
+    encode_swapped_upper, encode_trimmed, encode_trimmed_upper, encode_upper, encode_vec,
+    encode_vec_upper, encode_vectored, encode_vectored_upper, try_encode, try_encode_upper,
+};
+
+#[cfg(feature = "std")]
+pub use encode::{encode_to_writer, encode_to_writer_upper};
+
+#[cfg(feature = "arrayvec")]
+pub use decode::decode_arrayvec;
+#[cfg(feature = "arrayvec")]
+pub use encode::{encode_arrayvec, encode_arrayvec_upper};
+
+#[cfg(feature = "heapless")]
+pub use decode::decode_heapless;
+#[cfg(feature = "heapless")]
+pub use encode::{encode_heapless, encode_heapless_upper};
+
+#[cfg(feature = "smallvec")]
+pub use decode::decode_smallvec;
+
+#[cfg(feature = "bytemuck")]
+pub use pod::{decode_pod_to_slice, encode_pod_to_fmt, encode_pod_to_slice};
+
+#[cfg(all(feature = "bytemuck", feature = "alloc"))]
+pub use pod::{encode_pod, encode_pod_upper};
+
+#[cfg(feature = "nightly")]
+pub use encode::{encode_array, encode_array_upper};
+
+#[cfg(feature = "allocator-api")]
+pub use decode::decode_in;
+#[cfg(feature = "allocator-api")]
+pub use encode::{encode_in, encode_in_upper};
+
+#[cfg(feature = "alloc")]
+pub use types::HexBuf;
 
 #[cfg(feature = "alloc")]
-pub use encode::{encode, encode_upper};
+pub use uint::encode_words;
 
 #[cfg(feature = "alloc")]
-pub use decode::decode;
+pub use decode::{
+    decode, decode_append, decode_boxed, decode_delimited, decode_escaped, decode_escaped_ascii,
+    decode_lossy, decode_prefixed, decode_reverse, decode_swapped, decode_swapped_filler,
+    decode_trimmed, decode_vec, take_hex, try_decode,
+};
+
+#[cfg(all(feature = "alloc", not(feature = "safe")))]
+pub use decode::decode_unchecked;
+
+// `#[no_panic]` is checked when linking, so every function it's on needs to be used somewhere.
+// Run with `cargo test --profile no-panic --features no-panic --lib`,
+// and again with `--features no-panic,safe`, since the safe build takes different paths.
+// It doesn't support `const fn`s, so the array functions are checked through the non-const shims below.
+#[cfg(all(test, feature = "no-panic"))]
+mod no_panic {
+    use core::hint::black_box;
+
+    #[no_panic::no_panic]
+    fn encode_to_array(input: &[u8; 3], output: &mut [u8; 6]) -> usize {
+        crate::encode_to_array(input, output).len()
+    }
+
+    #[no_panic::no_panic]
+    fn encode_to_array_upper(input: &[u8; 3], output: &mut [u8; 6]) -> usize {
+        crate::encode_to_array_upper(input, output).len()
+    }
+
+    #[no_panic::no_panic]
+    fn encode_array_owned(input: &[u8; 3]) -> [u8; 6] {
+        crate::encode_array_owned(input)
+    }
+
+    #[no_panic::no_panic]
+    fn encode_array_owned_upper(input: &[u8; 3]) -> [u8; 6] {
+        crate::encode_array_owned_upper(input)
+    }
+
+    #[no_panic::no_panic]
+    fn decode_array(input: &[u8]) -> Result<[u8; 3], crate::DecodeError> {
+        crate::decode_array(input)
+    }
+
+    #[test]
+    fn instantiate_all() {
+        let input: &[u8] = black_box(b"decaff");
+        let mut output = [0; 6];
+
+        let array = black_box([0xde, 0xca, 0xff]);
+        black_box(encode_to_array(&array, &mut output));
+        black_box(encode_to_array_upper(&array, &mut output));
+        black_box(encode_array_owned(&array));
+        black_box(encode_array_owned_upper(&array));
+        black_box(decode_array(input).is_ok());
+
+        black_box(crate::encode_to_slice(input, &mut output).is_ok());
+        black_box(crate::encode_to_slice_upper(input, &mut output).is_ok());
+        black_box(crate::encode_vectored_to_slice(&[input], &mut output).is_ok());
+        black_box(crate::encode_vectored_to_slice_upper(&[input], &mut output).is_ok());
+        black_box(crate::encode_prefixed_to_slice(input, &mut output).is_ok());
+        black_box(crate::encode_prefixed_to_slice_upper(input, &mut output).is_ok());
+        black_box(crate::encode_reverse_to_slice(input, &mut output).is_ok());
+        black_box(crate::encode_reverse_to_slice_upper(input, &mut output).is_ok());
+        #[cfg(feature = "bytemuck")]
+        black_box(crate::encode_pod_to_slice(&[black_box(0xdeca_u16)], &mut output[..4]).is_ok());
+        black_box(crate::eq_hex(input, input));
+        black_box(crate::encode_uint(input.len(), &mut crate::UintBuffer::new()).len());
+        black_box(
+            crate::UintOptions::new()
+                .trim(true)
+                .encode(input.len(), &mut crate::UintBuffer::new())
+                .len(),
+        );
+        black_box(
+            crate::EncodeOptions::new()
+                .encode_to_slice(input, &mut output)
+                .is_ok(),
+        );
+
+        black_box(crate::decode_to_slice(input, &mut output[..3]).is_ok());
+        black_box(crate::decode_to_slice_mut(input, &mut output[..3]).is_ok());
+        black_box(crate::decode_prefixed_to_slice(input, &mut output[..2]).is_ok());
+        black_box(crate::decode_lossy_to_slice(input, &mut output));
+        black_box(crate::take_hex_to_slice(input, &mut output));
+        black_box(crate::decode_delimited_to_slice(input, ':', &mut output).is_ok());
+        black_box(crate::decode_reverse_to_slice(input, &mut output[..3]).is_ok());
+        black_box(crate::decode_swapped_to_slice(input, &mut output[..3]).is_ok());
+        #[cfg(feature = "bytemuck")]
+        black_box(crate::decode_pod_to_slice(&input[..4], &mut [0_u16; 1]).is_ok());
+        black_box(crate::mac::decode_mac::<6, _>(input).is_ok());
+        black_box(crate::uuid::decode_uuid(input).is_ok());
+        black_box(crate::eip55::verify_with_hash(input, &[0; 32]));
+        black_box(crate::decode_to_array::<_, 3>(input).is_ok());
+        black_box(crate::decode_uint::<u32>(input).is_ok());
+        black_box(crate::decode_right_aligned::<_, 4>(input).is_ok());
+        black_box(crate::decode_into_array(input, &mut [0; 3]).is_ok());
+        black_box(crate::decode_in_place(&mut output).is_ok());
+        black_box(crate::validate(input).is_ok());
+        black_box(crate::is_hex(input));
+        black_box(crate::find_invalid(input).is_some());
+        black_box(crate::starts_with_hex_prefix(input, input).is_ok());
+        black_box(
+            crate::DecodeOptions::new()
+                .decode_to_slice(input, &mut output[..3])
+                .is_ok(),
+        );
+
+        #[cfg(not(feature = "safe"))]
+        {
+            let mut uninit = [core::mem::MaybeUninit::uninit(); 6];
+
+            black_box(crate::encode_to_uninit_slice(input, &mut uninit).is_ok());
+            black_box(crate::encode_to_uninit_slice_upper(input, &mut uninit).is_ok());
+            black_box(crate::decode_to_uninit_slice(input, &mut uninit[..3]).is_ok());
+            // Safety: `input` is valid hex.
+            black_box(unsafe { crate::decode_to_slice_unchecked(input, &mut output[..3]) }.is_ok());
+        }
+
+        black_box(crate::hex_eq_ignore_case(input, input));
+        black_box(crate::hex_cmp_ignore_case(input, input));
+        black_box(crate::detect_case(input).is_ok());
+        black_box(crate::make_lowercase(&mut output).is_ok());
+        black_box(crate::make_uppercase(&mut output).is_ok());
+
+        let mut string = std::string::String::from(black_box("decaff"));
+        black_box(crate::make_lowercase_str(&mut string).is_ok());
+        black_box(crate::make_uppercase_str(&mut string).is_ok());
+
+        black_box(crate::ct::encode_to_slice(input, &mut output).is_ok());
+        black_box(crate::ct::encode_to_slice_upper(input, &mut output).is_ok());
+        black_box(crate::ct::eq(input, input));
+        #[cfg(feature = "subtle")]
+        black_box(crate::ct::eq_choice(input, input));
+    }
+}
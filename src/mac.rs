@@ -0,0 +1,247 @@
+//! MAC addresses (EUI-48 and EUI-64), in the usual notations.
+//!
+//! Everything here works with `[u8; 6]` (EUI-48) and `[u8; 8]` (EUI-64), other lengths fail to compile.
+//!
+//! # Examples
+//! ```
+//! use hector::mac::{self, MacStyle};
+//!
+//! let address: [u8; 6] = mac::decode_mac("00:1b:63:84:45:e6")?;
+//!
+//! assert_eq!(mac::encode_mac(&address, MacStyle::Dotted), "001b.6384.45e6");
+//! assert_eq!(mac::encode_mac_upper(&address, MacStyle::Dash), "00-1B-63-84-45-E6");
+//! # Ok::<(), hector::DecodeError>(())
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::DecodeError;
+
+/// The ways MAC addresses are usually written.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MacStyle {
+    /// `00:1b:63:84:45:e6`, used by most unix tools.
+    Colon,
+
+    /// `00-1b-63-84-45-e6`, used by Windows (and the IEEE).
+    Dash,
+
+    /// `001b.6384.45e6`, used by Cisco.
+    Dotted,
+}
+
+impl MacStyle {
+    /// The separator, and how many bytes go between each one.
+    fn separator(self) -> (char, usize) {
+        match self {
+            MacStyle::Colon => (':', 1),
+            MacStyle::Dash => ('-', 1),
+            MacStyle::Dotted => ('.', 2),
+        }
+    }
+
+    /// The length of an `N` byte address in this style.
+    #[cfg(feature = "alloc")]
+    fn len(self, n: usize) -> usize {
+        let (_, group) = self.separator();
+
+        n * 2 + n / group - 1
+    }
+}
+
+/// Checks that `N` is a MAC address length, at compile time.
+struct MacLen<const N: usize>;
+
+impl<const N: usize> MacLen<N> {
+    const CHECK: () = assert!(
+        N == 6 || N == 8,
+        "MAC addresses are 6 (EUI-48) or 8 (EUI-64) bytes"
+    );
+}
+
+/// Encode `mac` in `style`, passing each piece to `write`.
+fn encode_with<E, const N: usize, const UPPER: bool>(
+    mac: &[u8; N],
+    style: MacStyle,
+    write: impl FnMut(&str) -> Result<(), E>,
+) -> Result<(), E> {
+    let () = MacLen::<N>::CHECK;
+
+    let (separator, group) = style.separator();
+    crate::encode::encode_delimited_with::<_, UPPER>(mac, separator, group, write)
+}
+
+/// Encode `mac` in `style`, in lowercase.
+///
+/// # Examples
+/// ```
+/// use hector::mac::{encode_mac, MacStyle};
+///
+/// let address = [0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6];
+///
+/// assert_eq!(encode_mac(&address, MacStyle::Colon), "00:1b:63:84:45:e6");
+/// assert_eq!(encode_mac(&address, MacStyle::Dash), "00-1b-63-84-45-e6");
+/// assert_eq!(encode_mac(&address, MacStyle::Dotted), "001b.6384.45e6");
+///
+/// let eui64 = [0x00, 0x1b, 0x63, 0xff, 0xfe, 0x84, 0x45, 0xe6];
+/// assert_eq!(encode_mac(&eui64, MacStyle::Colon), "00:1b:63:ff:fe:84:45:e6");
+/// ```
+///
+/// ```compile_fail
+/// hector::mac::encode_mac(&[0; 4], hector::mac::MacStyle::Colon);
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_mac<const N: usize>(mac: &[u8; N], style: MacStyle) -> String {
+    crate::output::collect_string(style.len(N), |write| {
+        encode_with::<_, N, false>(mac, style, write)
+    })
+}
+
+/// Encode `mac` in `style`, in uppercase.
+///
+/// # Examples
+/// ```
+/// use hector::mac::{encode_mac_upper, MacStyle};
+///
+/// assert_eq!(encode_mac_upper(&[0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6], MacStyle::Dash), "00-1B-63-84-45-E6");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_mac_upper<const N: usize>(mac: &[u8; N], style: MacStyle) -> String {
+    crate::output::collect_string(style.len(N), |write| {
+        encode_with::<_, N, true>(mac, style, write)
+    })
+}
+
+/// Write `mac` to `output` in `style`, in lowercase.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// use hector::mac::{encode_mac_to_fmt, MacStyle};
+///
+/// struct Interface {
+///     mac: [u8; 6],
+/// }
+///
+/// impl core::fmt::Display for Interface {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         f.write_str("ether ")?;
+///         encode_mac_to_fmt(&self.mac, MacStyle::Colon, f)
+///     }
+/// }
+///
+/// let interface = Interface { mac: [0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6] };
+/// assert_eq!(interface.to_string(), "ether 00:1b:63:84:45:e6");
+/// ```
+pub fn encode_mac_to_fmt<const N: usize, W: core::fmt::Write + ?Sized>(
+    mac: &[u8; N],
+    style: MacStyle,
+    output: &mut W,
+) -> core::fmt::Result {
+    encode_with::<_, N, false>(mac, style, |chunk| output.write_str(chunk))
+}
+
+/// Write `mac` to `output` in `style`, in uppercase.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// use hector::mac::{encode_mac_to_fmt_upper, MacStyle};
+///
+/// let mut output = String::new();
+/// encode_mac_to_fmt_upper(&[0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6], MacStyle::Dotted, &mut output)?;
+///
+/// assert_eq!(output, "001B.6384.45E6");
+/// # Ok::<(), core::fmt::Error>(())
+/// ```
+pub fn encode_mac_to_fmt_upper<const N: usize, W: core::fmt::Write + ?Sized>(
+    mac: &[u8; N],
+    style: MacStyle,
+    output: &mut W,
+) -> core::fmt::Result {
+    encode_with::<_, N, true>(mac, style, |chunk| output.write_str(chunk))
+}
+
+/// Decode a MAC address written in any [`MacStyle`] (which is detected from the input), in either case.
+///
+/// # Errors
+/// - [`DecodeError::InvalidHex`] if a character isn't a valid hex character, or a separator is missing or out of place.
+/// - [`DecodeError::OddLength`] if `input` ends halfway through a byte.
+/// - [`DecodeError::MismatchedLength`] if `input` is a MAC address of the wrong length (EUI-64 rather than EUI-48, for instance).
+///
+/// # Examples
+/// ```
+/// use hector::mac::decode_mac;
+///
+/// let address = [0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6];
+///
+/// assert_eq!(decode_mac("00:1b:63:84:45:e6"), Ok(address));
+/// assert_eq!(decode_mac("00-1B-63-84-45-E6"), Ok(address));
+/// assert_eq!(decode_mac("001b.6384.45e6"), Ok(address));
+/// ```
+///
+/// ```
+/// use hector::{mac::decode_mac, DecodeError};
+///
+/// assert_eq!(decode_mac::<6, _>("00:1b:63-84:45:e6"), Err(DecodeError::InvalidHex { offset: 8, value: b'-' }));
+/// assert_eq!(
+///     decode_mac::<6, _>("00:1b:63:ff:fe:84:45:e6"),
+///     Err(DecodeError::MismatchedLength { source_len: 16, dest_len: 6 })
+/// );
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn decode_mac<const N: usize, T: AsRef<[u8]>>(input: T) -> Result<[u8; N], DecodeError> {
+    let () = MacLen::<N>::CHECK;
+
+    let input = input.as_ref();
+
+    // The first separator is either after the first byte, or (for dotted addresses) the second.
+    let style = match input.get(2) {
+        Some(b':') => MacStyle::Colon,
+        Some(b'-') => MacStyle::Dash,
+        _ => MacStyle::Dotted,
+    };
+
+    let (separator, group) = style.separator();
+
+    let mut output = [0; N];
+    crate::decode::decode_grouped_to_slice(input, separator, group, &mut output)?;
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_mac, encode_mac, encode_mac_upper, MacStyle};
+
+    #[test]
+    fn round_trip() {
+        let eui48 = [0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6];
+        let eui64 = [0x00, 0x1b, 0x63, 0xff, 0xfe, 0x84, 0x45, 0xe6];
+
+        for style in [MacStyle::Colon, MacStyle::Dash, MacStyle::Dotted] {
+            let output = encode_mac(&eui48, style);
+            assert_eq!(output.len(), style.len(6));
+            assert_eq!(output.capacity(), output.len());
+            assert_eq!(decode_mac(&output), Ok(eui48));
+
+            let output = encode_mac_upper(&eui64, style);
+            assert_eq!(output.len(), style.len(8));
+            assert_eq!(decode_mac(&output), Ok(eui64));
+        }
+    }
+
+    #[test]
+    fn dotted_separators_between_pairs() {
+        assert!(decode_mac::<6, _>("00.1b.63.84.45.e6").is_err());
+        assert!(decode_mac::<6, _>("001b6384.45e6").is_err());
+        assert!(decode_mac::<6, _>("001b.6384.45e6.").is_err());
+    }
+}
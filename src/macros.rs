@@ -0,0 +1,54 @@
+//! Macros, and the (hidden) functions they expand to.
+
+/// Decode a hex string literal into a `[u8; N]` at compile time.
+///
+/// This is for test vectors and magic constants, which would otherwise need decoding at runtime.
+///
+/// The literal may be in either case, and may be split into several literals, which are concatenated.
+///
+/// # Examples
+/// ```
+/// const MAGIC: [u8; 4] = hector::hex!("deadbeef");
+/// assert_eq!(MAGIC, [0xde, 0xad, 0xbe, 0xef]);
+///
+/// let key = hector::hex!("C0FFEE" "decaff");
+/// assert_eq!(key, [0xc0, 0xff, 0xee, 0xde, 0xca, 0xff]);
+///
+/// assert_eq!(hector::hex!(""), []);
+/// ```
+///
+/// Invalid input is a compile error:
+/// ```compile_fail
+/// let odd = hector::hex!("abc");
+/// ```
+///
+/// ```compile_fail
+/// let invalid = hector::hex!("c0ffeg");
+/// ```
+#[macro_export]
+macro_rules! hex {
+    ($($input:literal)+) => {{
+        const INPUT: &str = ::core::concat!($($input),+);
+        const OUTPUT: [u8; INPUT.len() / 2] = $crate::__decode_literal(INPUT);
+        OUTPUT
+    }};
+}
+
+/// Decode `input` for [`hex!`], panicking (at compile time) if it isn't valid.
+#[doc(hidden)]
+#[must_use]
+pub const fn decode_literal<const N: usize>(input: &str) -> [u8; N] {
+    let input = input.as_bytes();
+
+    if !input.len().is_multiple_of(2) {
+        panic!("hex! input has an odd number of characters");
+    }
+
+    match crate::decode_array(input) {
+        Ok(output) => output,
+        Err(crate::DecodeError::InvalidHex { .. }) => {
+            panic!("hex! input contains a character that isn't hex")
+        }
+        Err(_) => panic!("hex! input doesn't decode to `N` bytes"),
+    }
+}
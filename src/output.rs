@@ -0,0 +1,216 @@
+//! Helpers for the buffers that encoding and decoding write into.
+//!
+//! This is where most of the unsafe code for output lives, so that the `safe` feature can swap it out in one place.
+
+use core::convert::Infallible;
+use core::mem::MaybeUninit;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+/// A byte of output, either initialized (`u8`) or not (`MaybeUninit<u8>`).
+///
+/// The kernels are generic over this, so that initialized output doesn't need to be cast to `MaybeUninit` (which is unsafe).
+pub(crate) trait OutputByte {
+    fn new(byte: u8) -> Self;
+}
+
+impl OutputByte for u8 {
+    #[inline(always)]
+    fn new(byte: u8) -> Self {
+        byte
+    }
+}
+
+impl OutputByte for MaybeUninit<u8> {
+    #[inline(always)]
+    fn new(byte: u8) -> Self {
+        MaybeUninit::new(byte)
+    }
+}
+
+/// Output that hasn't been written yet.
+///
+/// With the `safe` feature, this is zeroed first (which costs a bit), otherwise it's left uninitialized.
+#[cfg(not(feature = "safe"))]
+pub(crate) type Spare = MaybeUninit<u8>;
+
+/// Output that hasn't been written yet.
+///
+/// With the `safe` feature, this is zeroed first (which costs a bit), otherwise it's left uninitialized.
+#[cfg(feature = "safe")]
+pub(crate) type Spare = u8;
+
+/// The initial value of [`Spare`] output.
+#[cfg(not(feature = "safe"))]
+pub(crate) const UNWRITTEN: Spare = MaybeUninit::uninit();
+
+/// The initial value of [`Spare`] output.
+#[cfg(feature = "safe")]
+pub(crate) const UNWRITTEN: Spare = 0;
+
+/// Run `encode`, an encoder that passes its output to a callback (like the `*_with` kernels), passing each piece to `write`.
+///
+/// This is for writing to something that can't fail (like a buffer), so the callback's error is [`Infallible`].
+pub(crate) fn for_each_piece(
+    encode: impl FnOnce(&mut dyn FnMut(&str) -> Result<(), Infallible>) -> Result<(), Infallible>,
+    mut write: impl FnMut(&str),
+) {
+    let result = encode(&mut |piece| {
+        write(piece);
+        Ok(())
+    });
+
+    let Ok(()) = result;
+}
+
+/// Run `encode` (like [`for_each_piece`]), collecting its output into a `String` allocated with room for `len` characters.
+#[cfg(feature = "alloc")]
+pub(crate) fn collect_string(
+    len: usize,
+    encode: impl FnOnce(&mut dyn FnMut(&str) -> Result<(), Infallible>) -> Result<(), Infallible>,
+) -> String {
+    let mut output = String::with_capacity(len);
+    for_each_piece(encode, |piece| output.push_str(piece));
+
+    output
+}
+
+/// Append `len` bytes to `output`, which `write` *must* write all of.
+#[cfg(feature = "alloc")]
+pub(crate) fn append(output: &mut Vec<u8>, len: usize, write: impl FnOnce(&mut [Spare])) {
+    let result = try_append(output, len, |spare| {
+        write(spare);
+        Ok::<(), Infallible>(())
+    });
+
+    let Ok(()) = result;
+}
+
+/// Append `len` bytes to `output`, which `write` *must* write all of if it returns `Ok`.
+///
+/// If `write` returns an error, `output` is left unchanged.
+#[cfg(all(feature = "alloc", not(feature = "safe")))]
+pub(crate) fn try_append<E>(
+    output: &mut Vec<u8>,
+    len: usize,
+    write: impl FnOnce(&mut [Spare]) -> Result<(), E>,
+) -> Result<(), E> {
+    output.reserve(len);
+
+    let start = output.len();
+
+    // vec only guarantees we get "at least" `len` capacity, we might have a bit extra.
+    write(&mut output.spare_capacity_mut()[..len])?;
+
+    // Safety: `write` returned `Ok`, so it initialized all `len` bytes.
+    unsafe { output.set_len(start + len) };
+
+    Ok(())
+}
+
+/// Append `len` bytes to `output`, which `write` *must* write all of if it returns `Ok`.
+///
+/// If `write` returns an error, `output` is left unchanged.
+#[cfg(all(feature = "alloc", feature = "safe"))]
+pub(crate) fn try_append<E>(
+    output: &mut Vec<u8>,
+    len: usize,
+    write: impl FnOnce(&mut [Spare]) -> Result<(), E>,
+) -> Result<(), E> {
+    let start = output.len();
+    output.resize(start + len, UNWRITTEN);
+
+    let result = write(&mut output[start..]);
+
+    if result.is_err() {
+        output.truncate(start);
+    }
+
+    result
+}
+
+/// Like [`try_append`], for a `Vec` in a custom allocator.
+#[cfg(all(feature = "allocator-api", not(feature = "safe")))]
+pub(crate) fn try_append_in<E, A: core::alloc::Allocator>(
+    output: &mut alloc::vec::Vec<u8, A>,
+    len: usize,
+    write: impl FnOnce(&mut [Spare]) -> Result<(), E>,
+) -> Result<(), E> {
+    output.reserve(len);
+
+    let start = output.len();
+
+    write(&mut output.spare_capacity_mut()[..len])?;
+
+    // Safety: `write` returned `Ok`, so it initialized all `len` bytes.
+    unsafe { output.set_len(start + len) };
+
+    Ok(())
+}
+
+/// Like [`try_append`], for a `Vec` in a custom allocator.
+#[cfg(all(feature = "allocator-api", feature = "safe"))]
+pub(crate) fn try_append_in<E, A: core::alloc::Allocator>(
+    output: &mut alloc::vec::Vec<u8, A>,
+    len: usize,
+    write: impl FnOnce(&mut [Spare]) -> Result<(), E>,
+) -> Result<(), E> {
+    let start = output.len();
+    output.resize(start + len, UNWRITTEN);
+
+    let result = write(&mut output[start..]);
+
+    if result.is_err() {
+        output.truncate(start);
+    }
+
+    result
+}
+
+/// View encoded hex as a string.
+///
+/// `hex` *must* be ascii (it's only ever called with output that the encoder just wrote).
+pub(crate) fn hex_str(hex: &[u8]) -> &str {
+    #[cfg(not(feature = "safe"))]
+    // Safety: hex characters are ascii, which is valid UTF-8.
+    return unsafe { core::str::from_utf8_unchecked(hex) };
+
+    // Falling back to an empty string (for output that can't not be UTF-8) instead of panicking
+    // lets the safe build pass the `no-panic` checks.
+    #[cfg(feature = "safe")]
+    return core::str::from_utf8(hex).unwrap_or_default();
+}
+
+/// View encoded hex as a mutable string.
+///
+/// `hex` *must* be ascii (it's only ever called with output that the encoder just wrote).
+///
+/// This is a `const fn` for [`encode_to_array`](crate::encode_to_array).
+pub(crate) const fn hex_str_mut(hex: &mut [u8]) -> &mut str {
+    #[cfg(not(feature = "safe"))]
+    // Safety: hex characters are ascii, which is valid UTF-8.
+    return unsafe { core::str::from_utf8_unchecked_mut(hex) };
+
+    // `unwrap_or_default` isn't usable in const fn, so this falls back to converting an empty slice,
+    // which is always valid UTF-8 (so it never recurses further).
+    #[cfg(feature = "safe")]
+    return match core::str::from_utf8_mut(hex) {
+        Ok(hex) => hex,
+        Err(_) => hex_str_mut(&mut []),
+    };
+}
+
+/// Convert encoded hex into a string.
+///
+/// `hex` *must* be ascii (it's only ever called with output that the encoder just wrote).
+#[cfg(feature = "alloc")]
+pub(crate) fn hex_string(hex: Vec<u8>) -> String {
+    #[cfg(not(feature = "safe"))]
+    // Safety: hex characters are ascii, which is valid UTF-8.
+    return unsafe { String::from_utf8_unchecked(hex) };
+
+    // Like `hex_str`, this falls back to an empty string instead of panicking.
+    #[cfg(feature = "safe")]
+    return String::from_utf8(hex).unwrap_or_default();
+}
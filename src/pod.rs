@@ -0,0 +1,128 @@
+//! Encoding slices of [`Pod`] types (with `bytemuck`), as the bytes they're made of.
+//!
+//! Each value is encoded as it's laid out in memory, so integers (and floats) come out in native byte order.
+
+use bytemuck::Pod;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::{DecodeError, EncodeError};
+
+/// Encode the bytes of `input` to a lowercase hex string.
+///
+/// # Examples
+/// ```
+/// let samples: [u16; 3] = [0x0102, 0x0304, 0x0506];
+///
+/// let expected = hector::encode(bytemuck::cast_slice::<u16, u8>(&samples));
+/// assert_eq!(hector::encode_pod(&samples), expected);
+///
+/// assert_eq!(hector::encode_pod(&[[0xde_u8, 0xca], [0xff, 0xee]]), "decaffee");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_pod<T: Pod>(input: &[T]) -> String {
+    crate::encode(bytemuck::cast_slice::<T, u8>(input))
+}
+
+/// Encode the bytes of `input` to an uppercase hex string.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::encode_pod_upper(&[[0xde_u8, 0xca], [0xff, 0xee]]), "DECAFFEE");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_pod_upper<T: Pod>(input: &[T]) -> String {
+    crate::encode_upper(bytemuck::cast_slice::<T, u8>(input))
+}
+
+/// Encode the bytes of `input` to lowercase hex, in `output`.
+///
+/// For convenience, this returns a [`&str`](str), backed by `output`.
+///
+/// # Errors
+/// - [`EncodeError`] if the output isn't exactly twice the size of `input` (in bytes).
+///
+/// # Examples
+/// ```
+/// let mut storage = [0; 8];
+///
+/// assert_eq!(hector::encode_pod_to_slice(&[[0xde_u8, 0xca], [0xff, 0xee]], &mut storage), Ok("decaffee"));
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn encode_pod_to_slice<'a, T: Pod>(
+    input: &[T],
+    output: &'a mut [u8],
+) -> Result<&'a str, EncodeError> {
+    crate::encode_to_slice(bytemuck::cast_slice::<T, u8>(input), output)
+}
+
+/// Write the lowercase hex encoding of the bytes of `input` to `output`.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// struct Frame([u32; 2]);
+///
+/// impl core::fmt::Display for Frame {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         hector::encode_pod_to_fmt(&self.0, f)
+///     }
+/// }
+///
+/// let frame = Frame([u32::from_ne_bytes([0xde, 0xca, 0xff, 0xee]), 0]);
+/// assert_eq!(frame.to_string(), "decaffee00000000");
+/// ```
+pub fn encode_pod_to_fmt<T: Pod, W: core::fmt::Write + ?Sized>(
+    input: &[T],
+    output: &mut W,
+) -> core::fmt::Result {
+    crate::encode_to_fmt(bytemuck::cast_slice::<T, u8>(input), output)
+}
+
+/// Decode `input` into the bytes of `output`, the reverse of [`encode_pod_to_slice`].
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`DecodeError::MismatchedLength`] if `input` isn't exactly twice the size of `output` (in bytes),
+///   `dest_len` is in bytes too.
+/// - [`DecodeError::InvalidHex`] if any character isn't a valid hex character.
+///
+/// # Examples
+/// ```
+/// let mut registers = [0_u32; 2];
+///
+/// hector::decode_pod_to_slice("decaffee00000001", &mut registers)?;
+/// assert_eq!(registers, [u32::from_ne_bytes([0xde, 0xca, 0xff, 0xee]), u32::from_ne_bytes([0, 0, 0, 1])]);
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn decode_pod_to_slice<T: Pod, I: AsRef<[u8]>>(
+    input: I,
+    output: &mut [T],
+) -> Result<&[T], DecodeError> {
+    crate::decode_to_slice(input, bytemuck::cast_slice_mut::<T, u8>(output))?;
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_pod_to_slice, encode_pod};
+
+    #[test]
+    fn pod_round_trips() {
+        let input: [u64; 3] = [0, u64::MAX, 0x0123_4567_89ab_cdef];
+
+        let hex = encode_pod(&input);
+        assert_eq!(hex.len(), 48);
+
+        let mut output = [0_u64; 3];
+        assert_eq!(decode_pod_to_slice(&hex, &mut output), Ok(&input[..]));
+    }
+}
@@ -0,0 +1,124 @@
+//! [`Scratch`], internal buffers that might hold sensitive data.
+
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+/// A buffer used for staging data internally (an array or a boxed slice).
+///
+/// With the `zeroize` feature, this is wiped when it's dropped,
+/// so the data that passed through it doesn't linger in memory.
+pub(crate) struct Scratch<B: AsRef<[u8]> + AsMut<[u8]>>(pub(crate) B);
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Deref for Scratch<B> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> DerefMut for Scratch<B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0.as_mut()
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]> + fmt::Debug> fmt::Debug for Scratch<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Drop for Scratch<B> {
+    fn drop(&mut self) {
+        wipe(self.0.as_mut());
+    }
+}
+
+/// Wipe `buffer`, for staging buffers that can't be a [`Scratch`] (or are wiped at a specific point).
+#[cfg(feature = "zeroize")]
+pub(crate) fn wipe<Z: zeroize::Zeroize + ?Sized>(buffer: &mut Z) {
+    buffer.zeroize();
+
+    #[cfg(test)]
+    tests::WIPES.with(|wipes| wipes.set(wipes.get() + 1));
+}
+
+#[cfg(all(test, feature = "zeroize"))]
+mod tests {
+    use core::cell::Cell;
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+
+    std::thread_local! {
+        /// How many times [`wipe`](super::wipe) has run on this thread.
+        pub(super) static WIPES: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Whether running `f` wiped at least one staging buffer.
+    fn wipes(f: impl FnOnce()) -> bool {
+        let before = WIPES.with(Cell::get);
+        f();
+        WIPES.with(Cell::get) > before
+    }
+
+    #[test]
+    fn staging_buffers_are_wiped() {
+        let input = crate::test_util::bytes(64);
+        let hex = crate::encode(&input);
+
+        assert!(wipes(|| drop(crate::encode_delimited(&input, ':'))));
+        assert!(wipes(|| drop(crate::encode_reverse(&input))));
+        assert!(wipes(|| drop(crate::encode_swapped(&input))));
+        assert!(wipes(
+            || crate::encode_into(&input, &mut String::new()).unwrap()
+        ));
+        assert!(wipes(|| {
+            let mut output = String::new();
+            crate::EncodeOptions::new()
+                .encode_to_fmt(&input, &mut output)
+                .unwrap();
+        }));
+        assert!(wipes(|| drop(crate::encode_words(
+            &[1_u32, 2],
+            crate::Endian::Big,
+            None
+        ))));
+        assert!(wipes(|| drop(crate::dump::dump(&input).to_string())));
+        assert!(wipes(|| crate::decode_into(&hex, &mut Vec::new()).unwrap()));
+
+        assert!(wipes(|| {
+            let mut writer = crate::io::HexEncodeWriter::new(Vec::new());
+            std::io::Write::write_all(&mut writer, &input).unwrap();
+        }));
+        assert!(wipes(|| {
+            let mut reader = crate::io::HexDecodeReader::new(hex.as_bytes());
+            std::io::Read::read_to_end(&mut reader, &mut Vec::new()).unwrap();
+        }));
+        assert!(wipes(|| {
+            let mut writer = crate::dump::DumpWriter::new(Vec::new());
+            std::io::Write::write_all(&mut writer, &input).unwrap();
+        }));
+
+        #[cfg(feature = "bytes")]
+        assert!(wipes(|| {
+            let mut output = [0; 64];
+            crate::buf::decode_from_buf(&mut hex.as_bytes(), &mut output).unwrap();
+        }));
+
+        #[cfg(feature = "embedded-io")]
+        assert!(wipes(|| {
+            let mut output = [0; 128];
+            let mut writer = crate::embedded::EncodeWriter::new(&mut output[..]);
+            embedded_io::Write::write_all(&mut writer, &input).unwrap();
+        }));
+
+        #[cfg(feature = "ihex")]
+        {
+            let image = crate::ihex::encode(0, &input);
+            assert!(wipes(|| drop(crate::ihex::encode(0, &input))));
+            assert!(wipes(|| drop(crate::ihex::decode(&image).unwrap())));
+        }
+    }
+}
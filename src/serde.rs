@@ -0,0 +1,478 @@
+//! (De)serialize bytes as hex strings, for use with `#[serde(with = "hector::serde")]`.
+//!
+//! Serializing works for anything that's [`AsRef<[u8]>`](AsRef) (`Vec<u8>`, `[u8; N]`, `&[u8]`, ...),
+//! and always produces lowercase hex.
+//!
+//! Deserializing works for anything that's [`Decodable`] (`Vec<u8>`, `Box<[u8]>`, `[u8; N]`),
+//! it does _not_ enforce a specific casing convention, and errors use hector's messages.
+//! Arrays don't need `alloc`, and have to decode to exactly `N` bytes.
+//!
+//! Hex is only used for human readable formats (JSON, TOML, YAML, ...),
+//! binary formats (bincode, postcard, ...) get the raw bytes instead, so the data isn't double-encoded.
+//!
+//! # Examples
+//! ```
+//! #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+//! struct Message {
+//!     #[serde(with = "hector::serde")]
+//!     payload: Vec<u8>,
+//! }
+//!
+//! let message = Message { payload: vec![0xde, 0xca, 0xff] };
+//! let json = serde_json::to_string(&message).unwrap();
+//!
+//! assert_eq!(json, r#"{"payload":"decaff"}"#);
+//! assert_eq!(serde_json::from_str::<Message>(&json).unwrap(), message);
+//!
+//! let err = serde_json::from_str::<Message>(r#"{"payload":"decafg"}"#).unwrap_err();
+//! assert!(err.to_string().starts_with("character `g` (0x67) at `5` is not a valid hex character"));
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use ::serde::de::{self, Deserializer, Visitor};
+use ::serde::Serializer;
+
+use crate::DecodeError;
+
+/// Types that [`deserialize`] can decode hex into.
+///
+/// This trait is sealed, it can't be implemented outside of hector.
+pub trait Decodable: Sized + private::Sealed {
+    #[doc(hidden)]
+    fn from_hex(hex: &[u8]) -> Result<Self, DecodeError>;
+
+    /// Copy raw `bytes` (from a binary format), returning the expected length if it's wrong.
+    #[doc(hidden)]
+    fn from_bytes(bytes: &[u8]) -> Result<Self, usize>;
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+#[cfg(feature = "alloc")]
+impl private::Sealed for alloc::vec::Vec<u8> {}
+
+#[cfg(feature = "alloc")]
+impl Decodable for alloc::vec::Vec<u8> {
+    fn from_hex(hex: &[u8]) -> Result<Self, DecodeError> {
+        crate::decode(hex)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, usize> {
+        Ok(bytes.to_vec())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl private::Sealed for alloc::boxed::Box<[u8]> {}
+
+#[cfg(feature = "alloc")]
+impl Decodable for alloc::boxed::Box<[u8]> {
+    fn from_hex(hex: &[u8]) -> Result<Self, DecodeError> {
+        crate::FromHex::from_hex(hex)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, usize> {
+        Ok(bytes.into())
+    }
+}
+
+impl<const N: usize> private::Sealed for [u8; N] {}
+
+/// Arrays are decoded directly (without an intermediate `Vec`),
+/// and the input has to decode to exactly `N` bytes.
+impl<const N: usize> Decodable for [u8; N] {
+    fn from_hex(hex: &[u8]) -> Result<Self, DecodeError> {
+        crate::decode_to_array(hex)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, usize> {
+        bytes.try_into().map_err(|_| N)
+    }
+}
+
+/// The number of bytes a hex string was expected to decode to, for error messages.
+struct ExpectedLen(usize);
+
+impl de::Expected for ExpectedLen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} bytes", self.0)
+    }
+}
+
+/// Formats bytes as hex, for `collect_str` (which avoids allocating a `String` where the serializer allows it).
+struct Hex<'a> {
+    bytes: &'a [u8],
+    upper: bool,
+    prefixed: bool,
+}
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.prefixed {
+            f.write_str("0x")?;
+        }
+
+        match self.upper {
+            false => crate::encode_to_fmt(self.bytes, f),
+            true => crate::encode_to_fmt_upper(self.bytes, f),
+        }
+    }
+}
+
+fn serialize_with<S: Serializer>(
+    bytes: &[u8],
+    serializer: S,
+    upper: bool,
+    prefixed: bool,
+) -> Result<S::Ok, S::Error> {
+    if !serializer.is_human_readable() {
+        return serializer.serialize_bytes(bytes);
+    }
+
+    serializer.collect_str(&Hex {
+        bytes,
+        upper,
+        prefixed,
+    })
+}
+
+fn deserialize_with<'de, T: Decodable, D: Deserializer<'de>>(
+    deserializer: D,
+    prefixed: bool,
+) -> Result<T, D::Error> {
+    if !deserializer.is_human_readable() {
+        return deserializer.deserialize_bytes(BytesVisitor(PhantomData));
+    }
+
+    deserializer.deserialize_str(HexVisitor {
+        prefixed,
+        marker: PhantomData,
+    })
+}
+
+struct HexVisitor<T> {
+    prefixed: bool,
+    marker: PhantomData<T>,
+}
+
+impl<T: Decodable> Visitor<'_> for HexVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.prefixed {
+            false => f.write_str("a hex string"),
+            true => f.write_str("a `0x` prefixed hex string"),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        let hex = match self.prefixed {
+            false => value,
+            true => value
+                .strip_prefix("0x")
+                .or_else(|| value.strip_prefix("0X"))
+                .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))?,
+        };
+
+        T::from_hex(hex.as_bytes()).map_err(|err| match err {
+            // Only fixed size outputs have a length to mismatch.
+            DecodeError::MismatchedLength {
+                source_len,
+                dest_len,
+            } if source_len.is_multiple_of(2) => {
+                E::invalid_length(source_len / 2, &ExpectedLen(dest_len))
+            }
+            // Error offsets are into `value`, not just the hex part.
+            err => E::custom(err.offset_by(value.len() - hex.len())),
+        })
+    }
+}
+
+/// The visitor for binary formats, which just copies the bytes.
+struct BytesVisitor<T>(PhantomData<T>);
+
+impl<T: Decodable> Visitor<'_> for BytesVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<T, E> {
+        T::from_bytes(value).map_err(|len| E::invalid_length(value.len(), &ExpectedLen(len)))
+    }
+}
+
+/// Serialize `value` as a lowercase hex string.
+///
+/// # Errors
+/// - Any error from `serializer`.
+pub fn serialize<T: AsRef<[u8]> + ?Sized, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serialize_with(value.as_ref(), serializer, false, false)
+}
+
+/// Deserialize a hex string into a `T`.
+///
+/// # Errors
+/// - Any error from `deserializer`.
+/// - If the string isn't valid hex (see [`decode`](crate::decode)).
+pub fn deserialize<'de, T: Decodable, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    deserialize_with(deserializer, false)
+}
+
+/// Like [`hector::serde`](self), but serializes as uppercase hex, for use with `#[serde(with = "hector::serde::upper")]`.
+///
+/// # Examples
+/// ```
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Entry {
+///     #[serde(with = "hector::serde::upper")]
+///     id: Vec<u8>,
+/// }
+///
+/// let json = serde_json::to_string(&Entry { id: vec![0xde, 0xca, 0xff] }).unwrap();
+///
+/// assert_eq!(json, r#"{"id":"DECAFF"}"#);
+/// ```
+pub mod upper {
+    use ::serde::{Deserializer, Serializer};
+
+    use super::Decodable;
+
+    /// Serialize `value` as an uppercase hex string.
+    ///
+    /// # Errors
+    /// - Any error from `serializer`.
+    pub fn serialize<T: AsRef<[u8]> + ?Sized, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::serialize_with(value.as_ref(), serializer, true, false)
+    }
+
+    /// Deserialize a hex string into a `T`, this doesn't require the string to be uppercase.
+    ///
+    /// # Errors
+    /// - Any error from `deserializer`.
+    /// - If the string isn't valid hex (see [`decode`](crate::decode)).
+    pub fn deserialize<'de, T: Decodable, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        super::deserialize_with(deserializer, false)
+    }
+}
+
+/// Like [`hector::serde`](self), but with a `0x` prefix (like Ethereum's JSON-RPC),
+/// for use with `#[serde(with = "hector::serde::prefixed")]`.
+///
+/// Deserializing requires the prefix, either as `0x` or `0X`.
+///
+/// # Examples
+/// ```
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Transaction {
+///     #[serde(with = "hector::serde::prefixed")]
+///     input: Vec<u8>,
+/// }
+///
+/// let json = serde_json::to_string(&Transaction { input: vec![0xde, 0xca, 0xff] }).unwrap();
+/// assert_eq!(json, r#"{"input":"0xdecaff"}"#);
+///
+/// assert!(serde_json::from_str::<Transaction>(r#"{"input":"decaff"}"#).is_err());
+/// ```
+pub mod prefixed {
+    use ::serde::{Deserializer, Serializer};
+
+    use super::Decodable;
+
+    /// Serialize `value` as a `0x` prefixed lowercase hex string.
+    ///
+    /// # Errors
+    /// - Any error from `serializer`.
+    pub fn serialize<T: AsRef<[u8]> + ?Sized, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::serialize_with(value.as_ref(), serializer, false, true)
+    }
+
+    /// Deserialize a `0x` prefixed hex string into a `T`.
+    ///
+    /// # Errors
+    /// - Any error from `deserializer`.
+    /// - If the string doesn't start with `0x` (or `0X`).
+    /// - If the rest of the string isn't valid hex (see [`decode`](crate::decode)).
+    pub fn deserialize<'de, T: Decodable, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        super::deserialize_with(deserializer, true)
+    }
+}
+
+/// Like [`prefixed`], but serializes as uppercase hex (the prefix is still `0x`),
+/// for use with `#[serde(with = "hector::serde::prefixed_upper")]`.
+///
+/// # Examples
+/// ```
+/// #[derive(serde::Serialize)]
+/// struct Register {
+///     #[serde(with = "hector::serde::prefixed_upper")]
+///     value: [u8; 2],
+/// }
+///
+/// let json = serde_json::to_string(&Register { value: [0xca, 0xfe] }).unwrap();
+///
+/// assert_eq!(json, r#"{"value":"0xCAFE"}"#);
+/// ```
+pub mod prefixed_upper {
+    use ::serde::{Deserializer, Serializer};
+
+    use super::Decodable;
+
+    /// Serialize `value` as a `0x` prefixed uppercase hex string.
+    ///
+    /// # Errors
+    /// - Any error from `serializer`.
+    pub fn serialize<T: AsRef<[u8]> + ?Sized, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::serialize_with(value.as_ref(), serializer, true, true)
+    }
+
+    /// Deserialize a `0x` prefixed hex string into a `T`, this doesn't require the string to be uppercase.
+    ///
+    /// # Errors
+    /// - Any error from `deserializer`.
+    /// - If the string doesn't start with `0x` (or `0X`).
+    /// - If the rest of the string isn't valid hex (see [`decode`](crate::decode)).
+    pub fn deserialize<'de, T: Decodable, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        super::deserialize_with(deserializer, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::boxed::Box;
+    use std::string::ToString;
+
+    #[derive(::serde::Serialize)]
+    struct Borrowed<'a> {
+        #[serde(with = "crate::serde")]
+        bytes: &'a [u8],
+    }
+
+    #[derive(::serde::Deserialize, Debug)]
+    struct Boxed {
+        #[serde(with = "crate::serde")]
+        bytes: Box<[u8]>,
+    }
+
+    #[test]
+    fn borrowed_and_boxed() {
+        let json = serde_json::to_string(&Borrowed {
+            bytes: &[0xc0, 0xff, 0xee],
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"bytes":"c0ffee"}"#);
+
+        let boxed: Boxed = serde_json::from_str(r#"{"bytes":"C0FFEE"}"#).unwrap();
+        assert_eq!(&*boxed.bytes, [0xc0, 0xff, 0xee]);
+    }
+
+    #[test]
+    fn rejects_non_strings() {
+        let err = serde_json::from_str::<Boxed>(r#"{"bytes":[192]}"#).unwrap_err();
+        assert!(err.to_string().contains("expected a hex string"), "{err}");
+
+        let err = serde_json::from_str::<Boxed>(r#"{"bytes":"c0f"}"#).unwrap_err();
+        assert!(err.to_string().contains("odd length"), "{err}");
+    }
+
+    #[derive(::serde::Serialize, ::serde::Deserialize, Debug, PartialEq)]
+    struct Prefixed {
+        #[serde(with = "crate::serde::prefixed_upper")]
+        bytes: std::vec::Vec<u8>,
+    }
+
+    #[test]
+    fn prefixed_round_trip() {
+        let value = Prefixed {
+            bytes: std::vec![0xc0, 0xff, 0xee],
+        };
+
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, r#"{"bytes":"0xC0FFEE"}"#);
+        assert_eq!(serde_json::from_str::<Prefixed>(&json).unwrap(), value);
+        assert_eq!(
+            serde_json::from_str::<Prefixed>(r#"{"bytes":"0Xc0ffee"}"#).unwrap(),
+            value
+        );
+
+        let err = serde_json::from_str::<Prefixed>(r#"{"bytes":"c0ffee"}"#).unwrap_err();
+        assert!(err.to_string().contains("`0x` prefixed"), "{err}");
+
+        // Offsets include the prefix.
+        let err = serde_json::from_str::<Prefixed>(r#"{"bytes":"0xc0ffeg"}"#).unwrap_err();
+        assert!(err.to_string().contains("at `7`"), "{err}");
+    }
+
+    #[derive(::serde::Serialize, ::serde::Deserialize, Debug, PartialEq)]
+    struct Digest {
+        #[serde(with = "crate::serde")]
+        bytes: [u8; 4],
+    }
+
+    #[test]
+    fn arrays() {
+        let value = Digest {
+            bytes: [0xde, 0xad, 0xbe, 0xef],
+        };
+
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, r#"{"bytes":"deadbeef"}"#);
+        assert_eq!(serde_json::from_str::<Digest>(&json).unwrap(), value);
+
+        let err = serde_json::from_str::<Digest>(r#"{"bytes":"deadbe"}"#).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("invalid length 3, expected 4 bytes"),
+            "{err}"
+        );
+
+        let err = serde_json::from_str::<Digest>(r#"{"bytes":"deadbeeg"}"#).unwrap_err();
+        assert!(err.to_string().contains("at `7`"), "{err}");
+    }
+
+    #[test]
+    fn binary_formats_use_bytes() {
+        let value = Digest {
+            bytes: [0xde, 0xad, 0xbe, 0xef],
+        };
+
+        // postcard is a length followed by the raw bytes.
+        let encoded = postcard::to_allocvec(&value).unwrap();
+        assert_eq!(encoded, [4, 0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(postcard::from_bytes::<Digest>(&encoded).unwrap(), value);
+
+        let prefixed = postcard::to_allocvec(&Prefixed {
+            bytes: std::vec![0xc0, 0xff, 0xee],
+        })
+        .unwrap();
+        assert_eq!(prefixed, [3, 0xc0, 0xff, 0xee]);
+
+        // Wrong length for the array.
+        assert!(postcard::from_bytes::<Digest>(&prefixed).is_err());
+    }
+}
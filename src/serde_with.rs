@@ -0,0 +1,72 @@
+//! Adapters for [`serde_with`], so hex composes with `Option`, `Vec`, maps, and so on.
+//!
+//! These behave exactly like the matching [`hector::serde`](crate::serde) modules
+//! (including using raw bytes for binary formats).
+//!
+//! # Examples
+//! ```
+//! use serde_with::serde_as;
+//!
+//! #[serde_as]
+//! #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+//! struct Block {
+//!     #[serde_as(as = "Option<hector::serde_with::Hex>")]
+//!     parent: Option<[u8; 2]>,
+//!     #[serde_as(as = "Vec<hector::serde_with::HexPrefixed>")]
+//!     transactions: Vec<Vec<u8>>,
+//! }
+//!
+//! let block = Block { parent: Some([0xca, 0xfe]), transactions: vec![vec![0xde, 0xca, 0xff], vec![]] };
+//! let json = serde_json::to_string(&block).unwrap();
+//!
+//! assert_eq!(json, r#"{"parent":"cafe","transactions":["0xdecaff","0x"]}"#);
+//! assert_eq!(serde_json::from_str::<Block>(&json).unwrap(), block);
+//! ```
+
+use ::serde::{Deserializer, Serializer};
+use ::serde_with::{DeserializeAs, SerializeAs};
+
+use crate::serde::Decodable;
+
+macro_rules! adapter {
+    ($(#[$attr:meta])* $name:ident => $module:path) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct $name;
+
+        impl<T: AsRef<[u8]>> SerializeAs<T> for $name {
+            fn serialize_as<S: Serializer>(source: &T, serializer: S) -> Result<S::Ok, S::Error> {
+                // `$module::serialize` isn't allowed for `path` fragments.
+                use $module as module;
+                module::serialize(source, serializer)
+            }
+        }
+
+        impl<'de, T: Decodable> DeserializeAs<'de, T> for $name {
+            fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+                use $module as module;
+                module::deserialize(deserializer)
+            }
+        }
+    };
+}
+
+adapter! {
+    /// (De)serialize as lowercase hex, like [`hector::serde`](crate::serde).
+    Hex => crate::serde
+}
+
+adapter! {
+    /// (De)serialize as uppercase hex, like [`hector::serde::upper`](crate::serde::upper).
+    HexUpper => crate::serde::upper
+}
+
+adapter! {
+    /// (De)serialize as `0x` prefixed lowercase hex, like [`hector::serde::prefixed`](crate::serde::prefixed).
+    HexPrefixed => crate::serde::prefixed
+}
+
+adapter! {
+    /// (De)serialize as `0x` prefixed uppercase hex, like [`hector::serde::prefixed_upper`](crate::serde::prefixed_upper).
+    HexPrefixedUpper => crate::serde::prefixed_upper
+}
@@ -0,0 +1,422 @@
+//! [`HexSink`] and [`ByteSink`], the output containers that [`encode_into`] and [`decode_into`] append to.
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+use crate::encode::{encode_chunked, FMT_BUFFER_LEN};
+use crate::scratch::Scratch;
+use crate::{DecodeError, EncodeError};
+
+/// Somewhere that [`encode_into`] can append hex to.
+///
+/// This is implemented for the usual text and byte buffers, including the fixed capacity ones
+/// from the `arrayvec` and `heapless` features (and everything that implements [`ByteSink`]),
+/// and can be implemented for other buffers too.
+///
+/// # Examples
+/// ```
+/// use hector::{HexSink, ReserveError};
+///
+/// /// Keeps the last 8 characters written.
+/// struct Tail([u8; 8]);
+///
+/// impl HexSink for Tail {
+///     fn reserve_hex(&mut self, _additional: usize) -> Result<(), ReserveError> {
+///         Ok(())
+///     }
+///
+///     fn append_hex(&mut self, hex: &str) {
+///         for &char in hex.as_bytes() {
+///             self.0.rotate_left(1);
+///             self.0[7] = char;
+///         }
+///     }
+/// }
+///
+/// let mut tail = Tail([b' '; 8]);
+/// hector::encode_into(b"Hello, world!", &mut tail)?;
+///
+/// assert_eq!(&tail.0, b"726c6421");
+/// # Ok::<(), hector::EncodeError>(())
+/// ```
+pub trait HexSink {
+    /// Make room for `additional` more characters, failing if there isn't (and can't be) room.
+    fn reserve_hex(&mut self, additional: usize) -> Result<(), ReserveError>;
+
+    /// Append `hex`, which is always ascii hex characters, and always fits in the room made by [`reserve_hex`](Self::reserve_hex).
+    fn append_hex(&mut self, hex: &str);
+}
+
+/// Somewhere that [`decode_into`] can append bytes to.
+///
+/// This is implemented for the usual byte buffers, including the ones
+/// from the `arrayvec`, `heapless`, `smallvec`, and `bytes` features, and can be implemented for other buffers too.
+///
+/// Every byte sink is also a [`HexSink`] (for the ascii bytes of the hex).
+///
+/// # Examples
+/// ```
+/// use hector::{ByteSink, ReserveError};
+///
+/// /// Only keeps a checksum of the bytes written.
+/// struct Checksum(u8);
+///
+/// impl ByteSink for Checksum {
+///     fn reserve(&mut self, _additional: usize) -> Result<(), ReserveError> {
+///         Ok(())
+///     }
+///
+///     fn append(&mut self, bytes: &[u8]) {
+///         self.0 = bytes.iter().fold(self.0, |sum, &byte| sum.wrapping_add(byte));
+///     }
+/// }
+///
+/// let mut checksum = Checksum(0);
+/// hector::decode_into("0102037f", &mut checksum)?;
+///
+/// assert_eq!(checksum.0, 0x85);
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+pub trait ByteSink {
+    /// Make room for `additional` more bytes, failing if there isn't (and can't be) room.
+    fn reserve(&mut self, additional: usize) -> Result<(), ReserveError>;
+
+    /// Append `bytes`, which always fit in the room made by [`reserve`](Self::reserve).
+    fn append(&mut self, bytes: &[u8]);
+}
+
+/// Why a [`HexSink`] or [`ByteSink`] couldn't make room for more output.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ReserveError {
+    /// The sink has a fixed capacity, with only `room` more bytes (or characters) left.
+    Full {
+        /// How much room is left.
+        room: usize,
+    },
+
+    /// The sink can grow, but allocating the room failed.
+    AllocationFailed,
+}
+
+impl<T: ByteSink + ?Sized> HexSink for T {
+    fn reserve_hex(&mut self, additional: usize) -> Result<(), ReserveError> {
+        self.reserve(additional)
+    }
+
+    fn append_hex(&mut self, hex: &str) {
+        self.append(hex.as_bytes());
+    }
+}
+
+/// Like `io::Write` for `&mut [u8]`, appending overwrites the start of the slice, and moves it past what was written.
+impl ByteSink for &mut [u8] {
+    fn reserve(&mut self, additional: usize) -> Result<(), ReserveError> {
+        room(self.len(), additional)
+    }
+
+    fn append(&mut self, bytes: &[u8]) {
+        let (written, rest) = core::mem::take(self).split_at_mut(bytes.len());
+        written.copy_from_slice(bytes);
+        *self = rest;
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ByteSink for Vec<u8> {
+    fn reserve(&mut self, additional: usize) -> Result<(), ReserveError> {
+        self.try_reserve(additional)
+            .map_err(|_| ReserveError::AllocationFailed)
+    }
+
+    fn append(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl HexSink for String {
+    fn reserve_hex(&mut self, additional: usize) -> Result<(), ReserveError> {
+        self.try_reserve(additional)
+            .map_err(|_| ReserveError::AllocationFailed)
+    }
+
+    fn append_hex(&mut self, hex: &str) {
+        self.push_str(hex);
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<const CAP: usize> ByteSink for arrayvec::ArrayVec<u8, CAP> {
+    fn reserve(&mut self, additional: usize) -> Result<(), ReserveError> {
+        room(self.remaining_capacity(), additional)
+    }
+
+    fn append(&mut self, bytes: &[u8]) {
+        let result = self.try_extend_from_slice(bytes);
+        debug_assert!(result.is_ok());
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<const CAP: usize> HexSink for arrayvec::ArrayString<CAP> {
+    fn reserve_hex(&mut self, additional: usize) -> Result<(), ReserveError> {
+        room(self.remaining_capacity(), additional)
+    }
+
+    fn append_hex(&mut self, hex: &str) {
+        let result = self.try_push_str(hex);
+        debug_assert!(result.is_ok());
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> ByteSink for heapless::Vec<u8, N> {
+    fn reserve(&mut self, additional: usize) -> Result<(), ReserveError> {
+        room(self.capacity() - self.len(), additional)
+    }
+
+    fn append(&mut self, bytes: &[u8]) {
+        let result = self.extend_from_slice(bytes);
+        debug_assert!(result.is_ok());
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> HexSink for heapless::String<N> {
+    fn reserve_hex(&mut self, additional: usize) -> Result<(), ReserveError> {
+        room(self.capacity() - self.len(), additional)
+    }
+
+    fn append_hex(&mut self, hex: &str) {
+        let result = self.push_str(hex);
+        debug_assert!(result.is_ok());
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array<Item = u8>> ByteSink for smallvec::SmallVec<A> {
+    fn reserve(&mut self, additional: usize) -> Result<(), ReserveError> {
+        self.try_reserve(additional)
+            .map_err(|_| ReserveError::AllocationFailed)
+    }
+
+    fn append(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl ByteSink for bytes::BytesMut {
+    fn reserve(&mut self, additional: usize) -> Result<(), ReserveError> {
+        bytes::BytesMut::reserve(self, additional);
+        Ok(())
+    }
+
+    fn append(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
+
+/// An [`Extend`] collection as a [`ByteSink`], which always has room (for [`decode_extend`](crate::decode_extend)).
+pub(crate) struct ExtendSink<'a, E: ?Sized>(pub(crate) &'a mut E);
+
+impl<E: Extend<u8> + ?Sized> ByteSink for ExtendSink<'_, E> {
+    fn reserve(&mut self, _additional: usize) -> Result<(), ReserveError> {
+        Ok(())
+    }
+
+    fn append(&mut self, bytes: &[u8]) {
+        self.0.extend(bytes.iter().copied());
+    }
+}
+
+/// Whether a fixed `room` is enough for `additional` more bytes, in the form [`ByteSink::reserve`] returns.
+fn room(room: usize, additional: usize) -> Result<(), ReserveError> {
+    if room >= additional {
+        Ok(())
+    } else {
+        Err(ReserveError::Full { room })
+    }
+}
+
+pub(crate) fn encode_into_impl<const UPPER: bool, S: HexSink + ?Sized>(
+    input: &[u8],
+    output: &mut S,
+) -> Result<(), EncodeError> {
+    output
+        .reserve_hex(input.len() * 2)
+        .map_err(|_| EncodeError)?;
+
+    crate::output::for_each_piece(
+        |write| encode_chunked::<_, FMT_BUFFER_LEN, UPPER>(input, write),
+        |chunk| output.append_hex(chunk),
+    );
+
+    Ok(())
+}
+
+/// Append the lowercase hex encoding of `input` to `output`.
+///
+/// # Errors
+/// - [`EncodeError`] if there isn't room in `output` for `input.len() * 2` more characters,
+///   in which case nothing is written.
+///
+/// # Examples
+/// ```
+/// let mut text = String::from("id=");
+/// hector::encode_into([0xde, 0xca, 0xff], &mut text)?;
+/// assert_eq!(text, "id=decaff");
+///
+/// let mut frame = b"id=".to_vec();
+/// hector::encode_into([0xde, 0xca, 0xff], &mut frame)?;
+/// assert_eq!(frame, b"id=decaff");
+///
+/// let mut storage = [0; 8];
+/// let mut rest = &mut storage[..];
+/// hector::encode_into([0xde, 0xca, 0xff], &mut rest)?;
+/// assert_eq!(rest.len(), 2);
+/// assert_eq!(&storage[..6], b"decaff");
+/// # Ok::<(), hector::EncodeError>(())
+/// ```
+pub fn encode_into<T: AsRef<[u8]>, S: HexSink + ?Sized>(
+    input: T,
+    output: &mut S,
+) -> Result<(), EncodeError> {
+    encode_into_impl::<false, S>(input.as_ref(), output)
+}
+
+/// Append the uppercase hex encoding of `input` to `output`.
+///
+/// # Errors
+/// - [`EncodeError`] if there isn't room in `output` for `input.len() * 2` more characters,
+///   in which case nothing is written.
+///
+/// # Examples
+/// ```
+/// let mut text = String::from("ID=");
+/// hector::encode_into_upper([0xde, 0xca, 0xff], &mut text)?;
+///
+/// assert_eq!(text, "ID=DECAFF");
+/// # Ok::<(), hector::EncodeError>(())
+/// ```
+pub fn encode_into_upper<T: AsRef<[u8]>, S: HexSink + ?Sized>(
+    input: T,
+    output: &mut S,
+) -> Result<(), EncodeError> {
+    encode_into_impl::<true, S>(input.as_ref(), output)
+}
+
+/// Decode the hex encoded `input`, appending the bytes to `output`.
+///
+/// `input` is checked before anything is written, so `output` is left unchanged on error.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`DecodeError::OddLength`] if `input.len()` is not even.
+/// - [`DecodeError::InvalidHex`] if any character isn't a valid hex character.
+/// - [`DecodeError::MismatchedLength`] if there isn't room in `output` for `input.len() / 2` more bytes,
+///   with `dest_len` as the room there is.
+/// - [`DecodeError::AllocationFailed`] if `output` can grow, but allocating the room failed.
+///
+/// # Examples
+/// ```
+/// let mut frame = vec![0x01];
+/// hector::decode_into("decaff", &mut frame)?;
+/// assert_eq!(frame, [0x01, 0xde, 0xca, 0xff]);
+///
+/// let mut storage = [0; 4];
+/// let mut rest = &mut storage[..];
+/// hector::decode_into("c0ffee", &mut rest)?;
+/// assert_eq!(storage, [0xc0, 0xff, 0xee, 0x00]);
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// let mut storage = [0; 2];
+/// let output = hector::decode_into("c0ffee", &mut &mut storage[..]);
+///
+/// assert_eq!(output, Err(DecodeError::MismatchedLength { source_len: 6, dest_len: 2 }));
+/// ```
+pub fn decode_into<T: AsRef<[u8]>, S: ByteSink + ?Sized>(
+    input: T,
+    output: &mut S,
+) -> Result<(), DecodeError> {
+    let input = input.as_ref();
+
+    crate::validate(input)?;
+
+    output.reserve(input.len() / 2).map_err(|err| match err {
+        ReserveError::Full { room } => DecodeError::MismatchedLength {
+            source_len: input.len(),
+            dest_len: room,
+        },
+        ReserveError::AllocationFailed => DecodeError::AllocationFailed {
+            len: input.len() / 2,
+        },
+    })?;
+
+    // The same size of buffer on the stack as encoding uses.
+    let mut buffer = Scratch([0; FMT_BUFFER_LEN / 2]);
+    for chunk in input.chunks(FMT_BUFFER_LEN) {
+        let bytes = &mut buffer[..chunk.len() / 2];
+
+        // `input` was validated above, so the chunks don't need to be checked again.
+        crate::decode::decode_trusted_to_slice(chunk, bytes);
+        output.append(bytes);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::String;
+    use std::vec::Vec;
+
+    use super::{decode_into, encode_into, ByteSink, ReserveError, FMT_BUFFER_LEN};
+
+    #[test]
+    fn sinks_match_encode() {
+        let input = crate::test_util::bytes(FMT_BUFFER_LEN * 2 + 1);
+        let expected = crate::encode(&input);
+
+        let mut string = String::from("hex: ");
+        encode_into(&input, &mut string).unwrap();
+        assert_eq!(string, std::format!("hex: {expected}"));
+
+        let mut storage = std::vec![0; expected.len()];
+        encode_into(&input, &mut &mut storage[..]).unwrap();
+        assert_eq!(storage, expected.as_bytes());
+        assert!(encode_into(&input, &mut &mut storage[1..]).is_err());
+
+        let mut output = Vec::new();
+        decode_into(&expected, &mut output).unwrap();
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    fn decode_into_leaves_output_on_error() {
+        let mut output = std::vec![0xaa];
+
+        assert!(decode_into("decafg", &mut output).is_err());
+        assert!(decode_into("decaf", &mut output).is_err());
+        assert_eq!(output, [0xaa]);
+    }
+
+    #[test]
+    fn reserve_errors() {
+        assert_eq!(
+            ByteSink::reserve(&mut Vec::new(), usize::MAX),
+            Err(ReserveError::AllocationFailed)
+        );
+
+        let mut storage = [0; 2];
+        assert_eq!(
+            ByteSink::reserve(&mut &mut storage[..], 3),
+            Err(ReserveError::Full { room: 2 })
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! Hex in SQL, as hex literals (`X'DECAFF'` and `0xDECAFF`), and as PostgreSQL's `bytea` type writes it (`\xdecaff`).
+//!
+//! # Examples
+//! ```
+//! let literal = hector::sql::encode_literal([0xde, 0xca, 0xff]);
+//! assert_eq!(literal, "X'DECAFF'");
+//!
+//! assert_eq!(hector::sql::decode_literal(&literal)?, [0xde, 0xca, 0xff]);
+//! # Ok::<(), hector::DecodeError>(())
+//! ```
+//!
+//! ```
+//! let column = hector::sql::encode_bytea([0xde, 0xca, 0xff]);
+//! assert_eq!(column, r"\xdecaff");
+//!
+//! assert_eq!(hector::sql::decode_bytea(&column)?, [0xde, 0xca, 0xff]);
+//! # Ok::<(), hector::DecodeError>(())
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "alloc")]
+use crate::{DecodeError, DecodeOptions, OddLength};
+
+/// Encode `input` as a standard SQL hex literal, `X'DECAFF'`.
+///
+/// The hex is uppercase (which most databases write these in), and the output is allocated at exactly the right size up front.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::sql::encode_literal(b"hi"), "X'6869'");
+/// assert_eq!(hector::sql::encode_literal([]), "X''");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_literal<T: AsRef<[u8]>>(input: T) -> String {
+    let input = input.as_ref();
+
+    let mut output = String::with_capacity(3 + input.len() * 2);
+    output.push_str("X'");
+    crate::encode_append_upper(input, &mut output);
+    output.push('\'');
+
+    output
+}
+
+/// Encode `input` as a `0x` prefixed hex literal (`0xDECAFF`), as MySQL and SQL Server allow.
+///
+/// The hex is uppercase, and the output is allocated at exactly the right size up front.
+/// For empty input, that's just `0x`, which not every database accepts, [`encode_literal`] works everywhere.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::sql::encode_literal_prefixed(b"hi"), "0x6869");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_literal_prefixed<T: AsRef<[u8]>>(input: T) -> String {
+    let input = input.as_ref();
+
+    let mut output = String::with_capacity(2 + input.len() * 2);
+    output.push_str("0x");
+    crate::encode_append_upper(input, &mut output);
+
+    output
+}
+
+/// Write `input` to `output` as a standard SQL hex literal, `X'DECAFF'`.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// let mut query = String::from("SELECT * FROM keys WHERE id = ");
+///
+/// hector::sql::encode_literal_to_fmt([0xc0, 0xff, 0xee], &mut query).unwrap();
+///
+/// assert_eq!(query, "SELECT * FROM keys WHERE id = X'C0FFEE'");
+/// ```
+pub fn encode_literal_to_fmt<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> core::fmt::Result {
+    output.write_str("X'")?;
+    crate::encode_to_fmt_upper(input, output)?;
+    output.write_str("'")
+}
+
+/// Write `input` to `output` as a `0x` prefixed hex literal, `0xDECAFF`, see [`encode_literal_prefixed`].
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// let mut query = String::from("UPDATE keys SET id = ");
+///
+/// hector::sql::encode_literal_prefixed_to_fmt([0xc0, 0xff, 0xee], &mut query).unwrap();
+///
+/// assert_eq!(query, "UPDATE keys SET id = 0xC0FFEE");
+/// ```
+pub fn encode_literal_prefixed_to_fmt<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> core::fmt::Result {
+    output.write_str("0x")?;
+    crate::encode_to_fmt_upper(input, output)
+}
+
+/// Decode a SQL hex literal, either quoted (`X'DECAFF'`) or with a `0x` prefix (`0xDECAFF`).
+///
+/// Like in SQL, the `X` can be either case but the `0x` has to be lowercase,
+/// and a `0x` literal with an odd number of digits has an extra `0` at the start (so `0xfff` is `[0x0f, 0xff]`).
+/// Quoted literals have to have an even number of digits.
+///
+/// This function does _not_ enforce a specific casing convention (for the hex).
+///
+/// # Errors
+/// - [`DecodeError::MissingPrefix`] if `input` doesn't start with `X'`, `x'`, or `0x`.
+/// - [`DecodeError::MissingQuote`] if a quoted literal doesn't end with `'`.
+/// - [`DecodeError::OddLength`] if a quoted literal has an odd number of digits.
+/// - [`DecodeError::InvalidHex`] if a character isn't a valid hex character, the offset is into `input` (counting the prefix).
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::sql::decode_literal("X'DECAFF'"), Ok(vec![0xde, 0xca, 0xff]));
+/// assert_eq!(hector::sql::decode_literal("x'decaff'"), Ok(vec![0xde, 0xca, 0xff]));
+/// assert_eq!(hector::sql::decode_literal("0xDECAFF"), Ok(vec![0xde, 0xca, 0xff]));
+/// assert_eq!(hector::sql::decode_literal("0xfff"), Ok(vec![0x0f, 0xff]));
+/// assert_eq!(hector::sql::decode_literal("X''"), Ok(vec![]));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// assert_eq!(hector::sql::decode_literal("'DECAFF'"), Err(DecodeError::MissingPrefix));
+/// assert_eq!(hector::sql::decode_literal("X'DECAFF"), Err(DecodeError::MissingQuote));
+/// assert_eq!(hector::sql::decode_literal("X'DE'A'"), Err(DecodeError::InvalidHex { offset: 4, value: b'\'' }));
+/// assert_eq!(hector::sql::decode_literal("X'FFF'"), Err(DecodeError::OddLength));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_literal<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, DecodeError> {
+    match input.as_ref() {
+        [b'X' | b'x', b'\'', rest @ ..] => {
+            let hex = rest.strip_suffix(b"'").ok_or(DecodeError::MissingQuote)?;
+
+            crate::decode(hex).map_err(|err| err.offset_by(2))
+        }
+        [b'0', b'x', hex @ ..] => DecodeOptions::new()
+            .odd_length(OddLength::PadStart)
+            .decode(hex)
+            .map_err(|err| err.offset_by(2)),
+        _ => Err(DecodeError::MissingPrefix),
+    }
+}
+
+/// The prefix of a `bytea` in the hex format.
+const BYTEA_PREFIX: &str = r"\x";
+
+/// Encode `input` in PostgreSQL's `bytea` hex format, `\x` followed by lowercase hex.
+///
+/// The output is allocated at exactly the right size up front.
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::sql::encode_bytea(b"hi"), r"\x6869");
+/// assert_eq!(hector::sql::encode_bytea([]), r"\x");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_bytea<T: AsRef<[u8]>>(input: T) -> String {
+    let input = input.as_ref();
+
+    let mut output = String::with_capacity(BYTEA_PREFIX.len() + input.len() * 2);
+    output.push_str(BYTEA_PREFIX);
+    crate::encode_append(input, &mut output);
+
+    output
+}
+
+/// Write `input` to `output` in PostgreSQL's `bytea` hex format, `\x` followed by lowercase hex.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// let mut query = String::from("INSERT INTO blobs VALUES ('");
+///
+/// hector::sql::encode_bytea_to_fmt([0xc0, 0xff, 0xee], &mut query).unwrap();
+/// query.push_str("')");
+///
+/// assert_eq!(query, r"INSERT INTO blobs VALUES ('\xc0ffee')");
+/// ```
+pub fn encode_bytea_to_fmt<T: AsRef<[u8]>, W: core::fmt::Write + ?Sized>(
+    input: T,
+    output: &mut W,
+) -> core::fmt::Result {
+    output.write_str(BYTEA_PREFIX)?;
+    crate::encode_to_fmt(input, output)
+}
+
+/// Decode a `bytea` in PostgreSQL's hex format (`\xdecaff`).
+///
+/// Like PostgreSQL, this allows whitespace between bytes (but not inside them).
+/// The prefix can also be `\\x`, as it is once the backslash has been escaped,
+/// in `COPY` text dumps and string literals without `standard_conforming_strings`.
+///
+/// This function does _not_ enforce a specific casing convention (for the hex, the `x` is always lowercase).
+///
+/// # Errors
+/// - [`DecodeError::MissingPrefix`] if `input` doesn't start with `\x` or `\\x`.
+/// - [`DecodeError::InvalidHex`] if a character isn't a valid hex character (or whitespace between bytes),
+///   the offset is into `input` (counting the prefix).
+/// - [`DecodeError::OddLength`] if `input` ends halfway through a byte (and every character is valid).
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::sql::decode_bytea(r"\xdecaff"), Ok(vec![0xde, 0xca, 0xff]));
+/// assert_eq!(hector::sql::decode_bytea(r"\\xDECAFF"), Ok(vec![0xde, 0xca, 0xff]));
+/// assert_eq!(hector::sql::decode_bytea(r"\x de ca ff"), Ok(vec![0xde, 0xca, 0xff]));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// assert_eq!(hector::sql::decode_bytea("decaff"), Err(DecodeError::MissingPrefix));
+/// assert_eq!(hector::sql::decode_bytea(r"\xd eca"), Err(DecodeError::InvalidHex { offset: 3, value: b' ' }));
+/// assert_eq!(hector::sql::decode_bytea(r"\xdec"), Err(DecodeError::OddLength));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_bytea<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, DecodeError> {
+    let input = input.as_ref();
+
+    let hex = input
+        .strip_prefix(BYTEA_PREFIX.as_bytes())
+        .or_else(|| input.strip_prefix(br"\\x"))
+        .ok_or(DecodeError::MissingPrefix)?;
+
+    // Exact for valid input without whitespace.
+    let mut output = Vec::with_capacity(hex.len() / 2);
+
+    let mut rest = hex;
+    while let Some((&first, tail)) = rest.split_first() {
+        let offset = input.len() - rest.len();
+
+        if is_space(first) {
+            rest = tail;
+            continue;
+        }
+
+        let [high, low, tail @ ..] = rest else {
+            // A lone character that isn't hex is reported as such, like `DecodeOptions` does.
+            crate::decode::decode_nibble(first, offset)?;
+            return Err(DecodeError::OddLength);
+        };
+
+        output.push(crate::decode::decode_pair([*high, *low], offset)?);
+        rest = tail;
+    }
+
+    Ok(output)
+}
+
+/// Whether `byte` is whitespace that PostgreSQL allows between bytes.
+#[cfg(feature = "alloc")]
+fn is_space(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decode_bytea, decode_literal, encode_bytea, encode_literal, encode_literal_prefixed,
+    };
+
+    #[test]
+    fn bytea_round_trips() {
+        let input: std::vec::Vec<u8> = (0..=u8::MAX).collect();
+
+        let encoded = encode_bytea(&input);
+        assert_eq!(encoded.capacity(), encoded.len());
+        assert_eq!(decode_bytea(&encoded), Ok(input.clone()));
+
+        // As it'd appear in a `COPY` dump.
+        let escaped = encoded.replace('\\', r"\\");
+        assert_eq!(decode_bytea(escaped), Ok(input));
+    }
+
+    #[test]
+    fn bytea_whitespace() {
+        assert_eq!(decode_bytea("\\x\r\n00\t01 \n"), Ok(std::vec![0, 1]));
+        assert_eq!(decode_bytea(r"\x "), Ok(std::vec![]));
+        assert_eq!(
+            decode_bytea(r" \x00"),
+            Err(crate::DecodeError::MissingPrefix)
+        );
+    }
+
+    #[test]
+    fn bytea_invalid_before_odd_length() {
+        assert_eq!(
+            decode_bytea(r"\xdeg"),
+            Err(crate::DecodeError::InvalidHex {
+                offset: 4,
+                value: b'g'
+            })
+        );
+        assert_eq!(decode_bytea(r"\xdef"), Err(crate::DecodeError::OddLength));
+    }
+
+    #[test]
+    fn literal_round_trips() {
+        let input: std::vec::Vec<u8> = (0..=u8::MAX).collect();
+
+        for encoded in [encode_literal(&input), encode_literal_prefixed(&input)] {
+            assert_eq!(encoded.capacity(), encoded.len());
+            assert_eq!(decode_literal(&encoded), Ok(input.clone()));
+        }
+
+        assert_eq!(decode_literal("0x"), Ok(std::vec![]));
+        assert_eq!(
+            decode_literal("0X00"),
+            Err(crate::DecodeError::MissingPrefix)
+        );
+        assert_eq!(decode_literal("X'"), Err(crate::DecodeError::MissingQuote));
+    }
+}
@@ -0,0 +1,396 @@
+//! Sans-io incremental encoding and decoding,
+//! for pushing arbitrarily large payloads through fixed size buffers without needing `std`.
+
+use crate::decode::decode_pair;
+use crate::encode::byte_to_hex;
+use crate::DecodeError;
+
+/// An incremental hex encoder.
+///
+/// Each call to [`encode_chunk`](Self::encode_chunk) encodes as much of the input as fits in the output,
+/// so both can be any size, the output is always completely filled unless the input runs out.
+///
+/// # Examples
+/// ```
+/// let mut encoder = hector::stream::Encoder::new();
+/// let mut input: &[u8] = &[0xde, 0xca, 0xff];
+///
+/// let mut scratch = [0; 5];
+/// let mut output = Vec::new();
+///
+/// while !input.is_empty() || encoder.has_pending() {
+///     let (consumed, written) = encoder.encode_chunk(input, &mut scratch);
+///     input = &input[consumed..];
+///     output.extend_from_slice(&scratch[..written]);
+/// }
+///
+/// assert_eq!(output, b"decaff");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Encoder {
+    /// The second character of a byte that didn't fit in the last output.
+    pending: Option<u8>,
+    upper: bool,
+}
+
+impl Encoder {
+    /// Create a new encoder, encoding to lowercase hex.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            pending: None,
+            upper: false,
+        }
+    }
+
+    /// Create a new encoder, encoding to uppercase hex.
+    #[must_use]
+    pub fn new_upper() -> Self {
+        Self {
+            pending: None,
+            upper: true,
+        }
+    }
+
+    /// Whether there's a character left over from the previous call that still has to be written.
+    ///
+    /// To write it, call [`encode_chunk`](Self::encode_chunk) with an empty input.
+    #[must_use]
+    pub fn has_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Encode as much of `input` into `output` as fits,
+    /// returning how many bytes were consumed from `input` and how many were written to `output`.
+    ///
+    /// If `output` has an odd amount of space left, the last byte is split,
+    /// its second character will be written at the start of the next output.
+    pub fn encode_chunk(&mut self, input: &[u8], output: &mut [u8]) -> (usize, usize) {
+        let mut written = 0;
+
+        if let Some(char) = self.pending {
+            let Some(first) = output.first_mut() else {
+                return (0, 0);
+            };
+
+            *first = char;
+            self.pending = None;
+            written = 1;
+        }
+
+        let consumed = input.len().min((output.len() - written) / 2);
+        let (whole, output) = output[written..].split_at_mut(consumed * 2);
+
+        // `whole` is exactly the right length, so this can't fail.
+        let result = match self.upper {
+            false => crate::encode_to_slice(&input[..consumed], whole),
+            true => crate::encode_to_slice_upper(&input[..consumed], whole),
+        };
+        debug_assert!(result.is_ok());
+
+        written += consumed * 2;
+
+        // Fill the last spot in the output with half a byte.
+        match (input.get(consumed), output.first_mut()) {
+            (Some(&byte), Some(first)) => {
+                let [high, low] = match self.upper {
+                    false => byte_to_hex::<false>(byte),
+                    true => byte_to_hex::<true>(byte),
+                };
+
+                *first = high;
+                self.pending = Some(low);
+
+                (consumed + 1, written + 1)
+            }
+            _ => (consumed, written),
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Encoder {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.pending);
+    }
+}
+
+/// An incremental hex decoder.
+///
+/// Each call to [`decode_chunk`](Self::decode_chunk) decodes as much of the input as fits in the output,
+/// the input can be split up anywhere (even in the middle of a byte).
+///
+/// This does _not_ enforce a specific casing convention.
+///
+/// # Examples
+/// ```
+/// let mut decoder = hector::stream::Decoder::new();
+/// let mut output = [0; 3];
+///
+/// assert_eq!(decoder.decode_chunk(b"dec", &mut output), Ok((3, 1)));
+/// assert_eq!(decoder.decode_chunk(b"aff", &mut output[1..]), Ok((3, 2)));
+/// assert_eq!(decoder.finish(), Ok(()));
+///
+/// assert_eq!(output, [0xde, 0xca, 0xff]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Decoder {
+    /// The first character of a byte that was split across inputs (already checked to be valid hex).
+    pending: Option<u8>,
+    /// The number of characters consumed so far, across all inputs.
+    consumed: usize,
+}
+
+impl Decoder {
+    /// Create a new decoder.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            pending: None,
+            consumed: 0,
+        }
+    }
+
+    /// The number of hex characters consumed so far, across all inputs.
+    #[must_use]
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Decode as much of `input` into `output` as fits,
+    /// returning how many bytes were consumed from `input` and how many were written to `output`.
+    ///
+    /// A trailing unpaired character is consumed (without writing anything) and paired up with the start of the next input.
+    ///
+    /// # Errors
+    /// - [`DecodeError::InvalidHex`] if the next character to consume isn't a valid hex character,
+    ///   `offset` is counted from the start of the first input.
+    ///
+    /// Any bytes before an invalid character are returned first (as `Ok`),
+    /// so the error shows up when the rest of the input is passed in again.
+    ///
+    /// # Examples
+    /// ```
+    /// use hector::DecodeError;
+    ///
+    /// let mut decoder = hector::stream::Decoder::new();
+    /// let mut output = [0; 8];
+    ///
+    /// assert_eq!(decoder.decode_chunk(b"c0ff", &mut output), Ok((4, 2)));
+    /// assert_eq!(decoder.decode_chunk(b"eeg", &mut output), Ok((2, 1)));
+    /// assert_eq!(
+    ///     decoder.decode_chunk(b"g", &mut output),
+    ///     Err(DecodeError::InvalidHex { offset: 6, value: b'g' })
+    /// );
+    /// ```
+    pub fn decode_chunk(
+        &mut self,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<(usize, usize), DecodeError> {
+        let result = self.decode_chunk_inner(input, output);
+
+        if let Ok((consumed, _)) = result {
+            self.consumed += consumed;
+        }
+
+        result
+    }
+
+    fn decode_chunk_inner(
+        &mut self,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<(usize, usize), DecodeError> {
+        let (mut consumed, mut written) = (0, 0);
+
+        if let Some(high) = self.pending {
+            let (Some(&low), Some(first)) = (input.first(), output.first_mut()) else {
+                return Ok((0, 0));
+            };
+
+            // `high` was already checked, so any error is at `low`.
+            *first = decode_pair([high, low], self.consumed - 1)?;
+            self.pending = None;
+
+            (consumed, written) = (1, 1);
+        }
+
+        let len = ((input.len() - consumed) / 2).min(output.len() - written);
+        let (whole, rest) = input[consumed..].split_at(len * 2);
+        let output = &mut output[written..][..len];
+
+        match crate::decode_to_slice(whole, output) {
+            Ok(_) => {}
+            Err(DecodeError::InvalidHex { offset, value }) => {
+                // Hand out everything before the error first (which is all valid).
+                let len = offset / 2;
+                let result = crate::decode_to_slice(&whole[..len * 2], &mut output[..len]);
+                debug_assert!(result.is_ok());
+
+                return match (consumed, len) {
+                    (0, 0) => Err(DecodeError::InvalidHex {
+                        offset: self.consumed + offset,
+                        value,
+                    }),
+                    _ => Ok((consumed + len * 2, written + len)),
+                };
+            }
+            Err(err) => return Err(err),
+        }
+
+        (consumed, written) = (consumed + len * 2, written + len);
+
+        // Hold onto half a byte until the next input.
+        if let &[char] = rest {
+            if !char.is_ascii_hexdigit() {
+                return match consumed {
+                    0 => Err(DecodeError::InvalidHex {
+                        offset: self.consumed,
+                        value: char,
+                    }),
+                    _ => Ok((consumed, written)),
+                };
+            }
+
+            self.pending = Some(char);
+            consumed += 1;
+        }
+
+        Ok((consumed, written))
+    }
+
+    /// Finish decoding, checking that the input didn't end in the middle of a byte.
+    ///
+    /// # Errors
+    /// - [`DecodeError::OddLength`] if there's an unpaired character left over.
+    ///
+    /// # Examples
+    /// ```
+    /// use hector::DecodeError;
+    ///
+    /// let mut decoder = hector::stream::Decoder::new();
+    ///
+    /// assert_eq!(decoder.decode_chunk(b"c0f", &mut [0; 4]), Ok((3, 1)));
+    /// assert_eq!(decoder.finish(), Err(DecodeError::OddLength));
+    /// ```
+    pub fn finish(self) -> Result<(), DecodeError> {
+        match self.pending {
+            Some(_) => Err(DecodeError::OddLength),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Decoder {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.pending);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use super::{Decoder, Encoder};
+    use crate::DecodeError;
+
+    #[test]
+    fn encoder_matches_encode() {
+        let input = crate::test_util::bytes(100);
+
+        for scratch_len in 1..=9 {
+            let mut encoder = Encoder::new_upper();
+            let mut scratch = std::vec![0; scratch_len];
+            let mut output = Vec::new();
+
+            let mut remaining = &input[..];
+            while !remaining.is_empty() || encoder.has_pending() {
+                let (consumed, written) = encoder.encode_chunk(remaining, &mut scratch);
+                remaining = &remaining[consumed..];
+                output.extend_from_slice(&scratch[..written]);
+            }
+
+            assert_eq!(
+                output,
+                crate::encode_upper(&input).into_bytes(),
+                "scratch_len: {scratch_len}"
+            );
+        }
+    }
+
+    #[test]
+    fn encoder_empty_output() {
+        let mut encoder = Encoder::new();
+
+        assert_eq!(encoder.encode_chunk(&[0xde], &mut [0]), (1, 1));
+        assert_eq!(encoder.encode_chunk(&[0xca], &mut []), (0, 0));
+        assert!(encoder.has_pending());
+    }
+
+    #[test]
+    fn decoder_matches_decode() {
+        let bytes = crate::test_util::bytes(100);
+        let input = crate::encode(&bytes);
+
+        for chunk_len in 1..=9 {
+            for scratch_len in 1..=5 {
+                let mut decoder = Decoder::new();
+                let mut scratch = std::vec![0; scratch_len];
+                let mut output = Vec::new();
+
+                for mut chunk in input.as_bytes().chunks(chunk_len) {
+                    while !chunk.is_empty() {
+                        let (consumed, written) =
+                            decoder.decode_chunk(chunk, &mut scratch).unwrap();
+
+                        chunk = &chunk[consumed..];
+                        output.extend_from_slice(&scratch[..written]);
+                    }
+                }
+
+                assert_eq!(decoder.consumed(), input.len());
+                assert_eq!(decoder.finish(), Ok(()));
+                assert_eq!(
+                    output, bytes,
+                    "chunk_len: {chunk_len}, scratch_len: {scratch_len}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn decoder_absolute_offset() {
+        let mut input = *b"0123456789abcdef";
+
+        for offset in 0..input.len() {
+            let value = input[offset];
+            input[offset] = b'x';
+
+            for chunk_len in 1..=5 {
+                let mut decoder = Decoder::new();
+                let mut output = [0; 8];
+
+                let err = input.chunks(chunk_len).find_map(|mut chunk| loop {
+                    match decoder.decode_chunk(chunk, &mut output) {
+                        Ok((consumed, _)) if consumed == chunk.len() => break None,
+                        Ok((consumed, _)) => chunk = &chunk[consumed..],
+                        Err(err) => break Some(err),
+                    }
+                });
+
+                assert_eq!(
+                    err,
+                    Some(DecodeError::InvalidHex {
+                        offset,
+                        value: b'x'
+                    }),
+                    "chunk_len: {chunk_len}"
+                );
+            }
+
+            input[offset] = value;
+        }
+    }
+}
@@ -0,0 +1,8 @@
+//! Helpers shared by the tests.
+
+use std::vec::Vec;
+
+/// `len` bytes of test input, which covers every byte value (once it's long enough) without just counting up.
+pub(crate) fn bytes(len: usize) -> Vec<u8> {
+    (0..len).map(|it| (it as u8).wrapping_mul(0x9d)).collect()
+}
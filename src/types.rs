@@ -0,0 +1,13 @@
+//! Types for bytes that are hex in text form.
+
+mod array;
+#[cfg(feature = "alloc")]
+mod buf;
+#[cfg(not(feature = "safe"))]
+mod str;
+
+pub use array::HexArray;
+#[cfg(feature = "alloc")]
+pub use buf::HexBuf;
+#[cfg(not(feature = "safe"))]
+pub use str::HexStr;
@@ -0,0 +1,183 @@
+//! [`HexArray`], fixed size bytes that are hex in text form.
+
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use core::str::FromStr;
+
+use crate::{display, DecodeError};
+
+/// Fixed size bytes that are hex in text form, for hashes, keys, addresses, and so on.
+///
+/// This parses from hex (via [`FromStr`] or [`TryFrom<&str>`](TryFrom)), which has to decode to exactly `N` bytes,
+/// displays as lowercase hex (like [`HexSlice`](crate::HexSlice), including flags),
+/// and with the `serde` feature, (de)serializes like [`hector::serde`](crate::serde).
+///
+/// # Examples
+/// ```
+/// use hector::HexArray;
+///
+/// let digest: HexArray<4> = "DEADBEEF".parse()?;
+///
+/// assert_eq!(digest.0, [0xde, 0xad, 0xbe, 0xef]);
+/// assert_eq!(digest.to_string(), "deadbeef");
+///
+/// assert!("decaff".parse::<HexArray<4>>().is_err());
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HexArray<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> HexArray<N> {
+    /// Get the underlying bytes.
+    #[must_use]
+    pub fn into_inner(self) -> [u8; N] {
+        self.0
+    }
+}
+
+impl<const N: usize> FromStr for HexArray<N> {
+    type Err = DecodeError;
+
+    fn from_str(input: &str) -> Result<Self, DecodeError> {
+        crate::decode_to_array(input).map(Self)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for HexArray<N> {
+    type Error = DecodeError;
+
+    fn try_from(input: &str) -> Result<Self, DecodeError> {
+        input.parse()
+    }
+}
+
+impl<const N: usize> fmt::Display for HexArray<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&display(&self.0), f)
+    }
+}
+
+impl<const N: usize> fmt::LowerHex for HexArray<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&display(&self.0), f)
+    }
+}
+
+impl<const N: usize> fmt::UpperHex for HexArray<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&display(&self.0), f)
+    }
+}
+
+impl<const N: usize> fmt::Debug for HexArray<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("HexArray").field(&display(&self.0)).finish()
+    }
+}
+
+impl<const N: usize> Deref for HexArray<N> {
+    type Target = [u8; N];
+
+    fn deref(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> DerefMut for HexArray<N> {
+    fn deref_mut(&mut self) -> &mut [u8; N] {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> AsRef<[u8; N]> for HexArray<N> {
+    fn as_ref(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for HexArray<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for HexArray<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl<const N: usize> From<HexArray<N>> for [u8; N] {
+    fn from(array: HexArray<N>) -> Self {
+        array.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> ::serde::Serialize for HexArray<N> {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serde::serialize(&self.0, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> ::serde::Deserialize<'de> for HexArray<N> {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::serde::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<const N: usize> zeroize::Zeroize for HexArray<N> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::format;
+
+    use super::HexArray;
+    use crate::DecodeError;
+
+    #[test]
+    fn hex_array() {
+        let array = HexArray::try_from("c0ffee").unwrap();
+
+        assert_eq!(array, HexArray([0xc0, 0xff, 0xee]));
+        assert_eq!(format!("{array:?}"), "HexArray(c0ffee)");
+        assert_eq!(format!("{array:#X}"), "0xC0FFEE");
+        assert!(array < HexArray([0xc1, 0, 0]));
+        assert_eq!(
+            "c0ff".parse::<HexArray<3>>(),
+            Err(DecodeError::MismatchedLength {
+                source_len: 4,
+                dest_len: 3
+            })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn hex_array_serde() {
+        let array = HexArray([0xc0, 0xff, 0xee]);
+
+        assert_eq!(serde_json::to_string(&array).unwrap(), r#""c0ffee""#);
+        assert_eq!(
+            serde_json::from_str::<HexArray<3>>(r#""C0FFEE""#).unwrap(),
+            array
+        );
+        assert!(serde_json::from_str::<HexArray<2>>(r#""C0FFEE""#).is_err());
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn hex_array_zeroize() {
+        let mut array = HexArray([0xc0, 0xff, 0xee]);
+
+        zeroize::Zeroize::zeroize(&mut array);
+
+        assert_eq!(array, HexArray([0; 3]));
+    }
+}
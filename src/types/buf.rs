@@ -0,0 +1,146 @@
+//! [`HexBuf`], owned bytes that are hex in text form.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use core::str::FromStr;
+
+use crate::{display, DecodeError};
+
+/// Owned bytes that are hex in text form, for configs, CLIs, and so on.
+///
+/// This parses from hex (via [`FromStr`]), displays as lowercase hex (like [`HexSlice`](crate::HexSlice), including flags),
+/// and with the `serde` feature, (de)serializes like [`hector::serde`](crate::serde).
+///
+/// # Examples
+/// ```
+/// use hector::HexBuf;
+///
+/// let key: HexBuf = "DECAFF".parse()?;
+///
+/// assert_eq!(&*key, [0xde, 0xca, 0xff]);
+/// assert_eq!(key.to_string(), "decaff");
+/// assert_eq!(format!("{key:#X}"), "0xDECAFF");
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HexBuf(pub Vec<u8>);
+
+impl HexBuf {
+    /// Get the underlying bytes.
+    #[must_use]
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl FromStr for HexBuf {
+    type Err = DecodeError;
+
+    fn from_str(input: &str) -> Result<Self, DecodeError> {
+        crate::decode(input).map(Self)
+    }
+}
+
+impl fmt::Display for HexBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&display(&self.0), f)
+    }
+}
+
+impl fmt::LowerHex for HexBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&display(&self.0), f)
+    }
+}
+
+impl fmt::UpperHex for HexBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&display(&self.0), f)
+    }
+}
+
+impl fmt::Debug for HexBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("HexBuf").field(&display(&self.0)).finish()
+    }
+}
+
+impl Deref for HexBuf {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl DerefMut for HexBuf {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl AsRef<[u8]> for HexBuf {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for HexBuf {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<HexBuf> for Vec<u8> {
+    fn from(buf: HexBuf) -> Self {
+        buf.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for HexBuf {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serde::serialize(&self.0, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for HexBuf {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::serde::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for HexBuf {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::format;
+
+    use super::HexBuf;
+    use crate::DecodeError;
+
+    #[test]
+    fn hex_buf() {
+        let buf: HexBuf = "c0ffee".parse().unwrap();
+
+        assert_eq!(format!("{buf:?}"), "HexBuf(c0ffee)");
+        assert_eq!(format!("{buf:X}"), "C0FFEE");
+        assert_eq!("c0ffe".parse::<HexBuf>(), Err(DecodeError::OddLength));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn hex_buf_serde() {
+        let buf = HexBuf(std::vec![0xc0, 0xff, 0xee]);
+
+        assert_eq!(serde_json::to_string(&buf).unwrap(), r#""c0ffee""#);
+        assert_eq!(serde_json::from_str::<HexBuf>(r#""C0FFEE""#).unwrap(), buf);
+    }
+}
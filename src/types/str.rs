@@ -0,0 +1,229 @@
+//! [`HexStr`], a borrowed string that's known to be valid hex.
+
+use core::fmt;
+
+use crate::DecodeError;
+
+/// A string that's known to be valid hex (like [`str`], but for hex).
+///
+/// Since it's validated when it's created, decoding it can skip validation,
+/// and APIs can use it to express "this has to already be hex" in their types.
+///
+/// This does _not_ enforce a specific casing convention,
+/// but [`make_ascii_lowercase`](Self::make_ascii_lowercase) and friends can normalize it.
+///
+/// # Examples
+/// ```
+/// use hector::HexStr;
+///
+/// let hex = HexStr::new("C0FFEE")?;
+///
+/// assert_eq!(hex.decoded_len(), 3);
+/// assert_eq!(hex.decode(), [0xc0, 0xff, 0xee]);
+///
+/// assert!(HexStr::new("hector").is_err());
+/// # Ok::<(), hector::DecodeError>(())
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct HexStr(str);
+
+impl HexStr {
+    /// Check that `input` is valid hex, and wrap it.
+    ///
+    /// # Errors
+    /// - [`DecodeError::OddLength`] if `input.len()` is not even.
+    /// - [`DecodeError::InvalidHex`] if any character isn't a valid hex character.
+    pub fn new(input: &str) -> Result<&Self, DecodeError> {
+        crate::validate(input)?;
+
+        // Safety: `input` was just validated.
+        Ok(unsafe { Self::new_unchecked(input) })
+    }
+
+    /// Check that `input` is valid hex, and wrap it.
+    ///
+    /// # Errors
+    /// - [`DecodeError::OddLength`] if `input.len()` is not even.
+    /// - [`DecodeError::InvalidHex`] if any character isn't a valid hex character.
+    ///
+    /// # Examples
+    /// ```
+    /// use hector::HexStr;
+    ///
+    /// assert_eq!(HexStr::from_bytes(b"decaff").map(HexStr::as_str), Ok("decaff"));
+    /// ```
+    pub fn from_bytes(input: &[u8]) -> Result<&Self, DecodeError> {
+        crate::validate(input)?;
+
+        let input = crate::output::hex_str(input);
+
+        // Safety: `input` was just validated.
+        Ok(unsafe { Self::new_unchecked(input) })
+    }
+
+    /// Check that `input` is valid hex, and wrap it mutably (for normalizing the case in place).
+    ///
+    /// # Errors
+    /// - [`DecodeError::OddLength`] if `input.len()` is not even.
+    /// - [`DecodeError::InvalidHex`] if any character isn't a valid hex character.
+    pub fn new_mut(input: &mut str) -> Result<&mut Self, DecodeError> {
+        crate::validate(&*input)?;
+
+        // Safety: `HexStr` is a `repr(transparent)` wrapper around `str`, and `input` was just validated.
+        Ok(unsafe { &mut *(input as *mut str as *mut Self) })
+    }
+
+    /// Wrap `input`, without checking that it's valid hex.
+    ///
+    /// # Safety
+    /// `input` must be valid hex, that is, [`validate(input)`](crate::validate) must return `Ok`.
+    #[must_use]
+    pub unsafe fn new_unchecked(input: &str) -> &Self {
+        // Safety: `HexStr` is a `repr(transparent)` wrapper around `str`, the caller guarantees `input` is valid.
+        unsafe { &*(input as *const str as *const Self) }
+    }
+
+    /// Get the hex as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Get the hex as a byte slice.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    /// The number of bytes this decodes to.
+    #[must_use]
+    pub fn decoded_len(&self) -> usize {
+        self.0.len() / 2
+    }
+
+    /// Decode into `output`, without validating again.
+    ///
+    /// For convenience, this returns a reference to `output`.
+    ///
+    /// # Errors
+    /// - [`DecodeError::MismatchedLength`] if `output.len() != self.decoded_len()`.
+    ///
+    /// # Examples
+    /// ```
+    /// let hex = hector::HexStr::new("decaff")?;
+    /// let mut storage = [0; 3];
+    ///
+    /// assert_eq!(hex.decode_to_slice(&mut storage), Ok(&[0xde, 0xca, 0xff][..]));
+    /// # Ok::<(), hector::DecodeError>(())
+    /// ```
+    pub fn decode_to_slice<'a>(&self, output: &'a mut [u8]) -> Result<&'a [u8], DecodeError> {
+        // Safety: `self` is always valid hex.
+        unsafe { crate::decode_to_slice_unchecked(&self.0, output) }
+    }
+
+    /// Decode into a new `Vec`, without validating again.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn decode(&self) -> alloc::vec::Vec<u8> {
+        // Safety: `self` is always valid hex.
+        unsafe { crate::decode_unchecked(&self.0) }
+    }
+
+    /// Convert the hex to lowercase in place, it stays valid hex.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut storage = String::from("DeCaFF");
+    /// let hex = hector::HexStr::new_mut(&mut storage)?;
+    ///
+    /// hex.make_ascii_lowercase();
+    ///
+    /// assert_eq!(hex.as_str(), "decaff");
+    /// # Ok::<(), hector::DecodeError>(())
+    /// ```
+    pub fn make_ascii_lowercase(&mut self) {
+        self.0.make_ascii_lowercase();
+    }
+
+    /// Convert the hex to uppercase in place, it stays valid hex.
+    pub fn make_ascii_uppercase(&mut self) {
+        self.0.make_ascii_uppercase();
+    }
+}
+
+impl<'a> TryFrom<&'a str> for &'a HexStr {
+    type Error = DecodeError;
+
+    fn try_from(input: &'a str) -> Result<Self, DecodeError> {
+        HexStr::new(input)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for &'a HexStr {
+    type Error = DecodeError;
+
+    fn try_from(input: &'a [u8]) -> Result<Self, DecodeError> {
+        HexStr::from_bytes(input)
+    }
+}
+
+impl AsRef<str> for HexStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for HexStr {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl<'a> From<&'a HexStr> for &'a str {
+    fn from(hex: &'a HexStr) -> Self {
+        &hex.0
+    }
+}
+
+impl fmt::Display for HexStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Debug for HexStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HexStr;
+    use crate::DecodeError;
+
+    #[test]
+    fn hex_str() {
+        assert_eq!(HexStr::new("abc"), Err(DecodeError::OddLength));
+        assert_eq!(
+            HexStr::from_bytes(b"ab\xff\xff"),
+            Err(DecodeError::InvalidHex {
+                offset: 2,
+                value: 0xff
+            })
+        );
+
+        let mut storage = std::string::String::from("c0FFee");
+        let hex = HexStr::new_mut(&mut storage).unwrap();
+        hex.make_ascii_uppercase();
+        assert_eq!(hex.as_str(), "C0FFEE");
+        assert_eq!(
+            hex.decode_to_slice(&mut [0; 2]),
+            Err(DecodeError::MismatchedLength {
+                source_len: 6,
+                dest_len: 2
+            })
+        );
+    }
+}
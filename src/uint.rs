@@ -0,0 +1,487 @@
+//! Encoding unsigned integers as hex into a buffer on the stack (without going through `core::fmt`),
+//! decoding them straight from hex, and encoding slices of them as words in either byte order.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::scratch::Scratch;
+use crate::{output, DecodeError};
+
+/// The most hex characters an integer can take (for `u128`).
+const MAX_LEN: usize = 32;
+
+/// The unsigned integer types that [`encode_uint`], [`decode_uint`], and the word encoders (like [`encode_words`]) work with.
+///
+/// This trait is sealed, it can't be implemented outside of hector.
+pub trait Uint: Copy + private::Sealed {
+    /// The number of hex characters it takes to write every nibble.
+    #[doc(hidden)]
+    const HEX_LEN: usize;
+
+    /// Write every nibble to the end of `output`,
+    /// returning how many characters are left without the leading zeros (always at least 1).
+    #[doc(hidden)]
+    fn write_hex<const UPPER: bool>(self, output: &mut [u8; MAX_LEN]) -> usize;
+
+    /// Decode `hex` (without a prefix), as a number.
+    #[doc(hidden)]
+    fn from_hex(hex: &[u8]) -> Result<Self, DecodeError>;
+
+    /// Reverse the byte order.
+    #[doc(hidden)]
+    #[must_use]
+    fn swap_bytes(self) -> Self;
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_uint {
+    ($($ty:ty),*) => {$(
+        impl private::Sealed for $ty {}
+
+        impl Uint for $ty {
+            const HEX_LEN: usize = core::mem::size_of::<$ty>() * 2;
+
+            fn write_hex<const UPPER: bool>(self, output: &mut [u8; MAX_LEN]) -> usize {
+                let hex = output[MAX_LEN - Self::HEX_LEN..].chunks_exact_mut(2);
+                for (pair, byte) in hex.zip(self.to_be_bytes()) {
+                    pair.copy_from_slice(&crate::encode::byte_to_hex::<UPPER>(byte));
+                }
+
+                let significant_bits = (<$ty>::BITS - self.leading_zeros()) as usize;
+                significant_bits.div_ceil(4).max(1)
+            }
+
+            fn from_hex(hex: &[u8]) -> Result<Self, DecodeError> {
+                let mut value: Self = 0;
+
+                for (offset, &digit) in hex.iter().enumerate() {
+                    let nibble = crate::decode::decode_nibble(digit, offset)?;
+
+                    // Leading zeros never overflow, however many of them there are.
+                    if value > Self::MAX >> 4 {
+                        return Err(DecodeError::Overflow);
+                    }
+
+                    value = value << 4 | Self::from(nibble);
+                }
+
+                Ok(value)
+            }
+
+            fn swap_bytes(self) -> Self {
+                <$ty>::swap_bytes(self)
+            }
+        }
+    )*};
+}
+
+impl_uint!(u8, u16, u32, u64, u128, usize);
+
+/// Space on the stack for [`encode_uint`] to write to, big enough for any integer.
+///
+/// # Examples
+/// ```
+/// let mut buffer = hector::UintBuffer::new();
+///
+/// assert_eq!(hector::encode_uint(0x2a_u8, &mut buffer), "2a");
+/// assert_eq!(hector::encode_uint(0xdead_u32, &mut buffer), "0000dead");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct UintBuffer {
+    bytes: [u8; MAX_LEN],
+}
+
+impl Default for UintBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UintBuffer {
+    /// A new buffer.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            bytes: [b'0'; MAX_LEN],
+        }
+    }
+}
+
+/// Encoding integers with a given width or case, see [`encode_uint`].
+///
+/// By default, this encodes exactly like [`encode_uint`].
+///
+/// # Examples
+/// ```
+/// use hector::{UintBuffer, UintOptions};
+///
+/// let mut buffer = UintBuffer::new();
+///
+/// let register = UintOptions::new().trim(true).min_width(2).uppercase(true);
+/// assert_eq!(register.encode(0x7_u32, &mut buffer), "07");
+/// assert_eq!(register.encode(0x1f00_u32, &mut buffer), "1F00");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct UintOptions {
+    upper: bool,
+    trim: bool,
+    min_width: usize,
+}
+
+impl Default for UintOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UintOptions {
+    /// Options that encode exactly like [`encode_uint`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            upper: false,
+            trim: false,
+            min_width: 0,
+        }
+    }
+
+    /// Whether to use uppercase hex characters.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::UintOptions::new().uppercase(true);
+    ///
+    /// assert_eq!(options.encode(0xbeef_u16, &mut hector::UintBuffer::new()), "BEEF");
+    /// ```
+    #[must_use]
+    pub const fn uppercase(mut self, upper: bool) -> Self {
+        self.upper = upper;
+        self
+    }
+
+    /// Whether to leave out leading zeros, zero is still written as `0`.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::UintOptions::new().trim(true);
+    /// let mut buffer = hector::UintBuffer::new();
+    ///
+    /// assert_eq!(options.encode(0xbeef_u64, &mut buffer), "beef");
+    /// assert_eq!(options.encode(0_u64, &mut buffer), "0");
+    /// ```
+    #[must_use]
+    pub const fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Pad the output with leading zeros to at least `width` characters, `0` by default.
+    ///
+    /// This is mostly useful with [`trim`](Self::trim), since otherwise the output is already as wide as the integer,
+    /// but it can pad beyond that as well.
+    ///
+    /// # Panics
+    /// If `width` is more than 32 (the width of a `u128`).
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::UintOptions::new().min_width(4);
+    /// let mut buffer = hector::UintBuffer::new();
+    ///
+    /// assert_eq!(options.encode(0xa_u8, &mut buffer), "000a");
+    /// assert_eq!(options.encode(0xa_u32, &mut buffer), "0000000a");
+    /// assert_eq!(options.trim(true).encode(0xa_u32, &mut buffer), "000a");
+    /// ```
+    #[must_use]
+    pub const fn min_width(mut self, width: usize) -> Self {
+        assert!(width <= MAX_LEN, "the minimum width can be at most 32");
+
+        self.min_width = width;
+        self
+    }
+
+    /// Encode `value` into `buffer` with these options.
+    ///
+    /// For convenience, this returns a [`&str`](str), backed by `buffer`.
+    ///
+    /// # Examples
+    /// ```
+    /// let options = hector::UintOptions::new().trim(true);
+    ///
+    /// assert_eq!(options.encode(0x1_0000_usize, &mut hector::UintBuffer::new()), "10000");
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn encode<'a, T: Uint>(&self, value: T, buffer: &'a mut UintBuffer) -> &'a str {
+        let significant = if self.upper {
+            value.write_hex::<true>(&mut buffer.bytes)
+        } else {
+            value.write_hex::<false>(&mut buffer.bytes)
+        };
+
+        // Anything before the integer is padding, which might have been written over by a wider integer before.
+        buffer.bytes[..MAX_LEN - T::HEX_LEN].fill(b'0');
+
+        let len = if self.trim { significant } else { T::HEX_LEN };
+        let len = len.max(self.min_width);
+
+        output::hex_str(&buffer.bytes[MAX_LEN.saturating_sub(len)..])
+    }
+}
+
+/// Encode `value` into `buffer` as lowercase hex, with every nibble (like `format!("{value:08x}")` for a `u32`).
+///
+/// For convenience, this returns a [`&str`](str), backed by `buffer`.
+///
+/// This doesn't go through `core::fmt` or allocate, see [`UintOptions`] for trimming leading zeros and other options.
+///
+/// # Examples
+/// ```
+/// let mut buffer = hector::UintBuffer::new();
+///
+/// assert_eq!(hector::encode_uint(0x1f_u8, &mut buffer), "1f");
+/// assert_eq!(hector::encode_uint(0xc0ffee_u32, &mut buffer), "00c0ffee");
+/// assert_eq!(hector::encode_uint(u64::MAX, &mut buffer), "ffffffffffffffff");
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn encode_uint<T: Uint>(value: T, buffer: &mut UintBuffer) -> &str {
+    UintOptions::new().encode(value, buffer)
+}
+
+/// Decode the hex number in `input`, which can start with `0x` (or `0X`).
+///
+/// Any number of hex characters is fine (odd or even), as long as the number fits in `T`.
+///
+/// This function does _not_ enforce a specific casing convention.
+///
+/// # Errors
+/// - [`DecodeError::Overflow`] if the number is too big for `T`.
+/// - [`DecodeError::InvalidHex`] if any character isn't a valid hex character,
+///   the offset is into `input` (counting the prefix).
+/// - [`DecodeError::NonCanonical`] if there aren't any hex characters (after the prefix),
+///   the same as [`decode_trimmed`](crate::decode_trimmed).
+///
+/// # Examples
+/// ```
+/// assert_eq!(hector::decode_uint::<u32>("0xc0ffee"), Ok(0xc0ffee));
+/// assert_eq!(hector::decode_uint::<u8>("F"), Ok(0xf));
+/// assert_eq!(hector::decode_uint::<u8>("0x00000000ff"), Ok(0xff));
+/// ```
+///
+/// ```
+/// use hector::DecodeError;
+///
+/// assert_eq!(hector::decode_uint::<u8>("0x100"), Err(DecodeError::Overflow));
+/// assert_eq!(hector::decode_uint::<u16>("0xc0fg"), Err(DecodeError::InvalidHex { offset: 5, value: b'g' }));
+/// assert_eq!(hector::decode_uint::<u16>("0x"), Err(DecodeError::NonCanonical));
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn decode_uint<T: Uint>(input: impl AsRef<[u8]>) -> Result<T, DecodeError> {
+    let input = input.as_ref();
+
+    let (hex, base) = match input {
+        [b'0', b'x' | b'X', hex @ ..] => (hex, 2),
+        hex => (hex, 0),
+    };
+
+    if hex.is_empty() {
+        return Err(DecodeError::NonCanonical);
+    }
+
+    T::from_hex(hex).map_err(|err| err.offset_by(base))
+}
+
+/// The byte order that [`encode_words`] writes each word in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Endian {
+    /// Most significant byte first, the way debuggers show memory as words (and the way numbers are written).
+    Big,
+
+    /// Least significant byte first, the way words are laid out in memory on most CPUs.
+    Little,
+}
+
+/// Encode each of `words` in `endian` byte order, with `separator` between each word (if there is one),
+/// passing each piece to `write`.
+fn encode_words_with<E, T: Uint>(
+    words: &[T],
+    endian: Endian,
+    separator: Option<char>,
+    mut write: impl FnMut(&str) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut buffer = Scratch([0; MAX_LEN]);
+    let mut separator_buffer = [0; 4];
+    let separator = separator.map(|separator| &*separator.encode_utf8(&mut separator_buffer));
+
+    for (index, &word) in words.iter().enumerate() {
+        if let (Some(separator), 1..) = (separator, index) {
+            write(separator)?;
+        }
+
+        let word = match endian {
+            Endian::Big => word,
+            Endian::Little => word.swap_bytes(),
+        };
+        word.write_hex::<false>(&mut buffer.0);
+
+        write(output::hex_str(&buffer[MAX_LEN - T::HEX_LEN..]))?;
+    }
+
+    Ok(())
+}
+
+/// Encode `words` to a lowercase hex string, each in `endian` byte order, with `separator` between each word (if there is one).
+///
+/// The output is allocated at exactly the right size up front.
+///
+/// # Examples
+/// ```
+/// use hector::Endian;
+///
+/// assert_eq!(hector::encode_words(&[0xdead_beef_u32, 0x1], Endian::Big, None), "deadbeef00000001");
+/// assert_eq!(hector::encode_words(&[0xdead_beef_u32, 0x1], Endian::Little, None), "efbeadde01000000");
+/// assert_eq!(hector::encode_words(&[0xc0ff_u16], Endian::Big, None), "c0ff");
+/// ```
+///
+/// ```
+/// use hector::Endian;
+///
+/// let memory = [0x2000_1000_u32, 0x0800_0101, 0x0800_0141];
+///
+/// assert_eq!(hector::encode_words(&memory, Endian::Big, Some(' ')), "20001000 08000101 08000141");
+/// assert_eq!(hector::encode_words(&[0x0102_u16, 0x0304], Endian::Little, Some(':')), "0201:0403");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_words<T: Uint>(words: &[T], endian: Endian, separator: Option<char>) -> String {
+    let separators = words.len().saturating_sub(1) * separator.map_or(0, char::len_utf8);
+    output::collect_string(words.len() * T::HEX_LEN + separators, |write| {
+        encode_words_with(words, endian, separator, write)
+    })
+}
+
+/// Write `words` to `output` as lowercase hex, each in `endian` byte order, with `separator` between each word (if there is one).
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// let mut output = String::from("r0-r1: ");
+///
+/// hector::encode_words_to_fmt(&[0x1_u32, 0xffff_0000], hector::Endian::Little, None, &mut output).unwrap();
+///
+/// assert_eq!(output, "r0-r1: 010000000000ffff");
+/// ```
+///
+/// ```
+/// struct Memory<'a>(&'a [u32]);
+///
+/// impl core::fmt::Display for Memory<'_> {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         hector::encode_words_to_fmt(self.0, hector::Endian::Big, Some(' '), f)
+///     }
+/// }
+///
+/// assert_eq!(Memory(&[0x2000_1000, 0x0800_0101]).to_string(), "20001000 08000101");
+/// ```
+pub fn encode_words_to_fmt<T: Uint, W: core::fmt::Write + ?Sized>(
+    words: &[T],
+    endian: Endian,
+    separator: Option<char>,
+    output: &mut W,
+) -> core::fmt::Result {
+    encode_words_with(words, endian, separator, |chunk| output.write_str(chunk))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::format;
+
+    use super::{decode_uint, encode_uint, encode_words, Endian, UintBuffer, UintOptions};
+    use crate::DecodeError;
+
+    #[test]
+    fn encode_uint_matches_fmt() {
+        let mut buffer = UintBuffer::new();
+        let trimmed = UintOptions::new().trim(true);
+        let upper = UintOptions::new().uppercase(true);
+
+        for value in [0, 1, 0xf, 0x10, 0xdead_beef, u64::MAX >> 4, u64::MAX] {
+            assert_eq!(encode_uint(value, &mut buffer), format!("{value:016x}"));
+            assert_eq!(trimmed.encode(value, &mut buffer), format!("{value:x}"));
+            assert_eq!(upper.encode(value, &mut buffer), format!("{value:016X}"));
+
+            let value = u128::from(value) << 64 | 0x1f;
+            assert_eq!(encode_uint(value, &mut buffer), format!("{value:032x}"));
+            assert_eq!(trimmed.encode(value, &mut buffer), format!("{value:x}"));
+        }
+    }
+
+    #[test]
+    fn min_width() {
+        let mut buffer = UintBuffer::new();
+
+        // Padding after a wider integer has been in the buffer.
+        assert_eq!(encode_uint(u128::MAX, &mut buffer).len(), 32);
+        assert_eq!(
+            UintOptions::new()
+                .min_width(32)
+                .encode(0xff_u8, &mut buffer),
+            format!("{:032x}", 0xff)
+        );
+
+        let options = UintOptions::new().trim(true).min_width(3);
+        assert_eq!(options.encode(0_u16, &mut buffer), "000");
+        assert_eq!(options.encode(0xabcd_u16, &mut buffer), "abcd");
+    }
+
+    #[test]
+    fn decode_uint_matches_from_str_radix() {
+        for value in [0, 1, 0xf, 0x10, 0xdead_beef, u64::MAX >> 4, u64::MAX] {
+            assert_eq!(decode_uint::<u64>(format!("{value:x}")), Ok(value));
+            assert_eq!(decode_uint::<u64>(format!("{value:#034X}")), Ok(value));
+
+            let expected = u32::try_from(value).map_err(|_| DecodeError::Overflow);
+            assert_eq!(decode_uint::<u32>(format!("{value:#x}")), expected);
+        }
+
+        assert_eq!(decode_uint::<u128>("f".repeat(32)), Ok(u128::MAX));
+        assert_eq!(
+            decode_uint::<u128>("1".repeat(33)),
+            Err(DecodeError::Overflow)
+        );
+        assert_eq!(
+            decode_uint::<u8>("0xx"),
+            Err(DecodeError::InvalidHex {
+                offset: 2,
+                value: b'x'
+            })
+        );
+    }
+
+    #[test]
+    fn encode_words_matches_encode() {
+        let words: std::vec::Vec<u64> = (0..5_u64)
+            .map(|it| it.wrapping_mul(0x9d8c_7b6a_5948_3726))
+            .collect();
+
+        let be: std::vec::Vec<u8> = words.iter().flat_map(|it| it.to_be_bytes()).collect();
+        let le: std::vec::Vec<u8> = words.iter().flat_map(|it| it.to_le_bytes()).collect();
+
+        assert_eq!(encode_words(&words, Endian::Big, None), crate::encode(&be));
+        assert_eq!(
+            encode_words(&words, Endian::Little, None),
+            crate::encode(&le)
+        );
+
+        let output = encode_words(&words, Endian::Little, Some('·'));
+        let expected: std::vec::Vec<_> = le.chunks(8).map(crate::encode).collect();
+        assert_eq!(output, expected.join("·"));
+        assert_eq!(output.capacity(), output.len());
+
+        assert_eq!(encode_words::<u32>(&[], Endian::Big, Some(' ')), "");
+    }
+}
@@ -0,0 +1,237 @@
+//! UUIDs, in their usual (8-4-4-4-12) text form.
+//!
+//! This only handles the text, for generating or inspecting UUIDs, see the `uuid` crate.
+//!
+//! # Examples
+//! ```
+//! let id = hector::uuid::decode_uuid("{67e55044-10b1-426f-9247-bb680e5fe0c8}")?;
+//!
+//! assert_eq!(hector::uuid::encode_uuid(&id), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+//! # Ok::<(), hector::DecodeError>(())
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::DecodeError;
+
+/// The length of a hyphenated UUID.
+const HYPHENATED_LEN: usize = 36;
+
+/// The offsets of the hyphens in a hyphenated UUID.
+const HYPHENS: [usize; 4] = [8, 13, 18, 23];
+
+/// Encode `uuid` with hyphens between each group.
+fn hyphenated<const UPPER: bool>(uuid: &[u8; 16]) -> [u8; HYPHENATED_LEN] {
+    let mut output = [b'-'; HYPHENATED_LEN];
+
+    let mut offset = 0;
+    for (index, &byte) in uuid.iter().enumerate() {
+        // Skipping over the hyphen before each group.
+        if matches!(index, 4 | 6 | 8 | 10) {
+            offset += 1;
+        }
+
+        if let Some(pair) = output.get_mut(offset..offset + 2) {
+            pair.copy_from_slice(&crate::encode::byte_to_hex::<UPPER>(byte));
+        }
+
+        offset += 2;
+    }
+
+    output
+}
+
+/// Encode `uuid` in lowercase, with hyphens (`67e55044-10b1-426f-9247-bb680e5fe0c8`).
+///
+/// # Examples
+/// ```
+/// let id = [0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0, 0xc8];
+///
+/// assert_eq!(hector::uuid::encode_uuid(&id), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_uuid(uuid: &[u8; 16]) -> String {
+    String::from(crate::output::hex_str(&hyphenated::<false>(uuid)))
+}
+
+/// Encode `uuid` in uppercase, with hyphens (`67E55044-10B1-426F-9247-BB680E5FE0C8`).
+///
+/// # Examples
+/// ```
+/// let id = [0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0, 0xc8];
+///
+/// assert_eq!(hector::uuid::encode_uuid_upper(&id), "67E55044-10B1-426F-9247-BB680E5FE0C8");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_uuid_upper(uuid: &[u8; 16]) -> String {
+    String::from(crate::output::hex_str(&hyphenated::<true>(uuid)))
+}
+
+/// Write `uuid` to `output` in lowercase, with hyphens.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// struct RequestId([u8; 16]);
+///
+/// impl core::fmt::Display for RequestId {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         hector::uuid::encode_uuid_to_fmt(&self.0, f)
+///     }
+/// }
+///
+/// assert_eq!(RequestId([0; 16]).to_string(), "00000000-0000-0000-0000-000000000000");
+/// ```
+pub fn encode_uuid_to_fmt<W: core::fmt::Write + ?Sized>(
+    uuid: &[u8; 16],
+    output: &mut W,
+) -> core::fmt::Result {
+    output.write_str(crate::output::hex_str(&hyphenated::<false>(uuid)))
+}
+
+/// Write `uuid` to `output` in uppercase, with hyphens.
+///
+/// # Errors
+/// - [`fmt::Error`](core::fmt::Error) if writing to `output` fails.
+///
+/// # Examples
+/// ```
+/// let mut output = String::new();
+///
+/// hector::uuid::encode_uuid_to_fmt_upper(&[0xff; 16], &mut output)?;
+/// assert_eq!(output, "FFFFFFFF-FFFF-FFFF-FFFF-FFFFFFFFFFFF");
+/// # Ok::<(), core::fmt::Error>(())
+/// ```
+pub fn encode_uuid_to_fmt_upper<W: core::fmt::Write + ?Sized>(
+    uuid: &[u8; 16],
+    output: &mut W,
+) -> core::fmt::Result {
+    output.write_str(crate::output::hex_str(&hyphenated::<true>(uuid)))
+}
+
+/// Check the hyphens of a hyphenated UUID, collecting the hex characters between them.
+fn strip_hyphens(input: &[u8; HYPHENATED_LEN]) -> Result<[u8; 32], DecodeError> {
+    let mut hex = [0; 32];
+    let mut output = hex.iter_mut();
+
+    for (offset, &value) in input.iter().enumerate() {
+        if HYPHENS.contains(&offset) {
+            if value != b'-' {
+                return Err(DecodeError::InvalidHex { offset, value });
+            }
+        } else if let Some(output) = output.next() {
+            *output = value;
+        }
+    }
+
+    Ok(hex)
+}
+
+/// Decode a UUID, with or without hyphens, optionally in braces, in either case.
+///
+/// # Errors
+/// - [`DecodeError::InvalidHex`] if a character isn't a valid hex character,
+///   or a hyphen (or brace) is missing or out of place, the offset is into `input`.
+/// - [`DecodeError::MismatchedLength`] if `input` isn't 32 (or 36 with hyphens) characters, not counting braces,
+///   `source_len` is the length of `input` (again, not counting braces).
+///
+/// # Examples
+/// ```
+/// use hector::uuid::decode_uuid;
+///
+/// let id = [0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0, 0xc8];
+///
+/// assert_eq!(decode_uuid("67e55044-10b1-426f-9247-bb680e5fe0c8"), Ok(id));
+/// assert_eq!(decode_uuid("67E55044-10B1-426F-9247-BB680E5FE0C8"), Ok(id));
+/// assert_eq!(decode_uuid("67e5504410b1426f9247bb680e5fe0c8"), Ok(id));
+/// assert_eq!(decode_uuid("{67e55044-10b1-426f-9247-bb680e5fe0c8}"), Ok(id));
+/// ```
+///
+/// ```
+/// use hector::{uuid::decode_uuid, DecodeError};
+///
+/// assert_eq!(
+///     decode_uuid("67e55044-10b1-426f-9247_bb680e5fe0c8"),
+///     Err(DecodeError::InvalidHex { offset: 23, value: b'_' })
+/// );
+/// assert_eq!(
+///     decode_uuid("{67e55044-10b1-426f-9247-bb680e5fe0cg}"),
+///     Err(DecodeError::InvalidHex { offset: 36, value: b'g' })
+/// );
+/// assert_eq!(decode_uuid("67e55044"), Err(DecodeError::MismatchedLength { source_len: 8, dest_len: 16 }));
+/// ```
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub fn decode_uuid<T: AsRef<[u8]>>(input: T) -> Result<[u8; 16], DecodeError> {
+    let input = input.as_ref();
+
+    let (inner, base) = match input {
+        [b'{', inner @ .., b'}'] => (inner, 1),
+        _ => (input, 0),
+    };
+
+    if let Ok(hyphenated) = <&[u8; HYPHENATED_LEN]>::try_from(inner) {
+        let hex = strip_hyphens(hyphenated).map_err(|err| err.offset_by(base))?;
+
+        return crate::decode_to_array(hex).map_err(|err| match err {
+            // Offsets into `hex` skip the hyphens before them.
+            DecodeError::InvalidHex { offset, value } => DecodeError::InvalidHex {
+                offset: HYPHENS.iter().fold(offset, |offset, &hyphen| {
+                    offset + usize::from(hyphen <= offset)
+                }),
+                value,
+            }
+            .offset_by(base),
+            err => err,
+        });
+    }
+
+    crate::decode_to_array(inner).map_err(|err| err.offset_by(base))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_uuid, encode_uuid, encode_uuid_upper};
+    use crate::DecodeError;
+
+    #[test]
+    fn round_trip() {
+        for uuid in [
+            [0; 16],
+            [0xff; 16],
+            core::array::from_fn(|it| it as u8 * 17),
+        ] {
+            assert_eq!(decode_uuid(encode_uuid(&uuid)), Ok(uuid));
+            assert_eq!(decode_uuid(encode_uuid_upper(&uuid)), Ok(uuid));
+            assert_eq!(decode_uuid(crate::encode(uuid)), Ok(uuid));
+        }
+    }
+
+    #[test]
+    fn offsets_count_hyphens() {
+        let valid = *b"67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+        for offset in 0..valid.len() {
+            let mut input = valid;
+            input[offset] = b'g';
+
+            assert_eq!(
+                decode_uuid(input),
+                Err(DecodeError::InvalidHex {
+                    offset,
+                    value: b'g'
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn unbalanced_braces() {
+        assert!(decode_uuid("{67e55044-10b1-426f-9247-bb680e5fe0c8").is_err());
+        assert!(decode_uuid("67e55044-10b1-426f-9247-bb680e5fe0c8}").is_err());
+    }
+}